@@ -19,31 +19,44 @@ use dyn_arb_bundles::{
             LayoutElementSizingMode, LayoutParentSizingMode, StaticLayoutElement,
             StaticLayoutParent, WindingRule,
         },
-        paints::{GradientColorStop, GradientVariant, ImageScaleMode},
+        nodes::BooleanOperation,
+        paints::{GradientColorStop, GradientVariant, ImageFilters, ImageScaleMode},
+        styles::{StrokeAlignment, StrokeCap, StrokeJoin},
     },
     events::{
-        CoreInputEvent, CreateAssetInputEvent, CreateNodeInputEvent, CreatePaintInputEvent,
-        DeleteEntityInputEvent, ExecuteLuaScriptInputEvent, FocusRootNodesInputEvent,
-        MoveEntityInputEvent, RegisterLuaScriptInputEvent, UpdateArtboardSizeInputEvent,
-        UpdateArtboardViewportInputEvent, UpdateDropShadowStyleInputEvent,
-        UpdateEllipseNodeInputEvent, UpdateEntityBlendModeInputEvent,
+        BindVariableInputEvent, CoreInputEvent, CreateAssetInputEvent, CreateNodeInputEvent,
+        CreatePaintInputEvent, CreateStyleInputEvent, CreateVariableInputEvent,
+        DeleteEntityInputEvent, DeleteStyleInputEvent, ExecuteLuaScriptInputEvent,
+        FocusRootNodesInputEvent, MoveEntityInputEvent, RegisterLuaScriptInputEvent,
+        ReorderStylesInputEvent, UpdateArtboardSizeInputEvent,
+        UpdateArtboardViewportInputEvent, UpdateBooleanOperationNodeInputEvent,
+        UpdateDropShadowStyleInputEvent, UpdateEllipseNodeInputEvent,
+        UpdateEntityBlendModeInputEvent,
         UpdateEntityChildrenInputEvent, UpdateEntityCornerRadiiInputEvent,
         UpdateEntityOpacityInputEvent, UpdateEntityRotationInputEvent, UpdateEntitySizeInputEvent,
         UpdateEntityTransformInputEvent, UpdateEntityVisibilityInputEvent,
         UpdateFillStyleInputEvent, UpdateFrameNodeInputEvent, UpdateGradientPaintInputEvent,
-        UpdateImagePaintInputEvent, UpdatePolygonNodeInputEvent, UpdateSolidPaintInputEvent,
-        UpdateStarNodeInputEvent, UpdateStorkeStyleInputEvent, UpdateTextNodeInputEvent,
+        UpdateImagePaintInputEvent, UpdateInnerShadowStyleInputEvent, UpdateLayerBlurStyleInputEvent,
+        UpdateBackgroundBlurStyleInputEvent, UpdateInstanceNodeInputEvent, UpdatePolygonNodeInputEvent,
+        UpdateSolidPaintInputEvent, UpdateStarNodeInputEvent, UpdateStorkeStyleInputEvent,
+        UpdateTextNodeInputEvent, UpdateVariableInputEvent, UpdateVariableModeInputEvent,
     },
     properties::{
-        AlignContent, AlignItems, AlignSelf, ArbVersion, FlexDirection, JustifyContent,
-        JustifyItems, JustifySelf, TextAttributeInterval, TextAttributes, Viewport,
+        AlignContent, AlignItems, AlignSelf, ArbVersion, Display, FlexDirection, FlexWrap,
+        GridLine, GridPlacement, GridTrack, InstanceOverride, JustifyContent, JustifyItems,
+        JustifySelf, TextAttributeInterval, TextAttributes, VariableProperty, VariableValue,
+        Viewport,
     },
     reference_id::{ReferenceId, ReferenceIdOrEntity, ReferenceIdOrImageId},
-    AssetWithId, DropShadowStyle, EllipseNode, FillStyle, FrameNode, GradientPaint, ImagePaint,
-    LuaScriptWithId, Node, Paint, PolygonNode, RectangleNode, SolidPaint, StarNode, StrokeStyle,
-    Style, TextNode, VectorNode,
+    AssetWithId, BackgroundBlurStyle, BooleanOperationNode, ComponentNode, DropShadowStyle,
+    EllipseNode, FillStyle, InnerShadowStyle, LayerBlurStyle,
+    FrameNode, GradientPaint, ImagePaint, InstanceNode, LuaScriptWithId, Node, Paint, PolygonNode,
+    RectangleNode, SolidPaint, StarNode, StrokeStyle, Style, TextNode, Variable, VariableBinding, VectorNode,
+};
+use dyn_arb_dtif::{
+    document::{DtifDocument, DtifDocumentArtboard},
+    DtifArtboard,
 };
-use dyn_arb_dtif::DtifArtboard;
 use dyn_utils::{
     properties::{color::Color, corner_radii::CornerRadii, opacity::Opacity},
     units::{
@@ -72,7 +85,8 @@ use utoipa::{OpenApi, ToSchema};
     ),
     paths(
         crate::routes::health::handler,
-        crate::routes::v1::arb::render::handler, 
+        crate::routes::v1::artboard::render::handler, 
+        crate::routes::v1::document::render::handler,
         crate::routes::v1::svg::simplify::handler, 
     ),
     components(
@@ -88,6 +102,8 @@ use utoipa::{OpenApi, ToSchema};
        // dyn_arb_dtif
 
         schemas(DtifArtboard),
+        schemas(DtifDocument),
+        schemas(DtifDocumentArtboard),
 
         // dyn_arb_bundles
 
@@ -100,6 +116,9 @@ use utoipa::{OpenApi, ToSchema};
         schemas(PolygonNode),
         schemas(TextNode),
         schemas(VectorNode),
+        schemas(BooleanOperationNode),
+        schemas(ComponentNode),
+        schemas(InstanceNode),
         schemas(Paint),
         schemas(SolidPaint),
         schemas(ImagePaint),
@@ -108,14 +127,22 @@ use utoipa::{OpenApi, ToSchema};
         schemas(FillStyle),
         schemas(StrokeStyle),
         schemas(DropShadowStyle),
+        schemas(InnerShadowStyle),
+        schemas(LayerBlurStyle),
+        schemas(BackgroundBlurStyle),
         schemas(AssetWithId),
         schemas(LuaScriptWithId),
+        schemas(Variable),
+        schemas(VariableBinding),
 
         // src/properties.rs
         schemas(ArbVersion),
         schemas(Viewport),
         schemas(TextAttributeInterval),
         schemas(TextAttributes),
+        schemas(InstanceOverride),
+        schemas(VariableValue),
+        schemas(VariableProperty),
         schemas(AlignItems),
         schemas(JustifyItems),
         schemas(AlignSelf),
@@ -123,6 +150,11 @@ use utoipa::{OpenApi, ToSchema};
         schemas(AlignContent),
         schemas(JustifyContent),
         schemas(FlexDirection),
+        schemas(FlexWrap),
+        schemas(Display),
+        schemas(GridTrack),
+        schemas(GridLine),
+        schemas(GridPlacement),
 
         // src/reference_id.rs
         schemas(ReferenceId),
@@ -140,14 +172,26 @@ use utoipa::{OpenApi, ToSchema};
         schemas(UpdateStarNodeInputEvent),
         schemas(UpdatePolygonNodeInputEvent),
         schemas(UpdateTextNodeInputEvent),
+        schemas(UpdateBooleanOperationNodeInputEvent),
+        schemas(UpdateInstanceNodeInputEvent),
+        schemas(CreateStyleInputEvent),
+        schemas(DeleteStyleInputEvent),
+        schemas(ReorderStylesInputEvent),
         schemas(UpdateFillStyleInputEvent),
         schemas(UpdateStorkeStyleInputEvent),
         schemas(UpdateDropShadowStyleInputEvent),
+        schemas(UpdateInnerShadowStyleInputEvent),
+        schemas(UpdateLayerBlurStyleInputEvent),
+        schemas(UpdateBackgroundBlurStyleInputEvent),
         schemas(CreatePaintInputEvent),
         schemas(UpdateSolidPaintInputEvent),
         schemas(UpdateImagePaintInputEvent),
         schemas(UpdateGradientPaintInputEvent),
         schemas(CreateAssetInputEvent),
+        schemas(CreateVariableInputEvent),
+        schemas(UpdateVariableInputEvent),
+        schemas(UpdateVariableModeInputEvent),
+        schemas(BindVariableInputEvent),
         schemas(DeleteEntityInputEvent),
         schemas(UpdateEntityTransformInputEvent),
         schemas(UpdateEntitySizeInputEvent),
@@ -173,8 +217,17 @@ use utoipa::{OpenApi, ToSchema};
         schemas(LayoutElementSizingMode),
         schemas(LayoutElement),
 
+        // src/components/nodes.rs
+        schemas(BooleanOperation),
+
+        // src/components/styles.rs
+        schemas(StrokeAlignment),
+        schemas(StrokeCap),
+        schemas(StrokeJoin),
+
         // src/components/paints.rs
         schemas(ImageScaleMode),
+        schemas(ImageFilters),
         schemas(GradientVariant),
         schemas(GradientColorStop),
 
@@ -5,3 +5,5 @@ pub mod environment;
 mod error;
 mod middlewares;
 mod routes;
+#[cfg(test)]
+mod test_utils;
@@ -1,10 +1,15 @@
 use crate::error::app_error::{AppError, AppErrorOptions, ErrorCode};
 use axum::{
-    extract::{rejection::JsonRejection, FromRequest, FromRequestParts, Request},
-    http::{request::Parts, StatusCode},
+    body::Bytes,
+    extract::{
+        rejection::{BytesRejection, JsonRejection},
+        FromRequest, FromRequestParts, Request,
+    },
+    http::{header, request::Parts, StatusCode},
     response::{IntoResponse, Response},
     Error, Json,
 };
+use dyn_arb_dtif::package::{DtifPackage, PackageError};
 use serde::de::DeserializeOwned;
 
 // Custom Query implementation to utilize 'serde_qs'
@@ -99,6 +104,77 @@ impl IntoResponse for AppJsonRejection {
     }
 }
 
+// Body extractor accepting a DTIF either as JSON
+// or as package ('application/zip' with 'document.json' and raw asset files)
+#[derive(Debug, Clone, Default)]
+pub struct AppDtif(DtifPackage);
+
+impl AppDtif {
+    pub fn get(self) -> DtifPackage {
+        self.0
+    }
+}
+
+#[axum::async_trait]
+impl<S> FromRequest<S> for AppDtif
+where
+    S: Send + Sync,
+{
+    type Rejection = AppDtifRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_package = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |value| value.starts_with("application/zip"));
+
+        if is_package {
+            let bytes = Bytes::from_request(req, state)
+                .await
+                .map_err(AppDtifRejection::FailedToBufferBody)?;
+            let package =
+                DtifPackage::from_bytes(&bytes).map_err(AppDtifRejection::InvalidPackage)?;
+            Ok(AppDtif(package))
+        } else {
+            let dtif = AppJson::<serde_json::Value>::from_request(req, state)
+                .await
+                .map_err(AppDtifRejection::InvalidJson)?
+                .get();
+            Ok(AppDtif(DtifPackage {
+                dtif,
+                ..Default::default()
+            }))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AppDtifRejection {
+    FailedToBufferBody(BytesRejection),
+    InvalidJson(AppJsonRejection),
+    InvalidPackage(PackageError),
+}
+
+impl IntoResponse for AppDtifRejection {
+    fn into_response(self) -> Response {
+        let description = match self {
+            Self::FailedToBufferBody(inner) => inner.body_text(),
+            Self::InvalidJson(inner) => return inner.into_response(),
+            Self::InvalidPackage(inner) => inner.to_string(),
+        };
+        AppError::new_with_options(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::new("INVALID_BODY"),
+            AppErrorOptions {
+                description: Some(description),
+                ..Default::default()
+            },
+        )
+        .into_response()
+    }
+}
+
 // pub type AppJsonBody<G> = Result<Json<G>, JsonRejection>;
 
 // pub fn extract_json_body<G>(app_json_body: AppJsonBody<G>) -> Result<G, AppError> {
@@ -0,0 +1,453 @@
+use super::render::FileFormat;
+use crate::error::app_error::{AppError, AppErrorOptions, ErrorCode};
+use axum::{
+    body::Body,
+    http::{header, StatusCode},
+    response::Response,
+};
+use bevy_app::App;
+use bevy_ecs::query::{With, Without};
+use dyn_arb_asset::asset::AssetContent;
+use dyn_arb_bundles::{components::marker::Root, AssetWithId};
+use dyn_arb_core::{resources::artboard::ArtboardRes, ArbCorePlugin};
+use dyn_arb_dtif::{
+    document::DtifDocument, migration::migrate, package::DtifPackage, validation::Diagnostic,
+    DtifArtboard,
+};
+use dyn_arb_svg_builder::{
+    events::SvgBuilderOutputEvent, svg::svg_bundle::SvgBundleVariant, ArbSvgBuilderPlugin,
+};
+use resvg::usvg::Options;
+use serde::de::DeserializeOwned;
+use std::{collections::HashMap, sync::mpsc::channel};
+use usvg::WriteOptions;
+
+pub fn migrate_dtif<T: DeserializeOwned>(mut raw_dtif: serde_json::Value) -> Result<T, AppError> {
+    migrate(&mut raw_dtif).map_err(|err| {
+        AppError::new_with_options(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::new("MIGRATE_DTIF"),
+            AppErrorOptions {
+                description: Some(err.to_string()),
+                ..Default::default()
+            },
+        )
+    })?;
+
+    return serde_json::from_value(raw_dtif).map_err(|err| {
+        AppError::new_with_options(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::new("INVALID_BODY"),
+            AppErrorOptions {
+                description: Some(format!(
+                    "Failed to deserialize the JSON body into the target type: {err}"
+                )),
+                ..Default::default()
+            },
+        )
+    });
+}
+
+/// Replaces references to files of the DTIF package with their binary content.
+pub fn resolve_package_assets(
+    dtif_package: &DtifPackage,
+    assets: &mut [AssetWithId],
+) -> Result<(), AppError> {
+    return dtif_package.resolve_assets(assets).map_err(|err| {
+        AppError::new_with_options(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::new("INVALID_BODY"),
+            AppErrorOptions {
+                description: Some(err.to_string()),
+                ..Default::default()
+            },
+        )
+    });
+}
+
+pub fn validate_dtif_artboard(dtif_artboard: &DtifArtboard) -> Result<(), AppError> {
+    return diagnostics_to_result(dyn_arb_dtif::validate(dtif_artboard));
+}
+
+/// Validates the artboards with the given indices of the document at once
+/// and reports the problems of all of them.
+pub fn validate_dtif_document(
+    dtif_document: &DtifDocument,
+    indices: &[usize],
+) -> Result<(), AppError> {
+    return diagnostics_to_result(dyn_arb_dtif::validate_document(dtif_document, indices));
+}
+
+fn diagnostics_to_result(diagnostics: Vec<Diagnostic>) -> Result<(), AppError> {
+    if !diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+        return Ok(());
+    }
+
+    let additional_errors = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            HashMap::from([
+                (
+                    String::from("path"),
+                    serde_json::Value::from(diagnostic.path.clone()),
+                ),
+                (
+                    String::from("severity"),
+                    serde_json::Value::from(diagnostic.severity.as_str()),
+                ),
+                (
+                    String::from("code"),
+                    serde_json::Value::from(diagnostic.code.as_str()),
+                ),
+                (
+                    String::from("message"),
+                    serde_json::Value::from(diagnostic.message.clone()),
+                ),
+            ])
+        })
+        .collect();
+
+    return Err(AppError::new_with_options(
+        StatusCode::BAD_REQUEST,
+        ErrorCode::new("INVALID_DTIF"),
+        AppErrorOptions {
+            description: Some(format!(
+                "The provided DTIF is invalid and contains {} problem(s).",
+                diagnostics.len()
+            )),
+            additional_errors: Some(additional_errors),
+            ..Default::default()
+        },
+    ));
+}
+
+pub async fn prepare_dtif_artboard(dtif_artboard: &mut DtifArtboard) -> Result<(), AppError> {
+    return prepare_dtif_assets(&mut dtif_artboard.assets).await;
+}
+
+/// Fetches the content of all assets referenced by URL
+/// so that the world doesn't have to load them.
+pub async fn prepare_dtif_assets(assets: &mut [AssetWithId]) -> Result<(), AppError> {
+    for asset in assets.iter_mut() {
+        let mut maybe_content = None;
+        if let AssetContent::Url { url } = &asset.content {
+            maybe_content = Some(fetch_asset(url).await.map_err(|err| {
+                AppError::new_with_options(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ErrorCode::new("PREPARE_DTIF"),
+                    AppErrorOptions {
+                        description: Some(err.to_string()),
+                        ..Default::default()
+                    },
+                )
+            })?);
+        }
+        if let Some(content) = maybe_content {
+            asset.content = AssetContent::Binary { content }
+        }
+    }
+
+    return Ok(());
+}
+
+async fn fetch_asset(url: &str) -> Result<Vec<u8>, reqwest::Error> {
+    return Ok(reqwest::get(url).await?.bytes().await?.to_vec());
+}
+
+pub fn build_svg_string(mut dtif: DtifArtboard) -> Result<String, AppError> {
+    let mut app = App::new();
+
+    let (svg_builder_output_event_sender, _) = channel::<SvgBuilderOutputEvent>();
+
+    // Register plugins
+    app.add_plugins((
+        ArbCorePlugin {
+            version: dtif.version,
+            size: dtif.size,
+            viewport: dtif.viewport,
+            sub_pixel_layout: dtif.sub_pixel_layout,
+        },
+        ArbSvgBuilderPlugin {
+            output_event_sender: svg_builder_output_event_sender,
+        },
+    ));
+
+    dtif.send_into_world(&mut app.world);
+    app.update();
+
+    let mut result = String::new();
+    let arb_res = app
+        .world
+        .get_resource::<ArtboardRes>()
+        .ok_or(AppError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::new("CANVAS_RES_NOT_FOUND"),
+        ))?;
+
+    // Open SVG tag
+    result.push_str(&format!(
+        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\">",
+        arb_res.size.width(),
+        arb_res.size.height()
+    ));
+
+    let mut system_state: bevy_ecs::system::SystemState<(
+        bevy_ecs::system::Query<&SvgBundleVariant, With<Root>>,
+        bevy_ecs::system::Query<&SvgBundleVariant, Without<Root>>,
+    )> = bevy_ecs::system::SystemState::new(&mut app.world);
+    let (root_bundle_variant_query, bundle_variant_query) = system_state.get(&mut app.world);
+
+    // Construct SVG string starting from root nodes
+    root_bundle_variant_query.iter().for_each(|bundle_variant| {
+        result.push_str(&bundle_variant.to_string(&bundle_variant_query))
+    });
+
+    // Close the SVG tag
+    result.push_str("</svg>");
+
+    return Ok(result);
+}
+
+pub fn parse_svg_tree(svg_string: &str) -> Result<usvg::Tree, AppError> {
+    // HTML content (e.g. of background blur styles) is only rendered by browsers
+    // and thus skipped, rendering the affected nodes without the effect
+    if svg_string.contains("<foreignObject") {
+        log::warn!("SVG contains HTML content (e.g. background blur) that can only be rendered by browsers and is skipped!");
+    }
+
+    let opts = Options::default();
+    return usvg::Tree::from_str(svg_string, &opts).map_err(|err| {
+        AppError::new_with_options(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::new("PARSE_SVG"),
+            AppErrorOptions {
+                description: Some(err.to_string()),
+                ..Default::default()
+            },
+        )
+    });
+}
+
+pub fn render_png(tree: &usvg::Tree) -> Result<Vec<u8>, AppError> {
+    let pixmap_size = tree.size().to_int_size();
+    let mut pixmap =
+        tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height()).ok_or(AppError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::new("RENDER_PNG"),
+        ))?;
+    resvg::render(tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    return pixmap.encode_png().map_err(|err| {
+        AppError::new_with_options(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::new("RENDER_PNG"),
+            AppErrorOptions {
+                description: Some(err.to_string()),
+                ..Default::default()
+            },
+        )
+    });
+}
+
+pub fn render_pdf(tree: &usvg::Tree) -> Vec<u8> {
+    return svg2pdf::to_pdf(
+        tree,
+        svg2pdf::ConversionOptions::default(),
+        svg2pdf::PageOptions::default(),
+    );
+}
+
+pub fn build_response(svg_string: String, format: FileFormat) -> Result<Response, AppError> {
+    match format {
+        FileFormat::Png => {
+            let tree = parse_svg_tree(&svg_string)?;
+            let png_data = render_png(&tree)?;
+
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "image/png")
+                .body(Body::from(png_data))
+                .unwrap())
+        }
+        FileFormat::Svg => {
+            let tree = parse_svg_tree(&svg_string)?;
+
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "image/svg+xml")
+                .body(Body::from(
+                    tree.to_string(&WriteOptions::default()).into_bytes(),
+                ))
+                .unwrap())
+        }
+        FileFormat::RawSvg => Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "image/svg+xml")
+            .body(Body::from(svg_string.into_bytes()))
+            .unwrap()),
+        FileFormat::Pdf => {
+            let tree = parse_svg_tree(&svg_string)?;
+            let pdf_data = render_pdf(&tree);
+
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "application/pdf")
+                .body(Body::from(pdf_data))
+                .unwrap())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::reference_id;
+    use dyn_arb_bundles::properties::ArbVersion;
+    use dyn_arb_dtif::document::DtifDocument;
+
+    /// Builds a 100x100 artboard with a white background and the given nodes
+    /// painted black by referencing the "black" paint.
+    fn artboard(nodes: serde_json::Value) -> DtifArtboard {
+        let nodes = nodes.as_array().unwrap();
+        let children: Vec<serde_json::Value> = nodes
+            .iter()
+            .map(|node| node["id"].clone())
+            .map(|id| reference_id(id.as_str().unwrap()))
+            .collect();
+        let mut all_nodes = vec![serde_json::json!({
+            "type": "Frame", "id": "root", "size": [100, 100],
+            "styles": [{ "type": "Fill", "paintId": reference_id("white") }],
+            "children": children
+        })];
+        all_nodes.extend(nodes.iter().cloned());
+
+        return serde_json::from_value(serde_json::json!({
+            "size": [100, 100],
+            "nodes": all_nodes,
+            "paints": [
+                { "type": "Solid", "id": "white", "color": [255, 255, 255] },
+                { "type": "Solid", "id": "black", "color": [0, 0, 0] }
+            ]
+        }))
+        .unwrap();
+    }
+
+    fn render(svg_string: &str) -> tiny_skia::Pixmap {
+        let tree = parse_svg_tree(svg_string).unwrap();
+        let mut pixmap = tiny_skia::Pixmap::new(100, 100).unwrap();
+        resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+        return pixmap;
+    }
+
+    /// Returns the red channel of the pixel, which is 255 for white and 0 for black.
+    fn red(pixmap: &tiny_skia::Pixmap, x: u32, y: u32) -> u8 {
+        return pixmap.pixel(x, y).unwrap().demultiply().red();
+    }
+
+    #[test]
+    fn test_migrate_dtif() {
+        let dtif: DtifArtboard =
+            migrate_dtif(serde_json::json!({ "size": [100, 100], "nodes": [] })).unwrap();
+        assert_eq!(dtif.version, Some(ArbVersion::LATEST));
+
+        let document: DtifDocument = migrate_dtif(serde_json::json!({
+            "artboards": [{ "size": [100, 100], "nodes": [] }]
+        }))
+        .unwrap();
+        assert_eq!(document.version, Some(ArbVersion::LATEST));
+
+        let err = migrate_dtif::<DtifArtboard>(serde_json::json!({
+            "version": "V999999",
+            "size": [100, 100],
+            "nodes": []
+        }))
+        .unwrap_err();
+        assert_eq!(err.status, 400);
+        assert_eq!(
+            serde_json::to_value(&err.code).unwrap(),
+            serde_json::json!("#ERR_MIGRATE_DTIF")
+        );
+    }
+
+    #[test]
+    fn test_render_layer_blur() {
+        let svg_string = build_svg_string(artboard(serde_json::json!([
+            { "type": "Rectangle", "id": "rect", "translation": [40, 40], "size": [20, 20],
+                "styles": [
+                    { "type": "Fill", "paintId": reference_id("black") },
+                    { "type": "LayerBlur", "blur": 4 }
+                ] }
+        ])))
+        .unwrap();
+        let pixmap = render(&svg_string);
+
+        // Blurred edges bleed out of the translated rectangle without being cut off
+        assert!(red(&pixmap, 50, 50) < 64);
+        assert!(red(&pixmap, 37, 50) < 255);
+        assert!(red(&pixmap, 50, 63) < 255);
+        assert_eq!(red(&pixmap, 20, 50), 255);
+    }
+
+    #[test]
+    fn test_render_layer_blur_of_line() {
+        let svg_string = build_svg_string(artboard(serde_json::json!([
+            { "type": "Vector", "id": "line", "path": "M 0 0 L 60 0", "windingRule": "Nonzero",
+                "translation": [20, 50], "size": [60, 0],
+                "styles": [
+                    { "type": "Stroke", "width": 4, "paintId": reference_id("black") },
+                    { "type": "LayerBlur", "blur": 2 }
+                ] }
+        ])))
+        .unwrap();
+        assert!(!svg_string.contains("NaN"));
+        assert!(!svg_string.contains("inf"));
+
+        let pixmap = render(&svg_string);
+        // The stroke covers the rows 48 to 51, the blurred edges bleed out of it
+        assert!(red(&pixmap, 50, 50) < 128);
+        assert!(red(&pixmap, 50, 47) < 255);
+        assert!(red(&pixmap, 50, 52) < 255);
+        assert!(red(&pixmap, 19, 50) < 255);
+        assert_eq!(red(&pixmap, 50, 70), 255);
+    }
+
+    #[test]
+    fn test_render_inner_shadow() {
+        let svg_string = build_svg_string(artboard(serde_json::json!([
+            { "type": "Rectangle", "id": "rect", "translation": [20, 20], "size": [60, 60],
+                "styles": [
+                    { "type": "InnerShadow", "color": [0, 0, 0], "position": [4, 4], "blur": 2 },
+                    { "type": "Fill", "paintId": reference_id("white") }
+                ] }
+        ])))
+        .unwrap();
+        let pixmap = render(&svg_string);
+
+        // The shadow darkens the inner top left edge only
+        assert!(red(&pixmap, 21, 50) < 128);
+        assert!(red(&pixmap, 50, 21) < 128);
+        assert_eq!(red(&pixmap, 50, 50), 255);
+        assert_eq!(red(&pixmap, 78, 50), 255);
+        assert_eq!(red(&pixmap, 10, 50), 255);
+    }
+
+    #[test]
+    fn test_render_background_blur() {
+        let svg_string = build_svg_string(artboard(serde_json::json!([
+            { "type": "Rectangle", "id": "glass", "translation": [25, 25], "size": [50, 50],
+                "styles": [{ "type": "BackgroundBlur", "blur": 8 }] },
+            { "type": "Rectangle", "id": "behind", "size": [50, 100],
+                "styles": [{ "type": "Fill", "paintId": reference_id("black") }] }
+        ])))
+        .unwrap();
+        let pixmap = render(&svg_string);
+
+        // The edge of the node behind is blurred within the shape of the glass only
+        assert!(red(&pixmap, 48, 50) > 0);
+        assert!(red(&pixmap, 52, 50) < 255);
+        assert_eq!(red(&pixmap, 48, 10), 0);
+        assert_eq!(red(&pixmap, 52, 10), 255);
+        assert_eq!(red(&pixmap, 10, 50), 0);
+        assert_eq!(red(&pixmap, 90, 50), 255);
+    }
+}
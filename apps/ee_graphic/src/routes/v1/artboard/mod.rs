@@ -1,4 +1,5 @@
 pub mod _template_id;
+pub mod helper;
 pub mod render;
 
 use crate::environment::app_state::AppState;
@@ -1,32 +1,22 @@
+use super::helper::{
+    build_response, build_svg_string, migrate_dtif, prepare_dtif_artboard, resolve_package_assets,
+    validate_dtif_artboard,
+};
 use crate::{
     environment::app_state::AppState,
-    error::app_error::{AppError, AppErrorOptions, ErrorCode},
-    middlewares::extract::{AppJson, AppQuery},
-};
-use axum::{
-    body::Body,
-    http::{header, StatusCode},
-    response::Response,
+    error::app_error::AppError,
+    middlewares::extract::{AppDtif, AppQuery},
 };
+use axum::response::Response;
 use axum::{routing::post, Router};
-use bevy_app::App;
-use bevy_ecs::query::{With, Without};
-use dyn_arb_asset::asset::AssetContent;
 use dyn_arb_bundles::{
-    components::marker::Root,
     events::{CoreInputEvent, ExecuteLuaScriptInputEvent},
     reference_id::ReferenceId,
 };
-use dyn_arb_core::{resources::artboard::ArtboardRes, ArbCorePlugin};
 use dyn_arb_dtif::DtifArtboard;
 use dyn_arb_lua::tables::args_table::LuaScriptArgsMap;
-use dyn_arb_svg_builder::{
-    events::SvgBuilderOutputEvent, svg::svg_bundle::SvgBundleVariant, ArbSvgBuilderPlugin,
-};
-use resvg::usvg::Options;
 use serde::Deserialize;
-use std::{collections::HashMap, sync::mpsc::channel};
-use usvg::WriteOptions;
+use std::collections::HashMap;
 
 pub fn router() -> Router<AppState> {
     Router::new().route("/", post(handler))
@@ -65,13 +55,17 @@ pub enum FileFormat {
 #[axum::debug_handler]
 async fn handler(
     app_query: AppQuery<QueryParams>,
-    app_body: AppJson<DtifArtboard>,
+    app_body: AppDtif,
 ) -> Result<Response, AppError> {
     let QueryParams {
         format: maybe_format,
         script_args: maybe_script_args,
     } = app_query.get();
-    let mut dtif = app_body.get();
+    let mut dtif_package = app_body.get();
+    let mut dtif: DtifArtboard = migrate_dtif(std::mem::take(&mut dtif_package.dtif))?;
+    resolve_package_assets(&dtif_package, &mut dtif.assets)?;
+
+    validate_dtif_artboard(&dtif)?;
 
     if let Some(script_args) = maybe_script_args {
         for (id, args) in script_args {
@@ -84,136 +78,9 @@ async fn handler(
         }
     }
 
-    prepare_dtif_artboard(&mut dtif).await.map_err(|err| {
-        AppError::new_with_options(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            ErrorCode::new("PREPARE_DTIF"),
-            AppErrorOptions {
-                description: Some(err.to_string()),
-                ..Default::default()
-            },
-        )
-    })?;
+    prepare_dtif_artboard(&mut dtif).await?;
     let svg_string = build_svg_string(dtif)?;
 
     // Determine response format from query parameter
-    match maybe_format.unwrap_or(FileFormat::Png) {
-        FileFormat::Png => {
-            let opts = Options::default();
-
-            let tree = usvg::Tree::from_str(&svg_string, &opts).unwrap();
-
-            let pixmap_size = tree.size().to_int_size();
-            let mut pixmap =
-                tiny_skia::Pixmap::new(pixmap_size.width(), pixmap_size.height()).unwrap();
-            resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
-            let png_data = pixmap.encode_png().unwrap();
-
-            Ok(Response::builder()
-                .status(StatusCode::OK)
-                .header(header::CONTENT_TYPE, "image/png")
-                .body(Body::from(png_data))
-                .unwrap())
-        }
-        FileFormat::Svg => {
-            let opts = Options::default();
-
-            let tree = usvg::Tree::from_str(&svg_string, &opts).unwrap();
-
-            Ok(Response::builder()
-                .status(StatusCode::OK)
-                .header(header::CONTENT_TYPE, "image/svg+xml")
-                .body(Body::from(
-                    tree.to_string(&WriteOptions::default()).into_bytes(),
-                ))
-                .unwrap())
-        }
-        FileFormat::RawSvg => Ok(Response::builder()
-            .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, "image/svg+xml")
-            .body(Body::from(svg_string.into_bytes()))
-            .unwrap()),
-        FileFormat::Pdf => {
-            let opts = Options::default();
-            let tree = usvg::Tree::from_str(&svg_string, &opts).unwrap();
-            let pdf_data = svg2pdf::to_pdf(
-                &tree,
-                svg2pdf::ConversionOptions::default(),
-                svg2pdf::PageOptions::default(),
-            );
-
-            Ok(Response::builder()
-                .status(StatusCode::OK)
-                .header(header::CONTENT_TYPE, "application/pdf")
-                .body(Body::from(pdf_data))
-                .unwrap())
-        }
-    }
-}
-
-async fn prepare_dtif_artboard(dtif_artboard: &mut DtifArtboard) -> Result<(), reqwest::Error> {
-    for asset in dtif_artboard.assets.iter_mut() {
-        let mut maybe_content = None;
-        if let AssetContent::Url { url } = &asset.content {
-            maybe_content = Some(reqwest::get(url).await?.bytes().await?.to_vec());
-        }
-        if let Some(content) = maybe_content {
-            asset.content = AssetContent::Binary { content }
-        }
-    }
-
-    return Ok(());
-}
-
-fn build_svg_string(mut dtif: DtifArtboard) -> Result<String, AppError> {
-    let mut app = App::new();
-
-    let (svg_builder_output_event_sender, _) = channel::<SvgBuilderOutputEvent>();
-
-    // Register plugins
-    app.add_plugins((
-        ArbCorePlugin {
-            version: dtif.version,
-            size: dtif.size,
-            viewport: dtif.viewport,
-        },
-        ArbSvgBuilderPlugin {
-            output_event_sender: svg_builder_output_event_sender,
-        },
-    ));
-
-    dtif.send_into_world(&mut app.world);
-    app.update();
-
-    let mut result = String::new();
-    let arb_res = app
-        .world
-        .get_resource::<ArtboardRes>()
-        .ok_or(AppError::new(
-            StatusCode::INTERNAL_SERVER_ERROR,
-            ErrorCode::new("CANVAS_RES_NOT_FOUND"),
-        ))?;
-
-    // Open SVG tag
-    result.push_str(&format!(
-        "<svg width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\">",
-        arb_res.size.width(),
-        arb_res.size.height()
-    ));
-
-    let mut system_state: bevy_ecs::system::SystemState<(
-        bevy_ecs::system::Query<&SvgBundleVariant, With<Root>>,
-        bevy_ecs::system::Query<&SvgBundleVariant, Without<Root>>,
-    )> = bevy_ecs::system::SystemState::new(&mut app.world);
-    let (root_bundle_variant_query, bundle_variant_query) = system_state.get(&mut app.world);
-
-    // Construct SVG string starting from root nodes
-    root_bundle_variant_query.iter().for_each(|bundle_variant| {
-        result.push_str(&bundle_variant.to_string(&bundle_variant_query))
-    });
-
-    // Close the SVG tag
-    result.push_str("</svg>");
-
-    return Ok(result);
+    return build_response(svg_string, maybe_format.unwrap_or(FileFormat::Png));
 }
@@ -0,0 +1,449 @@
+use crate::{
+    environment::app_state::AppState,
+    error::app_error::{AppError, AppErrorOptions, ErrorCode},
+    middlewares::extract::{AppDtif, AppQuery},
+    routes::v1::artboard::{
+        helper::{
+            build_response, build_svg_string, migrate_dtif, parse_svg_tree, prepare_dtif_assets,
+            render_png, resolve_package_assets, validate_dtif_document,
+        },
+        render::FileFormat,
+    },
+};
+use axum::{
+    body::Body,
+    http::{header, StatusCode},
+    response::Response,
+};
+use axum::{routing::post, Router};
+use dyn_arb_bundles::{
+    events::{CoreInputEvent, ExecuteLuaScriptInputEvent},
+    reference_id::ReferenceId,
+};
+use dyn_arb_dtif::{
+    document::{DtifDocument, DtifDocumentArtboard},
+    DtifArtboard,
+};
+use dyn_arb_lua::tables::args_table::LuaScriptArgsMap;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::{Cursor, Write},
+};
+use usvg::WriteOptions;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", post(handler))
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+struct QueryParams {
+    format: Option<FileFormat>,
+    /// Name or index of the artboard to render.
+    /// If not specified, all artboards are rendered.
+    artboard: Option<String>,
+    #[serde(rename(deserialize = "s"))]
+    script_args: Option<HashMap<String, LuaScriptArgsMap>>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/document/render",
+    operation_id = "post_v1_document_render_handler",
+    params(
+        QueryParams,
+    ),
+    request_body = DtifDocument,
+    responses(
+        (status = 200, description = "Generation success (multi-page PDF or ZIP if multiple artboards are rendered)", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = AppError)
+    ),
+)]
+#[axum::debug_handler]
+async fn handler(
+    app_query: AppQuery<QueryParams>,
+    app_body: AppDtif,
+) -> Result<Response, AppError> {
+    let QueryParams {
+        format: maybe_format,
+        artboard: maybe_artboard,
+        script_args: maybe_script_args,
+    } = app_query.get();
+    let mut dtif_package = app_body.get();
+    let mut dtif_document: DtifDocument = migrate_dtif(std::mem::take(&mut dtif_package.dtif))?;
+    resolve_package_assets(&dtif_package, &mut dtif_document.assets)?;
+
+    let indices = select_artboards(&dtif_document, maybe_artboard)?;
+
+    // Fetch shared assets only once for all artboards
+    prepare_dtif_assets(&mut dtif_document.assets).await?;
+
+    return render_artboards(
+        &dtif_document,
+        &indices,
+        maybe_format.unwrap_or(FileFormat::Png),
+        maybe_script_args,
+    );
+}
+
+/// Determines the indices of the artboards to render
+/// from the name or index of an artboard or, if not specified, all artboards.
+fn select_artboards(
+    dtif_document: &DtifDocument,
+    maybe_selector: Option<String>,
+) -> Result<Vec<usize>, AppError> {
+    let indices: Vec<usize> = match maybe_selector {
+        Some(selector) => vec![dtif_document
+            .find_artboard_index(&selector)
+            .ok_or_else(|| {
+                AppError::new_with_options(
+                    StatusCode::BAD_REQUEST,
+                    ErrorCode::new("ARTBOARD_NOT_FOUND"),
+                    AppErrorOptions {
+                        description: Some(format!(
+                            "The document contains no artboard named '{selector}' or at index '{selector}'."
+                        )),
+                        ..Default::default()
+                    },
+                )
+            })?],
+        None => (0..dtif_document.artboards.len()).collect(),
+    };
+    if indices.is_empty() {
+        return Err(AppError::new_with_options(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::new("EMPTY_DOCUMENT"),
+            AppErrorOptions {
+                description: Some(String::from("The document contains no artboards.")),
+                ..Default::default()
+            },
+        ));
+    }
+
+    return Ok(indices);
+}
+
+/// Renders the artboards with the given indices into a multi-page PDF
+/// or a ZIP with one file per artboard, or like the artboard render route if only one is selected.
+fn render_artboards(
+    dtif_document: &DtifDocument,
+    indices: &[usize],
+    format: FileFormat,
+    maybe_script_args: Option<HashMap<String, LuaScriptArgsMap>>,
+) -> Result<Response, AppError> {
+    validate_dtif_document(dtif_document, indices)?;
+
+    let mut dtif_artboards: Vec<(usize, DtifArtboard)> = indices
+        .iter()
+        .filter_map(|index| Some((*index, dtif_document.to_artboard(*index)?)))
+        .collect();
+
+    if let Some(script_args) = maybe_script_args {
+        for (_, dtif_artboard) in dtif_artboards.iter_mut() {
+            for (id, args) in script_args.iter() {
+                dtif_artboard.events.push(CoreInputEvent::ExecuteLuaScript(
+                    ExecuteLuaScriptInputEvent {
+                        id: ReferenceId::new(id.clone()),
+                        args_map: args.clone(),
+                    },
+                ))
+            }
+        }
+    }
+
+    // Respond like the artboard render route if only one artboard was selected
+    if dtif_artboards.len() == 1 {
+        if let Some((_, dtif_artboard)) = dtif_artboards.pop() {
+            return build_response(build_svg_string(dtif_artboard)?, format);
+        }
+    }
+
+    let mut svg_strings: Vec<(String, String)> = Vec::with_capacity(dtif_artboards.len());
+    for (index, dtif_artboard) in dtif_artboards {
+        svg_strings.push((
+            to_file_stem(index, &dtif_document.artboards[index]),
+            build_svg_string(dtif_artboard)?,
+        ));
+    }
+
+    return match format {
+        FileFormat::Pdf => {
+            let mut trees = Vec::with_capacity(svg_strings.len());
+            for (_, svg_string) in svg_strings.iter() {
+                trees.push(parse_svg_tree(svg_string)?);
+            }
+
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "application/pdf")
+                .body(Body::from(build_multi_page_pdf(&trees)))
+                .unwrap())
+        }
+        FileFormat::Png | FileFormat::Svg | FileFormat::RawSvg => {
+            let mut files = Vec::with_capacity(svg_strings.len());
+            for (file_stem, svg_string) in svg_strings {
+                files.push(match format {
+                    FileFormat::Png => (
+                        format!("{file_stem}.png"),
+                        render_png(&parse_svg_tree(&svg_string)?)?,
+                    ),
+                    FileFormat::Svg => (
+                        format!("{file_stem}.svg"),
+                        parse_svg_tree(&svg_string)?
+                            .to_string(&WriteOptions::default())
+                            .into_bytes(),
+                    ),
+                    _ => (format!("{file_stem}.svg"), svg_string.into_bytes()),
+                });
+            }
+
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header(header::CONTENT_TYPE, "application/zip")
+                .body(Body::from(build_zip(files)?))
+                .unwrap())
+        }
+    };
+}
+
+fn to_file_stem(index: usize, artboard: &DtifDocumentArtboard) -> String {
+    return match &artboard.name {
+        Some(name) => {
+            let name: String = name
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{index}-{name}")
+        }
+        None => index.to_string(),
+    };
+}
+
+/// Builds a PDF with one page per SVG tree, each page sized like its artboard.
+fn build_multi_page_pdf(trees: &[usvg::Tree]) -> Vec<u8> {
+    let mut alloc = Ref::new(1);
+    let catalog_id = alloc.bump();
+    let page_tree_id = alloc.bump();
+    let svg_name = Name(b"S1");
+
+    let mut pdf = Pdf::new();
+    let mut page_ids = Vec::with_capacity(trees.len());
+
+    for tree in trees {
+        let page_id = alloc.bump();
+        let content_id = alloc.bump();
+        page_ids.push(page_id);
+
+        // Convert SVG into XObject and move its ids into our id range
+        let (svg_chunk, svg_id) = svg2pdf::to_chunk(tree, svg2pdf::ConversionOptions::default());
+        let mut id_map = HashMap::new();
+        let svg_chunk =
+            svg_chunk.renumber(|old| *id_map.entry(old).or_insert_with(|| alloc.bump()));
+        let svg_id = id_map.get(&svg_id).copied().unwrap_or(svg_id);
+
+        let width = tree.size().width();
+        let height = tree.size().height();
+
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, width, height));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        page.resources().x_objects().pair(svg_name, svg_id);
+        page.finish();
+
+        // The XObject is a unit square and thus has to be scaled to the page size
+        let mut content = Content::new();
+        content
+            .save_state()
+            .transform([width, 0.0, 0.0, height, 0.0, 0.0])
+            .x_object(svg_name)
+            .restore_state();
+        pdf.stream(content_id, &content.finish());
+
+        pdf.extend(&svg_chunk);
+    }
+
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    let page_count = page_ids.len() as i32;
+    pdf.pages(page_tree_id).kids(page_ids).count(page_count);
+
+    return pdf.finish();
+}
+
+fn build_zip(files: Vec<(String, Vec<u8>)>) -> Result<Vec<u8>, AppError> {
+    let to_app_error = |err: String| {
+        AppError::new_with_options(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::new("BUILD_ZIP"),
+            AppErrorOptions {
+                description: Some(err),
+                ..Default::default()
+            },
+        )
+    };
+
+    let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (file_name, data) in files {
+        zip_writer
+            .start_file(file_name, options)
+            .map_err(|err| to_app_error(err.to_string()))?;
+        zip_writer
+            .write_all(&data)
+            .map_err(|err| to_app_error(err.to_string()))?;
+    }
+
+    return Ok(zip_writer
+        .finish()
+        .map_err(|err| to_app_error(err.to_string()))?
+        .into_inner());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::reference_id;
+    use axum::body::to_bytes;
+    use zip::ZipArchive;
+
+    fn artboard(name: &str, width: u32, height: u32, paint_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "size": [width, height],
+            "nodes": [{ "type": "Frame", "id": format!("{name}-root"), "size": [width, height],
+                "styles": [{ "type": "Fill", "paintId": reference_id(paint_id) }] }]
+        })
+    }
+
+    fn document() -> DtifDocument {
+        return serde_json::from_value(serde_json::json!({
+            "artboards": [
+                artboard("square", 100, 100, "paint"),
+                artboard("wide banner", 200, 50, "paint"),
+                artboard("story", 50, 100, "paint")
+            ],
+            "paints": [{ "type": "Solid", "id": "paint", "color": [255, 0, 0] }]
+        }))
+        .unwrap();
+    }
+
+    async fn into_parts(response: Response) -> (String, Vec<u8>) {
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        return (content_type, body.to_vec());
+    }
+
+    /// Reads the width and height from the IHDR chunk of the PNG.
+    fn png_size(data: &[u8]) -> (u32, u32) {
+        assert_eq!(&data[..8], b"\x89PNG\r\n\x1a\n");
+        let width = u32::from_be_bytes(data[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(data[20..24].try_into().unwrap());
+        return (width, height);
+    }
+
+    #[test]
+    fn test_select_artboards() {
+        let document = document();
+
+        assert_eq!(select_artboards(&document, None).unwrap(), vec![0, 1, 2]);
+        assert_eq!(
+            select_artboards(&document, Some(String::from("story"))).unwrap(),
+            vec![2]
+        );
+        assert_eq!(
+            select_artboards(&document, Some(String::from("1"))).unwrap(),
+            vec![1]
+        );
+
+        let err = select_artboards(&document, Some(String::from("3"))).unwrap_err();
+        assert_eq!(err.status, 400);
+        assert_eq!(
+            serde_json::to_value(&err.code).unwrap(),
+            serde_json::json!("#ERR_ARTBOARD_NOT_FOUND")
+        );
+
+        let empty: DtifDocument =
+            serde_json::from_value(serde_json::json!({ "artboards": [] })).unwrap();
+        let err = select_artboards(&empty, None).unwrap_err();
+        assert_eq!(
+            serde_json::to_value(&err.code).unwrap(),
+            serde_json::json!("#ERR_EMPTY_DOCUMENT")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_multi_page_pdf() {
+        let response = render_artboards(&document(), &[0, 1], FileFormat::Pdf, None).unwrap();
+        let (content_type, data) = into_parts(response).await;
+        let pdf = String::from_utf8_lossy(&data);
+
+        assert_eq!(content_type, "application/pdf");
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("/MediaBox [0 0 100 100]"));
+        assert!(pdf.contains("/MediaBox [0 0 200 50]"));
+    }
+
+    #[tokio::test]
+    async fn test_render_png_zip() {
+        let response = render_artboards(&document(), &[0, 1, 2], FileFormat::Png, None).unwrap();
+        let (content_type, data) = into_parts(response).await;
+        let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+
+        assert_eq!(content_type, "application/zip");
+        let mut file_names: Vec<&str> = archive.file_names().collect();
+        file_names.sort();
+        assert_eq!(
+            file_names,
+            vec!["0-square.png", "1-wide_banner.png", "2-story.png"]
+        );
+
+        let mut png_data = Vec::new();
+        std::io::Read::read_to_end(
+            &mut archive.by_name("1-wide_banner.png").unwrap(),
+            &mut png_data,
+        )
+        .unwrap();
+        assert_eq!(png_size(&png_data), (200, 50));
+    }
+
+    #[tokio::test]
+    async fn test_render_single_artboard() {
+        let response = render_artboards(&document(), &[2], FileFormat::Png, None).unwrap();
+        let (content_type, data) = into_parts(response).await;
+
+        assert_eq!(content_type, "image/png");
+        assert_eq!(png_size(&data), (50, 100));
+    }
+
+    #[test]
+    fn test_render_invalid_artboard() {
+        let document: DtifDocument = serde_json::from_value(serde_json::json!({
+            "artboards": [artboard("valid", 100, 100, "paint"), artboard("invalid", 100, 100, "missing")],
+            "paints": [{ "type": "Solid", "id": "paint", "color": [255, 0, 0] }]
+        }))
+        .unwrap();
+
+        let err = render_artboards(&document, &[0, 1], FileFormat::Png, None).unwrap_err();
+        assert_eq!(
+            serde_json::to_value(&err.code).unwrap(),
+            serde_json::json!("#ERR_INVALID_DTIF")
+        );
+        assert_eq!(err.additional_errors.len(), 1);
+        assert_eq!(
+            err.additional_errors[0]["path"],
+            serde_json::json!("$.artboards[1].nodes[0].styles[0].paintId.referenceId")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod artboard;
+pub mod document;
 pub mod svg;
 
 use axum::Router;
@@ -8,5 +9,6 @@ use crate::environment::app_state::AppState;
 pub fn router() -> Router<AppState> {
     Router::new()
         .nest("/artboard", artboard::router())
+        .nest("/document", document::router())
         .nest("/svg", svg::router())
 }
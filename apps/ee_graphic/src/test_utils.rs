@@ -0,0 +1,4 @@
+/// Reference to the element with the given id in DTIF JSON.
+pub fn reference_id(id: &str) -> serde_json::Value {
+    serde_json::json!({ "type": "ReferenceId", "referenceId": id })
+}
@@ -143,6 +143,7 @@ fn build_svg_string(mut dtif: DtifArtboard) -> Result<String, AppError> {
             version: dtif.version,
             size: dtif.size,
             viewport: dtif.viewport,
+            sub_pixel_layout: dtif.sub_pixel_layout,
         },
         ArbSvgBuilderPlugin {
             output_event_sender: svg_builder_output_event_sender,
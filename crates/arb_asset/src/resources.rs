@@ -27,6 +27,10 @@ impl AssetsRes {
         self.image_db.get(id.0)
     }
 
+    pub fn iter_images(&self) -> impl Iterator<Item = (ImageId, &ImageAsset)> {
+        self.image_db.iter().map(|(id, image)| (ImageId(id), image))
+    }
+
     pub fn insert_asset(&mut self, asset: Asset) -> Option<AssetId> {
         match asset.content_type {
             AssetContentType::Ttf => {
@@ -10,3 +10,7 @@ pub struct Removed;
 
 #[derive(Component, Debug, Default, Copy, Clone)]
 pub struct StaleStaticLayout;
+
+/// Marks an entity that was instantiated from a component and is managed by its instance.
+#[derive(Component, Debug, Default, Copy, Clone)]
+pub struct InstanceContent;
@@ -1,10 +1,14 @@
 use crate::{
-    properties::{AlignItems, AlignSelf, FlexDirection, JustifyContent, JustifySelf},
-    utils::{auto_length_to_taffy, length_to_taffy},
+    properties::{
+        AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, GridPlacement, GridTrack,
+        InstanceOverride, JustifyContent, JustifySelf, VariableProperty,
+    },
+    reference_id::{ReferenceId, ReferenceIdOrEntity},
+    utils::{auto_length_to_taffy, length_to_taffy, optional_length_to_taffy},
 };
 use bevy_ecs::{component::Component, entity::Entity};
-use dyn_attributed_string::AttributedString;
 use dyn_arb_asset::asset_id::ImageId;
+use dyn_attributed_string::AttributedString;
 use dyn_utils::{
     properties::{corner_radii::CornerRadii, opacity::Opacity, rect::Rect, size::Size},
     units::{auto_length::AutoLength, axes::Axes, length::Length},
@@ -111,13 +115,31 @@ pub struct ImageAssetMixin(pub Option<ImageId>);
 #[derive(Component, Debug, Clone)]
 pub struct AttributedStringMixin(pub AttributedString);
 
+/// Marks a node as component whose subtree can be instantiated.
+#[derive(Component, Debug, Default, Copy, Clone)]
+pub struct ComponentMixin;
+
+/// Marks a node as instance of a component.
+/// The children of the node are clones of the component's children
+/// with the overrides applied.
+#[derive(Component, Debug, Clone)]
+pub struct InstanceMixin {
+    pub component_id: ReferenceIdOrEntity,
+    pub overrides: SmallVec<[InstanceOverride; 2]>,
+}
+
+/// Properties of the entity that are bound to variables
+/// and thus re-resolved whenever a variable or the active variable mode changes.
+#[derive(Component, Debug, Default, Clone)]
+pub struct VariableBindingsMixin(pub SmallVec<[(VariableProperty, ReferenceId); 2]>);
+
 #[derive(Component, Debug, Copy, Clone)]
 pub struct StaticLayoutNodeId(pub taffy::NodeId);
 
-#[derive(Component, Debug, Default, Copy, Clone)]
+#[derive(Component, Debug, Default, Clone)]
 pub struct StaticLayoutParentMixin(pub StaticLayoutParent);
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 #[cfg_attr(
     feature = "specta_support",
     derive(serde::Serialize, serde::Deserialize, specta::Type),
@@ -125,6 +147,8 @@ pub struct StaticLayoutParentMixin(pub StaticLayoutParent);
 )]
 #[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
 pub struct StaticLayoutParent {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub display: Display,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub align_items: Option<AlignItems>,
     #[cfg_attr(feature = "specta_support", serde(default))]
@@ -137,6 +161,14 @@ pub struct StaticLayoutParent {
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub flex_direction: FlexDirection,
     #[cfg_attr(feature = "specta_support", serde(default))]
+    pub flex_wrap: FlexWrap,
+    /// The sizes of the grid rows, only used with [`Display::Grid`].
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub grid_template_rows: Vec<GridTrack>,
+    /// The sizes of the grid columns, only used with [`Display::Grid`].
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub grid_template_columns: Vec<GridTrack>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
     pub horizontal_sizing_mode: LayoutParentSizingMode,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub vertical_sizing_mode: LayoutParentSizingMode,
@@ -145,6 +177,7 @@ pub struct StaticLayoutParent {
 impl StaticLayoutParent {
     pub fn to_style(&self) -> taffy::Style {
         taffy::Style {
+            display: self.display.into(),
             align_items: self.align_items.map(|v| v.into()),
             justify_content: self.justify_content.map(|v| v.into()),
             gap: taffy::Size::<taffy::LengthPercentage> {
@@ -158,6 +191,17 @@ impl StaticLayoutParent {
                 right: length_to_taffy(self.padding.right),
             },
             flex_direction: self.flex_direction.into(),
+            flex_wrap: self.flex_wrap.into(),
+            grid_template_rows: self
+                .grid_template_rows
+                .iter()
+                .map(|track| (*track).into())
+                .collect(),
+            grid_template_columns: self
+                .grid_template_columns
+                .iter()
+                .map(|track| (*track).into())
+                .collect(),
             ..Default::default()
         }
     }
@@ -217,7 +261,7 @@ pub enum Constraint {
 #[derive(Component, Debug, Default, Copy, Clone)]
 pub struct StaticLayoutElementMixin(pub StaticLayoutElement);
 
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 #[cfg_attr(
     feature = "specta_support",
     derive(serde::Serialize, serde::Deserialize, specta::Type),
@@ -233,12 +277,61 @@ pub struct StaticLayoutElement {
     justify_self: Option<JustifySelf>,
     #[cfg_attr(feature = "specta_support", serde(default))]
     margin: Rect<AutoLength>,
+    /// The placement within the rows of a grid parent.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub grid_row: GridPlacement,
+    /// The placement within the columns of a grid parent.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub grid_column: GridPlacement,
+    /// The minimum size, an axis without value is unconstrained.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub min_size: Axes<Option<Length>>,
+    /// The maximum size, an axis without value is unconstrained.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub max_size: Axes<Option<Length>>,
+    /// The preferred width to height ratio.
+    /// If one axis fills the parent while the other is fixed,
+    /// the fixed axis is derived from the ratio instead.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub aspect_ratio: Option<f32>,
+    /// How much of the free space along the main axis of a flex parent
+    /// the element takes relative to its siblings.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub flex_grow: f32,
+    /// How much the element shrinks relative to its siblings
+    /// when its flex parent overflows along the main axis.
+    #[cfg_attr(feature = "specta_support", serde(default = "default_flex_shrink"))]
+    pub flex_shrink: f32,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub horizontal_sizing_mode: LayoutElementSizingMode,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub vertical_sizing_mode: LayoutElementSizingMode,
 }
 
+impl Default for StaticLayoutElement {
+    fn default() -> Self {
+        Self {
+            align_self: None,
+            justify_self: None,
+            margin: Rect::default(),
+            grid_row: GridPlacement::default(),
+            grid_column: GridPlacement::default(),
+            min_size: Axes::default(),
+            max_size: Axes::default(),
+            aspect_ratio: None,
+            flex_grow: 0.0,
+            flex_shrink: default_flex_shrink(),
+            horizontal_sizing_mode: LayoutElementSizingMode::default(),
+            vertical_sizing_mode: LayoutElementSizingMode::default(),
+        }
+    }
+}
+
+#[inline]
+fn default_flex_shrink() -> f32 {
+    1.0
+}
+
 impl StaticLayoutElement {
     pub fn to_style(&self) -> taffy::Style {
         taffy::Style {
@@ -250,6 +343,19 @@ impl StaticLayoutElement {
                 left: auto_length_to_taffy(self.margin.left),
                 right: auto_length_to_taffy(self.margin.right),
             },
+            grid_row: self.grid_row.into(),
+            grid_column: self.grid_column.into(),
+            min_size: taffy::Size::<taffy::Dimension> {
+                width: optional_length_to_taffy(self.min_size.x),
+                height: optional_length_to_taffy(self.min_size.y),
+            },
+            max_size: taffy::Size::<taffy::Dimension> {
+                width: optional_length_to_taffy(self.max_size.x),
+                height: optional_length_to_taffy(self.max_size.y),
+            },
+            aspect_ratio: self.aspect_ratio,
+            flex_grow: self.flex_grow,
+            flex_shrink: self.flex_shrink,
             ..Default::default()
         }
     }
@@ -275,7 +381,10 @@ pub enum LayoutElementSizingMode {
 )]
 #[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
 pub enum LayoutElement {
+    /// Positioned by its constraints relative to the parent.
+    /// Within a static layout parent the element is excluded from the flow of its siblings.
     Absolute(AbsoluteLayoutElement),
+    /// Positioned by the static layout (flex or grid) of the parent.
     Static(StaticLayoutElement),
 }
 
@@ -291,3 +400,56 @@ pub struct PreAbsoluteLayoutProperties {
     pub size: Size,
     pub parent_size: Option<Size>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyn_utils::units::{abs::Abs, ratio::Ratio};
+
+    #[test]
+    fn test_static_layout_element_to_style() {
+        let style = StaticLayoutElement::default().to_style();
+        assert_eq!(style.min_size, taffy::Size::auto());
+        assert_eq!(style.max_size, taffy::Size::auto());
+        assert_eq!(style.aspect_ratio, None);
+        assert_eq!(style.flex_grow, 0.0);
+        assert_eq!(style.flex_shrink, 1.0);
+
+        let mut element = StaticLayoutElement::default();
+        element.min_size = Axes::new(Some(Length::abs(Abs::pt(10.0))), None);
+        element.max_size = Axes::new(None, Some(Length::ratio(Ratio::new(0.5))));
+        element.aspect_ratio = Some(2.0);
+        element.flex_grow = 1.0;
+        element.flex_shrink = 0.0;
+
+        let style = element.to_style();
+        assert_eq!(
+            style.min_size,
+            taffy::Size {
+                width: taffy::Dimension::Length(10.0),
+                height: taffy::Dimension::Auto,
+            }
+        );
+        assert_eq!(
+            style.max_size,
+            taffy::Size {
+                width: taffy::Dimension::Auto,
+                height: taffy::Dimension::Percent(0.5),
+            }
+        );
+        assert_eq!(style.aspect_ratio, Some(2.0));
+        assert_eq!(style.flex_grow, 1.0);
+        assert_eq!(style.flex_shrink, 0.0);
+    }
+
+    #[test]
+    fn test_static_layout_parent_to_style() {
+        let mut parent = StaticLayoutParent::default();
+        assert_eq!(parent.to_style().flex_wrap, taffy::FlexWrap::NoWrap);
+
+        parent.flex_wrap = FlexWrap::Wrap;
+        assert_eq!(parent.to_style().flex_wrap, taffy::FlexWrap::Wrap);
+        parent.flex_wrap = FlexWrap::WrapReverse;
+        assert_eq!(parent.to_style().flex_wrap, taffy::FlexWrap::WrapReverse);
+    }
+}
@@ -20,6 +20,7 @@ pub enum ArbNodeVariant {
     Star,
     Text,
     Vector,
+    BooleanOperation,
 }
 
 /// Defines a layout container, similar to an HTML `<div>`, for hierarchical organization.
@@ -101,6 +102,68 @@ pub struct TextArbNode {
     pub sizing_mode: TextSizingMode,
 }
 
+impl TextArbNode {
+    /// Replaces the text while keeping the attribute intervals valid
+    /// by clamping them to the new text and stretching the last interval to its end.
+    pub fn replace_text(&mut self, text: String) {
+        let floor_char_boundary = |mut index: usize| {
+            index = index.min(text.len());
+            while !text.is_char_boundary(index) {
+                index -= 1;
+            }
+            return index;
+        };
+
+        let maybe_first = self.attributes.first().cloned();
+        for interval in self.attributes.iter_mut() {
+            interval.start = floor_char_boundary(interval.start);
+            interval.end = floor_char_boundary(interval.end);
+        }
+        self.attributes
+            .retain(|interval| interval.start < interval.end);
+
+        if self.attributes.is_empty() {
+            if let Some(mut first) = maybe_first {
+                first.start = 0;
+                first.end = text.len();
+                self.attributes.push(first);
+            }
+        } else if let Some(last) = self
+            .attributes
+            .iter_mut()
+            .max_by_key(|interval| interval.end)
+        {
+            last.end = text.len();
+        }
+
+        self.text = text;
+    }
+}
+
 /// A vector shape node.
 #[derive(Component, Debug, Default, Clone)]
 pub struct VectorArbNode;
+
+/// A shape node combining the outlines of its (shape) children with a boolean operation.
+#[derive(Component, Debug, Default, Copy, Clone)]
+pub struct BooleanOperationArbNode {
+    pub operation: BooleanOperation,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum BooleanOperation {
+    /// Area covered by any child.
+    #[default]
+    Union,
+    /// Area of the bottom-most child not covered by any other child.
+    Subtract,
+    /// Area covered by all children.
+    Intersect,
+    /// Area covered by an odd number of children.
+    Exclude,
+}
@@ -33,6 +33,7 @@ pub struct SolidArbPaint {
 #[derive(Component, Debug, Default, Copy, Clone)]
 pub struct ImageArbPaint {
     pub scale_mode: ImageScaleMode,
+    pub filters: ImageFilters,
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -65,6 +66,42 @@ pub enum ImageScaleMode {
     },
 }
 
+/// Color adjustments applied to an image.
+///
+/// Each adjustment ranges from -1.0 to 1.0, where 0.0 leaves the image unchanged.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct ImageFilters {
+    /// Brightens (positive) or darkens (negative) the image.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub exposure: f32,
+    /// Increases (positive) or decreases (negative) the difference between light and dark tones.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub contrast: f32,
+    /// Intensifies (positive) or desaturates (negative) the colors,
+    /// where -1.0 results in a grayscale image.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub saturation: f32,
+    /// Shifts the colors towards warm (positive) or cool (negative) tones.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub temperature: f32,
+    /// Shifts the colors towards magenta (positive) or green (negative).
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub tint: f32,
+}
+
+impl ImageFilters {
+    /// Whether no adjustment is applied.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Component, Debug, Clone)]
 pub struct GradientArbPaint {
     pub variant: GradientVariant,
@@ -87,6 +124,17 @@ pub enum GradientVariant {
         #[cfg_attr(feature = "specta_support", serde(default))]
         transform: Mat3,
     },
+    /// Conic gradient sweeping clockwise around the center,
+    /// starting at the positive x-axis of the gradient space.
+    Angular {
+        #[cfg_attr(feature = "specta_support", serde(default))]
+        transform: Mat3,
+    },
+    /// Gradient spreading from the center in the shape of a diamond.
+    Diamond {
+        #[cfg_attr(feature = "specta_support", serde(default))]
+        transform: Mat3,
+    },
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -12,6 +12,9 @@ pub enum ArbStyleVariant {
     Fill,
     Stroke,
     DropShadow,
+    InnerShadow,
+    LayerBlur,
+    BackgroundBlur,
 }
 
 #[derive(Component, Debug, Default, Copy, Clone)]
@@ -20,6 +23,116 @@ pub struct FillArbStyle;
 #[derive(Component, Debug, Default, Clone)]
 pub struct StrokeArbStyle {
     pub stroke: tiny_skia_path::Stroke,
+    /// Alternating lengths of dashes and gaps. An empty array draws a solid stroke.
+    pub dash_array: Vec<f32>,
+    /// Distance into the dash pattern at which the stroke starts.
+    pub dash_offset: f32,
+    pub alignment: StrokeAlignment,
+}
+
+impl StrokeArbStyle {
+    /// Builds the dash pattern, repeating arrays with an odd number of values (like SVG does).
+    /// Returns `None` for solid strokes or invalid dash arrays.
+    pub fn get_dash(&self) -> Option<tiny_skia_path::StrokeDash> {
+        if self.dash_array.is_empty() {
+            return None;
+        }
+
+        let mut dash_array = self.dash_array.clone();
+        if dash_array.len() % 2 == 1 {
+            dash_array.extend_from_within(..);
+        }
+
+        return tiny_skia_path::StrokeDash::new(dash_array, self.dash_offset);
+    }
+}
+
+/// Position of the stroke relative to the outline of the shape.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum StrokeAlignment {
+    /// Centered on the outline.
+    #[default]
+    Center,
+    /// Within the filled area of the shape.
+    Inside,
+    /// Outside the filled area of the shape.
+    Outside,
+}
+
+/// Shape at the end of open stroked paths.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum StrokeCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+impl From<StrokeCap> for tiny_skia_path::LineCap {
+    fn from(cap: StrokeCap) -> Self {
+        match cap {
+            StrokeCap::Butt => tiny_skia_path::LineCap::Butt,
+            StrokeCap::Round => tiny_skia_path::LineCap::Round,
+            StrokeCap::Square => tiny_skia_path::LineCap::Square,
+        }
+    }
+}
+
+impl From<tiny_skia_path::LineCap> for StrokeCap {
+    fn from(line_cap: tiny_skia_path::LineCap) -> Self {
+        match line_cap {
+            tiny_skia_path::LineCap::Butt => StrokeCap::Butt,
+            tiny_skia_path::LineCap::Round => StrokeCap::Round,
+            tiny_skia_path::LineCap::Square => StrokeCap::Square,
+        }
+    }
+}
+
+/// Shape at the corners of stroked paths.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum StrokeJoin {
+    /// Sharp corner, beveled once the miter limit is exceeded.
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl From<StrokeJoin> for tiny_skia_path::LineJoin {
+    fn from(join: StrokeJoin) -> Self {
+        match join {
+            StrokeJoin::Miter => tiny_skia_path::LineJoin::Miter,
+            StrokeJoin::Round => tiny_skia_path::LineJoin::Round,
+            StrokeJoin::Bevel => tiny_skia_path::LineJoin::Bevel,
+        }
+    }
+}
+
+impl From<tiny_skia_path::LineJoin> for StrokeJoin {
+    fn from(line_join: tiny_skia_path::LineJoin) -> Self {
+        match line_join {
+            tiny_skia_path::LineJoin::Miter | tiny_skia_path::LineJoin::MiterClip => {
+                StrokeJoin::Miter
+            }
+            tiny_skia_path::LineJoin::Round => StrokeJoin::Round,
+            tiny_skia_path::LineJoin::Bevel => StrokeJoin::Bevel,
+        }
+    }
 }
 
 #[derive(Component, Debug, Default, Clone)]
@@ -29,3 +142,23 @@ pub struct DropShadowArbStyle {
     pub spread: Abs,
     pub blur: Abs,
 }
+
+#[derive(Component, Debug, Default, Clone)]
+pub struct InnerShadowArbStyle {
+    pub color: Color,
+    pub position: Vec2,
+    pub spread: Abs,
+    pub blur: Abs,
+}
+
+/// Blurs the node including its styles and children.
+#[derive(Component, Debug, Default, Clone)]
+pub struct LayerBlurArbStyle {
+    pub blur: Abs,
+}
+
+/// Blurs everything behind the node within its shape.
+#[derive(Component, Debug, Default, Clone)]
+pub struct BackgroundBlurArbStyle {
+    pub blur: Abs,
+}
@@ -1,23 +1,28 @@
 use crate::{
     components::{
         mixins::BlendMode,
-        paints::{GradientColorStop, GradientVariant, ImageScaleMode},
+        nodes::BooleanOperation,
+        paints::{GradientColorStop, GradientVariant, ImageFilters, ImageScaleMode},
+        styles::{StrokeAlignment, StrokeCap, StrokeJoin},
     },
-    properties::{TextAttributeInterval, Viewport},
-    reference_id::ReferenceIdOrEntity,
-    AssetWithId, Node, Paint,
+    properties::{
+        InstanceOverride, TextAttributeInterval, VariableProperty, VariableValue, Viewport,
+    },
+    reference_id::{ReferenceId, ReferenceIdOrEntity},
+    AssetWithId, Node, Paint, Style, Variable,
 };
 use bevy_app::App;
 use bevy_ecs::{event::Event, world::World};
+use dyn_arb_asset::asset_id::ImageId;
 use dyn_attributed_string::layout::{
     HorizontalTextAlignment, LineWrap, TextSizingMode, VerticalTextAlignment,
 };
-use dyn_arb_asset::asset_id::ImageId;
 use dyn_utils::{
     properties::{color::Color, corner_radii::CornerRadii, opacity::Opacity, size::Size},
     units::{abs::Abs, angle::Angle},
 };
 use glam::Vec2;
+use std::collections::BTreeMap;
 
 pub trait InputEvent {
     fn register_events(app: &mut App);
@@ -44,11 +49,19 @@ pub enum CoreInputEvent {
     UpdateStarNode(UpdateStarNodeInputEvent),
     UpdatePolygonNode(UpdatePolygonNodeInputEvent),
     UpdateTextNode(UpdateTextNodeInputEvent),
+    UpdateInstanceNode(UpdateInstanceNodeInputEvent),
+    UpdateBooleanOperationNode(UpdateBooleanOperationNodeInputEvent),
 
     // Style
+    CreateStyle(CreateStyleInputEvent),
+    DeleteStyle(DeleteStyleInputEvent),
+    ReorderStyles(ReorderStylesInputEvent),
     UpdateFillStyle(UpdateFillStyleInputEvent),
     UpdateStrokeStyle(UpdateStorkeStyleInputEvent),
     UpdateDropShadowStyle(UpdateDropShadowStyleInputEvent),
+    UpdateInnerShadowStyle(UpdateInnerShadowStyleInputEvent),
+    UpdateLayerBlurStyle(UpdateLayerBlurStyleInputEvent),
+    UpdateBackgroundBlurStyle(UpdateBackgroundBlurStyleInputEvent),
 
     // Paint
     CreatePaint(CreatePaintInputEvent),
@@ -59,6 +72,12 @@ pub enum CoreInputEvent {
     // Asset
     CreateAsset(CreateAssetInputEvent),
 
+    // Variable
+    CreateVariable(CreateVariableInputEvent),
+    UpdateVariable(UpdateVariableInputEvent),
+    UpdateVariableMode(UpdateVariableModeInputEvent),
+    BindVariable(BindVariableInputEvent),
+
     // Entity
     DeleteEntity(DeleteEntityInputEvent),
     UpdateEntityTransform(UpdateEntityTransformInputEvent),
@@ -92,11 +111,19 @@ impl InputEvent for CoreInputEvent {
         app.add_event::<UpdateStarNodeInputEvent>();
         app.add_event::<UpdatePolygonNodeInputEvent>();
         app.add_event::<UpdateTextNodeInputEvent>();
+        app.add_event::<UpdateInstanceNodeInputEvent>();
+        app.add_event::<UpdateBooleanOperationNodeInputEvent>();
 
         // Style
+        app.add_event::<CreateStyleInputEvent>();
+        app.add_event::<DeleteStyleInputEvent>();
+        app.add_event::<ReorderStylesInputEvent>();
         app.add_event::<UpdateFillStyleInputEvent>();
         app.add_event::<UpdateStorkeStyleInputEvent>();
         app.add_event::<UpdateDropShadowStyleInputEvent>();
+        app.add_event::<UpdateInnerShadowStyleInputEvent>();
+        app.add_event::<UpdateLayerBlurStyleInputEvent>();
+        app.add_event::<UpdateBackgroundBlurStyleInputEvent>();
 
         // Paint
         app.add_event::<CreatePaintInputEvent>();
@@ -107,6 +134,12 @@ impl InputEvent for CoreInputEvent {
         // Asset
         app.add_event::<CreateAssetInputEvent>();
 
+        // Variable
+        app.add_event::<CreateVariableInputEvent>();
+        app.add_event::<UpdateVariableInputEvent>();
+        app.add_event::<UpdateVariableModeInputEvent>();
+        app.add_event::<BindVariableInputEvent>();
+
         // Entity
         app.add_event::<DeleteEntityInputEvent>();
         app.add_event::<UpdateEntityTransformInputEvent>();
@@ -158,8 +191,23 @@ impl InputEvent for CoreInputEvent {
             CoreInputEvent::UpdateTextNode(event) => {
                 world.send_event(event);
             }
+            CoreInputEvent::UpdateInstanceNode(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::UpdateBooleanOperationNode(event) => {
+                world.send_event(event);
+            }
 
             // Style
+            CoreInputEvent::CreateStyle(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::DeleteStyle(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::ReorderStyles(event) => {
+                world.send_event(event);
+            }
             CoreInputEvent::UpdateFillStyle(event) => {
                 world.send_event(event);
             }
@@ -169,6 +217,15 @@ impl InputEvent for CoreInputEvent {
             CoreInputEvent::UpdateDropShadowStyle(event) => {
                 world.send_event(event);
             }
+            CoreInputEvent::UpdateInnerShadowStyle(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::UpdateLayerBlurStyle(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::UpdateBackgroundBlurStyle(event) => {
+                world.send_event(event);
+            }
 
             // Paint
             CoreInputEvent::CreatePaint(event) => {
@@ -189,6 +246,20 @@ impl InputEvent for CoreInputEvent {
                 world.send_event(event);
             }
 
+            // Variable
+            CoreInputEvent::CreateVariable(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::UpdateVariable(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::UpdateVariableMode(event) => {
+                world.send_event(event);
+            }
+            CoreInputEvent::BindVariable(event) => {
+                world.send_event(event);
+            }
+
             // Entity
             CoreInputEvent::DeleteEntity(event) => {
                 world.send_event(event);
@@ -362,20 +433,76 @@ pub struct UpdateTextNodeInputEvent {
     pub sizing_mode: Option<TextSizingMode>,
 }
 
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateInstanceNodeInputEvent {
+    pub id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub component_id: Option<ReferenceIdOrEntity>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub overrides: Option<Vec<InstanceOverride>>,
+}
+
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateBooleanOperationNodeInputEvent {
+    pub id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub operation: Option<BooleanOperation>,
+}
+
 // =============================================================================
 // Style
 // =============================================================================
 
-// TODO: For now Styles can only be created via Node
-//
-// #[derive(Event, Debug, Clone)]
-// #[cfg_attr(
-//     feature = "specta_support",
-//     derive(serde::Serialize, serde::Deserialize, specta::Type)
-// )]
-// pub struct CreateStyleInputEvent {
-//     pub style: Style,
-// }
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct CreateStyleInputEvent {
+    pub node_id: ReferenceIdOrEntity,
+    pub style: Style,
+    /// Position within the styles of the node where 0 is the top-most style.
+    /// Defaults to the top.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub index: Option<usize>,
+}
+
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct DeleteStyleInputEvent {
+    pub id: ReferenceIdOrEntity,
+}
+
+/// Reorders the styles of a node (index 0 is the top-most style).
+/// Styles of the node missing in the list keep their relative order below the listed ones.
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct ReorderStylesInputEvent {
+    pub id: ReferenceIdOrEntity,
+    pub styles: Vec<ReferenceIdOrEntity>,
+}
 
 #[derive(Event, Debug, Clone)]
 #[cfg_attr(
@@ -402,6 +529,18 @@ pub struct UpdateStorkeStyleInputEvent {
     pub paint_id: Option<ReferenceIdOrEntity>,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub width: Option<Abs>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub alignment: Option<StrokeAlignment>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub cap: Option<StrokeCap>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub join: Option<StrokeJoin>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub miter_limit: Option<f32>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub dash_array: Option<Vec<Abs>>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub dash_offset: Option<Abs>,
 }
 
 #[derive(Event, Debug, Clone)]
@@ -422,6 +561,48 @@ pub struct UpdateDropShadowStyleInputEvent {
     pub blur: Option<Abs>,
 }
 
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateInnerShadowStyleInputEvent {
+    pub id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub color: Option<Color>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub position: Option<Vec2>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub spread: Option<Abs>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub blur: Option<Abs>,
+}
+
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateLayerBlurStyleInputEvent {
+    pub id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub blur: Option<Abs>,
+}
+
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateBackgroundBlurStyleInputEvent {
+    pub id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub blur: Option<Abs>,
+}
+
 // =============================================================================
 // Paint
 // =============================================================================
@@ -461,6 +642,8 @@ pub struct UpdateImagePaintInputEvent {
     pub scale_mode: Option<ImageScaleMode>,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub image_id: Option<ImageId>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub filters: Option<ImageFilters>,
 }
 
 #[derive(Event, Debug, Clone)]
@@ -492,6 +675,63 @@ pub struct CreateAssetInputEvent {
     pub asset: AssetWithId,
 }
 
+// =============================================================================
+// Variable
+// =============================================================================
+
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct CreateVariableInputEvent {
+    pub variable: Variable,
+}
+
+/// Updates the value of a variable and re-resolves all properties bound to it.
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateVariableInputEvent {
+    pub id: ReferenceId,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub value: Option<VariableValue>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub modes: Option<BTreeMap<String, VariableValue>>,
+}
+
+/// Switches the active variable mode (e.g. from "light" to "dark").
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct UpdateVariableModeInputEvent {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub mode: Option<String>,
+}
+
+/// Binds a property of a node, style or paint to a variable
+/// or unbinds it if no variable id is specified.
+#[derive(Event, Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct BindVariableInputEvent {
+    pub target_id: ReferenceIdOrEntity,
+    pub property: VariableProperty,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variable_id: Option<ReferenceId>,
+}
+
 // =============================================================================
 // Entity
 // =============================================================================
@@ -624,7 +864,7 @@ pub struct UpdateEntityChildrenInputEvent {
 // Note: Cannot be inlined because of Utoipa lack of renaming Schema references
 // https://github.com/juhaku/utoipa/issues/894#issuecomment-2164362189
 #[cfg(feature = "lua_scripts")]
-use crate::{reference_id::ReferenceId, LuaScriptWithId};
+use crate::LuaScriptWithId;
 #[cfg(feature = "lua_scripts")]
 use dyn_arb_lua::tables::args_table::LuaScriptArgsMap;
 
@@ -1,18 +1,24 @@
 pub mod components;
 pub mod events;
 pub mod mapper;
+pub mod path_data;
 pub mod properties;
 pub mod reference_id;
 pub mod utils;
 
 use crate::components::{
-    mixins::{CornerRadiiMixin, ImageAssetMixin, PathMixin, SizeMixin},
+    mixins::{
+        ComponentMixin, CornerRadiiMixin, ImageAssetMixin, InstanceMixin, PathMixin, SizeMixin,
+    },
     nodes::{
-        ArbNode, EllipseArbNode, FrameArbNode, PolygonArbNode, RectangleArbNode, StarArbNode,
-        TextArbNode, VectorArbNode,
+        ArbNode, BooleanOperationArbNode, EllipseArbNode, FrameArbNode, PolygonArbNode,
+        RectangleArbNode, StarArbNode, TextArbNode, VectorArbNode,
     },
     paints::{ArbPaint, GradientArbPaint, ImageArbPaint, SolidArbPaint},
-    styles::{DropShadowArbStyle, StrokeArbStyle},
+    styles::{
+        BackgroundBlurArbStyle, DropShadowArbStyle, InnerShadowArbStyle, LayerBlurArbStyle,
+        StrokeArbStyle,
+    },
 };
 use bevy_ecs::{
     bundle::Bundle,
@@ -25,26 +31,28 @@ use components::{
         StaticLayoutElementMixin, StaticLayoutParent, StaticLayoutParentMixin, VisibilityMixin,
         WindingRule,
     },
-    nodes::{ArbNodeVariant, EllipseArcData},
-    paints::{ArbPaintVariant, GradientColorStop, GradientVariant, ImageScaleMode},
-    styles::{ArbStyle, ArbStyleVariant, FillArbStyle},
-};
-use dyn_attributed_string::layout::{
-    HorizontalTextAlignment, LineWrap, TextSizingMode, VerticalTextAlignment,
+    nodes::{ArbNodeVariant, BooleanOperation, EllipseArcData},
+    paints::{ArbPaintVariant, GradientColorStop, GradientVariant, ImageFilters, ImageScaleMode},
+    styles::{ArbStyle, ArbStyleVariant, FillArbStyle, StrokeAlignment, StrokeCap, StrokeJoin},
 };
 use dyn_arb_asset::{
     asset::{Asset, AssetContent, AssetContentType},
     asset_id::ImageId,
 };
+use dyn_attributed_string::layout::{
+    HorizontalTextAlignment, LineWrap, TextSizingMode, VerticalTextAlignment,
+};
 use dyn_utils::{
     properties::{color::Color, corner_radii::CornerRadii, opacity::Opacity, size::Size},
     serde::{default_as_false, default_as_true},
     units::{abs::Abs, angle::Angle},
 };
 use glam::{Vec2, Vec3};
-use mapper::string_to_tiny_skia_path;
-use properties::TextAttributeInterval;
+use mapper::string_to_tiny_skia_path_lossy;
+use properties::{InstanceOverride, TextAttributeInterval, VariableProperty, VariableValue};
 use reference_id::{ReferenceId, ReferenceIdOrEntity, ReferenceIdOrImageId};
+use smallvec::SmallVec;
+use std::collections::BTreeMap;
 
 // =============================================================================
 // Node
@@ -65,6 +73,9 @@ pub enum Node {
     Polygon(PolygonNode),
     Text(TextNode),
     Vector(VectorNode),
+    BooleanOperation(BooleanOperationNode),
+    Component(ComponentNode),
+    Instance(InstanceNode),
 }
 
 #[derive(Bundle, Debug)]
@@ -139,8 +150,8 @@ impl FrameNode {
     pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
         let mut entity_commands = commands.spawn(self.to_bundle());
 
-        if let Some(layout_parent) = self.layout_parent {
-            entity_commands.insert(StaticLayoutParentMixin(layout_parent));
+        if let Some(layout_parent) = &self.layout_parent {
+            entity_commands.insert(StaticLayoutParentMixin(layout_parent.clone()));
         }
 
         match self.layout_element {
@@ -629,7 +640,7 @@ impl VectorNode {
                 variant: ArbNodeVariant::Vector,
             },
             path: PathMixin {
-                path: string_to_tiny_skia_path(&self.path).unwrap(),
+                path: string_to_tiny_skia_path_lossy(&self.path),
                 winding_rule: self.winding_rule,
             },
             vector: VectorArbNode,
@@ -661,6 +672,217 @@ impl VectorNode {
     }
 }
 
+#[derive(Bundle, Debug)]
+pub struct BooleanOperationArbNodeBundle {
+    pub node: ArbNode,
+    pub boolean_operation: BooleanOperationArbNode,
+    pub transform: TransformBundle,
+    pub size: SizeMixin,
+    pub visibility: VisibilityMixin,
+    pub blend_mode: BlendModeMixin,
+    pub opacity: OpacityMixin,
+}
+
+/// Combines the outlines of its children (shape nodes) into a single shape.
+///
+/// The children aren't rendered themselves, only the resulting shape is.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct BooleanOperationNode {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub id: Option<ReferenceId>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub operation: BooleanOperation,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub translation: Vec2,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub rotation_deg: Angle,
+    pub size: Size,
+    #[cfg_attr(feature = "specta_support", serde(default = "default_as_true"))]
+    pub visible: bool,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub blend_mode: BlendMode,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub opacity: Opacity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub layout_element: LayoutElement,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub styles: Vec<Style>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub children: Vec<ReferenceIdOrEntity>,
+}
+
+impl BooleanOperationNode {
+    pub fn to_bundle(&self) -> BooleanOperationArbNodeBundle {
+        BooleanOperationArbNodeBundle {
+            node: ArbNode {
+                variant: ArbNodeVariant::BooleanOperation,
+            },
+            boolean_operation: BooleanOperationArbNode {
+                operation: self.operation,
+            },
+            transform: TransformBundle::from_transform(Transform {
+                translation: self.translation.extend(0.0),
+                rotation: self.rotation_deg.to_quat(),
+                scale: Vec3::ONE,
+            }),
+            size: SizeMixin(self.size),
+            visibility: VisibilityMixin(self.visible),
+            blend_mode: BlendModeMixin(self.blend_mode),
+            opacity: OpacityMixin(self.opacity),
+        }
+    }
+
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let mut entity_commands = commands.spawn(self.to_bundle());
+
+        match self.layout_element {
+            LayoutElement::Absolute(layout_element) => {
+                entity_commands.insert(AbsoluteLayoutElementMixin(layout_element))
+            }
+            LayoutElement::Static(layout_element) => {
+                entity_commands.insert(StaticLayoutElementMixin(layout_element))
+            }
+        };
+
+        return entity_commands;
+    }
+}
+
+/// A reusable node tree that can be placed via instance nodes.
+///
+/// Components aren't rendered themselves, only their instances are.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct ComponentNode {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub id: Option<ReferenceId>,
+    #[cfg_attr(feature = "specta_support", serde(default = "default_as_false"))]
+    pub clip_content: bool,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub layout_parent: Option<StaticLayoutParent>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub corner_radii: CornerRadii,
+    pub size: Size,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub styles: Vec<Style>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub children: Vec<ReferenceIdOrEntity>,
+}
+
+impl ComponentNode {
+    pub fn to_bundle(&self) -> FrameArbNodeBundle {
+        FrameArbNodeBundle {
+            node: ArbNode {
+                variant: ArbNodeVariant::Frame,
+            },
+            frame: FrameArbNode {
+                clip_content: self.clip_content,
+            },
+            transform: TransformBundle::default(),
+            size: SizeMixin(self.size),
+            corner_radii: CornerRadiiMixin(self.corner_radii),
+            visibility: VisibilityMixin(false),
+            blend_mode: BlendModeMixin::default(),
+            opacity: OpacityMixin::default(),
+        }
+    }
+
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let mut entity_commands = commands.spawn((self.to_bundle(), ComponentMixin));
+
+        if let Some(layout_parent) = &self.layout_parent {
+            entity_commands.insert(StaticLayoutParentMixin(layout_parent.clone()));
+        }
+
+        return entity_commands;
+    }
+}
+
+/// Places the node tree of a component.
+///
+/// The content (children, styles, clipping, corner radii and layout)
+/// is taken from the component and kept in sync with it.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct InstanceNode {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub id: Option<ReferenceId>,
+    pub component_id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub overrides: Vec<InstanceOverride>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub translation: Vec2,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub rotation_deg: Angle,
+    pub size: Size,
+    #[cfg_attr(feature = "specta_support", serde(default = "default_as_true"))]
+    pub visible: bool,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub blend_mode: BlendMode,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub opacity: Opacity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub layout_element: LayoutElement,
+}
+
+impl InstanceNode {
+    pub fn to_bundle(&self) -> FrameArbNodeBundle {
+        FrameArbNodeBundle {
+            node: ArbNode {
+                variant: ArbNodeVariant::Frame,
+            },
+            frame: FrameArbNode::default(),
+            transform: TransformBundle::from_transform(Transform {
+                translation: self.translation.extend(0.0),
+                rotation: self.rotation_deg.to_quat(),
+                scale: Vec3::ONE,
+            }),
+            size: SizeMixin(self.size),
+            corner_radii: CornerRadiiMixin::default(),
+            visibility: VisibilityMixin(self.visible),
+            blend_mode: BlendModeMixin(self.blend_mode),
+            opacity: OpacityMixin(self.opacity),
+        }
+    }
+
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        let mut entity_commands = commands.spawn((
+            self.to_bundle(),
+            InstanceMixin {
+                component_id: self.component_id.clone(),
+                overrides: SmallVec::from_vec(self.overrides.clone()),
+            },
+        ));
+
+        match self.layout_element {
+            LayoutElement::Absolute(layout_element) => {
+                entity_commands.insert(AbsoluteLayoutElementMixin(layout_element))
+            }
+            LayoutElement::Static(layout_element) => {
+                entity_commands.insert(StaticLayoutElementMixin(layout_element))
+            }
+        };
+
+        return entity_commands;
+    }
+}
+
 // =============================================================================
 // Paint
 // =============================================================================
@@ -731,6 +953,8 @@ pub struct ImagePaint {
     pub image_id: ReferenceIdOrImageId,
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub scale_mode: ImageScaleMode,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub filters: Option<ImageFilters>,
 }
 
 impl ImagePaint {
@@ -741,6 +965,7 @@ impl ImagePaint {
             },
             image: ImageArbPaint {
                 scale_mode: self.scale_mode,
+                filters: self.filters.unwrap_or_default(),
             },
             asset: ImageAssetMixin(maybe_image_id),
         }
@@ -807,6 +1032,9 @@ pub enum Style {
     Fill(FillStyle),
     Stroke(StrokeStyle),
     DropShadow(DropShadowStyle),
+    InnerShadow(InnerShadowStyle),
+    LayerBlur(LayerBlurStyle),
+    BackgroundBlur(BackgroundBlurStyle),
 }
 
 #[derive(Bundle, Debug)]
@@ -876,6 +1104,24 @@ pub struct StrokeStyle {
     pub id: Option<ReferenceId>,
     pub width: Abs,
     pub paint_id: ReferenceIdOrEntity,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub alignment: StrokeAlignment,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub cap: StrokeCap,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub join: StrokeJoin,
+    /// Limit of the ratio of miter length to stroke width
+    /// at which miter joins are beveled.
+    #[cfg_attr(
+        feature = "specta_support",
+        serde(default = "default_stroke_miter_limit")
+    )]
+    pub miter_limit: f32,
+    /// Alternating lengths of dashes and gaps. An empty array draws a solid stroke.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub dash_array: Vec<Abs>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub dash_offset: Abs,
     #[cfg_attr(feature = "specta_support", serde(default = "default_as_true"))]
     pub visible: bool,
     #[cfg_attr(feature = "specta_support", serde(default))]
@@ -888,13 +1134,23 @@ impl StrokeStyle {
     pub fn to_bundle(&self) -> StrokeStyleBundle {
         StrokeStyleBundle {
             style: ArbStyle {
-                variant: ArbStyleVariant::Fill,
+                variant: ArbStyleVariant::Stroke,
             },
             stroke: StrokeArbStyle {
                 stroke: tiny_skia_path::Stroke {
                     width: self.width.to_pt(),
-                    ..Default::default()
+                    miter_limit: self.miter_limit,
+                    line_cap: self.cap.into(),
+                    line_join: self.join.into(),
+                    dash: None,
                 },
+                dash_array: self
+                    .dash_array
+                    .iter()
+                    .map(|length| length.to_pt())
+                    .collect(),
+                dash_offset: self.dash_offset.to_pt(),
+                alignment: self.alignment,
             },
             visibility: VisibilityMixin(self.visible),
             blend_mode: BlendModeMixin(self.blend_mode),
@@ -907,10 +1163,15 @@ impl StrokeStyle {
     }
 }
 
+#[inline]
+fn default_stroke_miter_limit() -> f32 {
+    4.0
+}
+
 #[derive(Bundle, Debug)]
 pub struct DropShadowStyleBundle {
     pub style: ArbStyle,
-    pub dorp_shadow: DropShadowArbStyle,
+    pub drop_shadow: DropShadowArbStyle,
     pub visibility: VisibilityMixin,
     pub blend_mode: BlendModeMixin,
     pub opacity: OpacityMixin,
@@ -946,7 +1207,7 @@ impl DropShadowStyle {
             style: ArbStyle {
                 variant: ArbStyleVariant::DropShadow,
             },
-            dorp_shadow: DropShadowArbStyle {
+            drop_shadow: DropShadowArbStyle {
                 color: self.color,
                 position: self.position,
                 spread: self.spread,
@@ -963,6 +1224,150 @@ impl DropShadowStyle {
     }
 }
 
+#[derive(Bundle, Debug)]
+pub struct InnerShadowStyleBundle {
+    pub style: ArbStyle,
+    pub inner_shadow: InnerShadowArbStyle,
+    pub visibility: VisibilityMixin,
+    pub blend_mode: BlendModeMixin,
+    pub opacity: OpacityMixin,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct InnerShadowStyle {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub id: Option<ReferenceId>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub color: Color,
+    pub position: Vec2,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub spread: Abs,
+    pub blur: Abs,
+    #[cfg_attr(feature = "specta_support", serde(default = "default_as_true"))]
+    pub visible: bool,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub blend_mode: BlendMode,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub opacity: Opacity,
+}
+
+impl InnerShadowStyle {
+    pub fn to_bundle(&self) -> InnerShadowStyleBundle {
+        InnerShadowStyleBundle {
+            style: ArbStyle {
+                variant: ArbStyleVariant::InnerShadow,
+            },
+            inner_shadow: InnerShadowArbStyle {
+                color: self.color,
+                position: self.position,
+                spread: self.spread,
+                blur: self.blur,
+            },
+            visibility: VisibilityMixin(self.visible),
+            blend_mode: BlendModeMixin(self.blend_mode),
+            opacity: OpacityMixin(self.opacity),
+        }
+    }
+
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        commands.spawn(self.to_bundle())
+    }
+}
+
+#[derive(Bundle, Debug)]
+pub struct LayerBlurStyleBundle {
+    pub style: ArbStyle,
+    pub layer_blur: LayerBlurArbStyle,
+    pub visibility: VisibilityMixin,
+    pub blend_mode: BlendModeMixin,
+    pub opacity: OpacityMixin,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct LayerBlurStyle {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub id: Option<ReferenceId>,
+    pub blur: Abs,
+    #[cfg_attr(feature = "specta_support", serde(default = "default_as_true"))]
+    pub visible: bool,
+}
+
+impl LayerBlurStyle {
+    pub fn to_bundle(&self) -> LayerBlurStyleBundle {
+        LayerBlurStyleBundle {
+            style: ArbStyle {
+                variant: ArbStyleVariant::LayerBlur,
+            },
+            layer_blur: LayerBlurArbStyle { blur: self.blur },
+            visibility: VisibilityMixin(self.visible),
+            blend_mode: BlendModeMixin::default(),
+            opacity: OpacityMixin::default(),
+        }
+    }
+
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        commands.spawn(self.to_bundle())
+    }
+}
+
+#[derive(Bundle, Debug)]
+pub struct BackgroundBlurStyleBundle {
+    pub style: ArbStyle,
+    pub background_blur: BackgroundBlurArbStyle,
+    pub visibility: VisibilityMixin,
+    pub blend_mode: BlendModeMixin,
+    pub opacity: OpacityMixin,
+}
+
+/// Blurs everything behind the node within its shape.
+///
+/// Only rendered by browsers, as SVG filters can't access what is behind an element.
+/// Renderers without HTML support (e.g. the PNG and PDF export) render the node without it.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct BackgroundBlurStyle {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub id: Option<ReferenceId>,
+    pub blur: Abs,
+    #[cfg_attr(feature = "specta_support", serde(default = "default_as_true"))]
+    pub visible: bool,
+}
+
+impl BackgroundBlurStyle {
+    pub fn to_bundle(&self) -> BackgroundBlurStyleBundle {
+        BackgroundBlurStyleBundle {
+            style: ArbStyle {
+                variant: ArbStyleVariant::BackgroundBlur,
+            },
+            background_blur: BackgroundBlurArbStyle { blur: self.blur },
+            visibility: VisibilityMixin(self.visible),
+            blend_mode: BlendModeMixin::default(),
+            opacity: OpacityMixin::default(),
+        }
+    }
+
+    pub fn spawn<'a>(&self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        commands.spawn(self.to_bundle())
+    }
+}
+
 // =============================================================================
 // Asset
 // =============================================================================
@@ -1019,3 +1424,48 @@ impl LuaScriptWithId {
         )
     }
 }
+
+// =============================================================================
+// Variable
+// =============================================================================
+
+/// A named value (design token) that properties can be bound to.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct Variable {
+    pub id: ReferenceId,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub name: Option<String>,
+    /// The value used if no mode is active or the active mode doesn't define a value.
+    pub value: VariableValue,
+    /// Values per mode (e.g. "light", "dark").
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub modes: BTreeMap<String, VariableValue>,
+}
+
+impl Variable {
+    pub fn resolve(&self, maybe_mode: Option<&str>) -> &VariableValue {
+        maybe_mode
+            .and_then(|mode| self.modes.get(mode))
+            .unwrap_or(&self.value)
+    }
+}
+
+/// Binds a property of a node, style or paint to a variable.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct VariableBinding {
+    pub target_id: ReferenceIdOrEntity,
+    pub property: VariableProperty,
+    pub variable_id: ReferenceId,
+}
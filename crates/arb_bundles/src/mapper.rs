@@ -1,73 +1,55 @@
-use std::error::Error;
+use crate::path_data::{parse_path_data, PathDataError, PathDataErrorKind};
+use std::fmt::Write;
 
-pub fn string_to_tiny_skia_path(value: &String) -> Result<tiny_skia_path::Path, Box<dyn Error>> {
+/// Parses SVG path data into a path, failing on the first error.
+pub fn string_to_tiny_skia_path(value: &str) -> Result<tiny_skia_path::Path, PathDataError> {
     let mut builder = tiny_skia_path::PathBuilder::new();
-    let commands = value.split_whitespace();
+    parse_path_data(value, &mut builder)?;
+    return builder.finish().ok_or(PathDataError {
+        position: 0,
+        kind: PathDataErrorKind::EmptyPath,
+    });
+}
 
-    let mut command_iter = commands.peekable();
-    while let Some(command) = command_iter.next() {
-        match command {
-            "M" => {
-                if let (Some(x), Some(y)) = (command_iter.next(), command_iter.next()) {
-                    builder.move_to(x.parse::<f32>()?, y.parse::<f32>()?);
-                }
-            }
-            "L" => {
-                if let (Some(x), Some(y)) = (command_iter.next(), command_iter.next()) {
-                    builder.line_to(x.parse::<f32>()?, y.parse::<f32>()?);
-                }
-            }
-            "Q" => {
-                if let (Some(x1), Some(y1), Some(x2), Some(y2)) = (
-                    command_iter.next(),
-                    command_iter.next(),
-                    command_iter.next(),
-                    command_iter.next(),
-                ) {
-                    builder.quad_to(
-                        x1.parse::<f32>()?,
-                        y1.parse::<f32>()?,
-                        x2.parse::<f32>()?,
-                        y2.parse::<f32>()?,
-                    );
-                }
-            }
-            "C" => {
-                if let (Some(x1), Some(y1), Some(x2), Some(y2), Some(x3), Some(y3)) = (
-                    command_iter.next(),
-                    command_iter.next(),
-                    command_iter.next(),
-                    command_iter.next(),
-                    command_iter.next(),
-                    command_iter.next(),
-                ) {
-                    builder.cubic_to(
-                        x1.parse::<f32>()?,
-                        y1.parse::<f32>()?,
-                        x2.parse::<f32>()?,
-                        y2.parse::<f32>()?,
-                        x3.parse::<f32>()?,
-                        y3.parse::<f32>()?,
-                    );
-                }
-            }
-            "Z" => {
-                builder.close();
-            }
-            _ => {}
-        }
+/// Parses SVG path data into a path, keeping everything up to the first error
+/// and falling back to an empty (zero sized) path if nothing valid remains.
+pub fn string_to_tiny_skia_path_lossy(value: &str) -> tiny_skia_path::Path {
+    let mut builder = tiny_skia_path::PathBuilder::new();
+    if let Err(err) = parse_path_data(value, &mut builder) {
+        log::warn!("Failed to parse path '{}': {}", value, err);
     }
+    return builder.finish().unwrap_or_else(empty_tiny_skia_path);
+}
 
-    return builder.finish().ok_or(Box::new(FailedToParseStringPath));
+/// Creates a zero sized path as placeholder for shapes without outline,
+/// since a `tiny_skia_path::Path` can't be empty.
+pub fn empty_tiny_skia_path() -> tiny_skia_path::Path {
+    let mut builder = tiny_skia_path::PathBuilder::new();
+    builder.move_to(0.0, 0.0);
+    builder.line_to(0.0, 0.0);
+    return builder.finish().unwrap();
 }
 
-#[derive(Debug, Clone)]
-pub struct FailedToParseStringPath;
+pub fn tiny_skia_path_to_string(path: &tiny_skia_path::Path) -> String {
+    let mut value = String::new();
 
-impl std::fmt::Display for FailedToParseStringPath {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Failed to parse string path!")
+    for segment in path.segments() {
+        let _ = match segment {
+            tiny_skia_path::PathSegment::MoveTo(p) => write!(value, "M {} {} ", p.x, p.y),
+            tiny_skia_path::PathSegment::LineTo(p) => write!(value, "L {} {} ", p.x, p.y),
+            tiny_skia_path::PathSegment::QuadTo(p0, p1) => {
+                write!(value, "Q {} {} {} {} ", p0.x, p0.y, p1.x, p1.y)
+            }
+            tiny_skia_path::PathSegment::CubicTo(p0, p1, p2) => write!(
+                value,
+                "C {} {} {} {} {} {} ",
+                p0.x, p0.y, p1.x, p1.y, p2.x, p2.y
+            ),
+            tiny_skia_path::PathSegment::Close => write!(value, "Z "),
+        };
     }
-}
 
-impl std::error::Error for FailedToParseStringPath {}
+    value.pop(); // ' '
+
+    return value;
+}
@@ -0,0 +1,537 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+use tiny_skia_path::PathBuilder;
+
+/// Parses SVG path data (the `d` attribute) into the provided path builder.
+///
+/// Supports all commands of the SVG specification (absolute and relative),
+/// implicit command repetition, compact number notation (e.g. `m10-5.5.5`)
+/// and elliptical arcs, which are approximated by cubic bezier curves.
+///
+/// As defined by the specification, everything up to the first error is added to the builder.
+///
+/// https://www.w3.org/TR/SVG2/paths.html#PathDataBNF
+pub fn parse_path_data(value: &str, builder: &mut PathBuilder) -> Result<(), PathDataError> {
+    let mut parser = PathDataParser::new(value);
+    let mut current = (0.0, 0.0);
+    let mut subpath_start = (0.0, 0.0);
+    let mut last_control: Option<(f32, f32)> = None;
+    let mut maybe_previous_command: Option<u8> = None;
+
+    loop {
+        parser.skip_whitespace();
+        let byte = match parser.peek() {
+            Some(byte) => byte,
+            None => break,
+        };
+        let command_position = parser.position;
+
+        // Determine command either explicitly or by repeating the previous one
+        let command = if is_command(byte) {
+            parser.position += 1;
+            byte
+        } else if is_number_start(byte) {
+            match maybe_previous_command {
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z') | Some(b'z') | None => {
+                    return Err(
+                        parser.error_at(command_position, PathDataErrorKind::ExpectedCommand)
+                    )
+                }
+                Some(previous_command) => previous_command,
+            }
+        } else {
+            return Err(parser.unexpected_character());
+        };
+
+        if maybe_previous_command.is_none() && command != b'M' && command != b'm' {
+            return Err(parser.error_at(command_position, PathDataErrorKind::ExpectedMoveTo));
+        }
+
+        let is_relative = command.is_ascii_lowercase();
+        let to_absolute = |(x, y): (f32, f32)| {
+            if is_relative {
+                (current.0 + x, current.1 + y)
+            } else {
+                (x, y)
+            }
+        };
+        let mut next_control = None;
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point = to_absolute(parser.parse_point()?);
+                builder.move_to(point.0, point.1);
+                current = point;
+                subpath_start = point;
+            }
+            b'L' => {
+                let point = to_absolute(parser.parse_point()?);
+                builder.line_to(point.0, point.1);
+                current = point;
+            }
+            b'H' => {
+                let x = parser.parse_number()?;
+                let point = (if is_relative { current.0 + x } else { x }, current.1);
+                builder.line_to(point.0, point.1);
+                current = point;
+            }
+            b'V' => {
+                let y = parser.parse_number()?;
+                let point = (current.0, if is_relative { current.1 + y } else { y });
+                builder.line_to(point.0, point.1);
+                current = point;
+            }
+            b'C' => {
+                let control1 = to_absolute(parser.parse_point()?);
+                let control2 = to_absolute(parser.parse_point()?);
+                let point = to_absolute(parser.parse_point()?);
+                builder.cubic_to(
+                    control1.0, control1.1, control2.0, control2.1, point.0, point.1,
+                );
+                next_control = Some(control2);
+                current = point;
+            }
+            b'S' => {
+                let control1 = match maybe_previous_command.map(|c| c.to_ascii_uppercase()) {
+                    Some(b'C') | Some(b'S') => reflect(last_control, current),
+                    _ => current,
+                };
+                let control2 = to_absolute(parser.parse_point()?);
+                let point = to_absolute(parser.parse_point()?);
+                builder.cubic_to(
+                    control1.0, control1.1, control2.0, control2.1, point.0, point.1,
+                );
+                next_control = Some(control2);
+                current = point;
+            }
+            b'Q' => {
+                let control = to_absolute(parser.parse_point()?);
+                let point = to_absolute(parser.parse_point()?);
+                builder.quad_to(control.0, control.1, point.0, point.1);
+                next_control = Some(control);
+                current = point;
+            }
+            b'T' => {
+                let control = match maybe_previous_command.map(|c| c.to_ascii_uppercase()) {
+                    Some(b'Q') | Some(b'T') => reflect(last_control, current),
+                    _ => current,
+                };
+                let point = to_absolute(parser.parse_point()?);
+                builder.quad_to(control.0, control.1, point.0, point.1);
+                next_control = Some(control);
+                current = point;
+            }
+            b'A' => {
+                let rx = parser.parse_number()?;
+                let ry = parser.parse_number()?;
+                let x_axis_rotation = parser.parse_number()?;
+                let large_arc = parser.parse_flag()?;
+                let sweep = parser.parse_flag()?;
+                let point = to_absolute(parser.parse_point()?);
+                arc_to(
+                    builder,
+                    current,
+                    (rx, ry),
+                    x_axis_rotation,
+                    large_arc,
+                    sweep,
+                    point,
+                );
+                current = point;
+            }
+            b'Z' => {
+                builder.close();
+                current = subpath_start;
+            }
+            _ => unreachable!(),
+        }
+
+        last_control = next_control;
+        maybe_previous_command = Some(command);
+    }
+
+    return Ok(());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathDataError {
+    /// Byte offset in the path data at which the error occurred.
+    pub position: usize,
+    pub kind: PathDataErrorKind,
+}
+
+impl std::fmt::Display for PathDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.kind, self.position)
+    }
+}
+
+impl std::error::Error for PathDataError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathDataErrorKind {
+    UnexpectedCharacter(char),
+    UnexpectedEnd,
+    ExpectedNumber,
+    ExpectedFlag,
+    ExpectedCommand,
+    /// Path data has to start with a move to command.
+    ExpectedMoveTo,
+    /// Path data doesn't describe any segment.
+    EmptyPath,
+}
+
+impl std::fmt::Display for PathDataErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedCharacter(c) => write!(f, "Unexpected character '{}'", c),
+            Self::UnexpectedEnd => write!(f, "Unexpected end of path data"),
+            Self::ExpectedNumber => write!(f, "Expected number"),
+            Self::ExpectedFlag => write!(f, "Expected flag ('0' or '1')"),
+            Self::ExpectedCommand => write!(f, "Expected command"),
+            Self::ExpectedMoveTo => write!(f, "Expected move to command ('M' or 'm')"),
+            Self::EmptyPath => write!(f, "Path data is empty"),
+        }
+    }
+}
+
+struct PathDataParser<'a> {
+    value: &'a str,
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PathDataParser<'a> {
+    fn new(value: &'a str) -> Self {
+        Self {
+            value,
+            bytes: value.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            if !is_whitespace(byte) {
+                break;
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Skips whitespace and at most one comma.
+    fn skip_separator(&mut self) {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.position += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn parse_point(&mut self) -> Result<(f32, f32), PathDataError> {
+        return Ok((self.parse_number()?, self.parse_number()?));
+    }
+
+    fn parse_number(&mut self) -> Result<f32, PathDataError> {
+        self.skip_whitespace();
+        let start = self.position;
+
+        if let Some(b'+') | Some(b'-') = self.peek() {
+            self.position += 1;
+        }
+        let integer_digits = self.skip_digits();
+        let mut fraction_digits = 0;
+        if self.peek() == Some(b'.') {
+            self.position += 1;
+            fraction_digits = self.skip_digits();
+        }
+        if integer_digits == 0 && fraction_digits == 0 {
+            return Err(self.expected(start, PathDataErrorKind::ExpectedNumber));
+        }
+
+        // Exponent (only if followed by digits, as 'e' isn't a command)
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            let exponent_start = self.position;
+            self.position += 1;
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.position += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.position = exponent_start;
+            }
+        }
+
+        let number = self.value[start..self.position]
+            .parse::<f32>()
+            .map_err(|_| self.error_at(start, PathDataErrorKind::ExpectedNumber))?;
+        self.skip_separator();
+
+        return Ok(number);
+    }
+
+    /// Parses an arc flag, which doesn't need to be separated from the following value.
+    fn parse_flag(&mut self) -> Result<bool, PathDataError> {
+        self.skip_whitespace();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.expected(self.position, PathDataErrorKind::ExpectedFlag)),
+        };
+        self.position += 1;
+        self.skip_separator();
+
+        return Ok(flag);
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        return self.position - start;
+    }
+
+    fn error_at(&self, position: usize, kind: PathDataErrorKind) -> PathDataError {
+        PathDataError { position, kind }
+    }
+
+    /// Reports the expected value or the end of the path data if nothing is left.
+    fn expected(&self, position: usize, kind: PathDataErrorKind) -> PathDataError {
+        if position >= self.bytes.len() {
+            self.error_at(position, PathDataErrorKind::UnexpectedEnd)
+        } else {
+            self.error_at(position, kind)
+        }
+    }
+
+    fn unexpected_character(&self) -> PathDataError {
+        let c = self.value[self.position..]
+            .chars()
+            .next()
+            .unwrap_or_default();
+        self.error_at(self.position, PathDataErrorKind::UnexpectedCharacter(c))
+    }
+}
+
+fn is_command(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'M' | b'm'
+            | b'L'
+            | b'l'
+            | b'H'
+            | b'h'
+            | b'V'
+            | b'v'
+            | b'C'
+            | b'c'
+            | b'S'
+            | b's'
+            | b'Q'
+            | b'q'
+            | b'T'
+            | b't'
+            | b'A'
+            | b'a'
+            | b'Z'
+            | b'z'
+    )
+}
+
+fn is_number_start(byte: u8) -> bool {
+    byte.is_ascii_digit() || matches!(byte, b'+' | b'-' | b'.')
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | b'\x0C')
+}
+
+/// Reflects the last control point about the current point
+/// or uses the current point if there is no last control point.
+fn reflect(last_control: Option<(f32, f32)>, current: (f32, f32)) -> (f32, f32) {
+    match last_control {
+        Some(control) => (2.0 * current.0 - control.0, 2.0 * current.1 - control.1),
+        None => current,
+    }
+}
+
+/// Approximates an elliptical arc with cubic bezier curves of at most 90° each.
+///
+/// https://www.w3.org/TR/SVG2/implnote.html#ArcImplementationNotes
+fn arc_to(
+    builder: &mut PathBuilder,
+    from: (f32, f32),
+    radii: (f32, f32),
+    x_axis_rotation_deg: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: (f32, f32),
+) {
+    if from == to {
+        return;
+    }
+
+    let (mut rx, mut ry) = (radii.0.abs(), radii.1.abs());
+    if rx == 0.0 || ry == 0.0 {
+        builder.line_to(to.0, to.1);
+        return;
+    }
+
+    let (sin_phi, cos_phi) = x_axis_rotation_deg.to_radians().sin_cos();
+
+    // Compute (x1', y1')
+    let dx = (from.0 - to.0) / 2.0;
+    let dy = (from.1 - to.1) / 2.0;
+    let x1 = cos_phi * dx + sin_phi * dy;
+    let y1 = -sin_phi * dx + cos_phi * dy;
+
+    // Scale up radii that are too small to reach the end point
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        let scale = lambda.sqrt();
+        rx *= scale;
+        ry *= scale;
+    }
+
+    // Compute center (cx', cy') and transform it back to (cx, cy)
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let cx1 = coefficient * rx * y1 / ry;
+    let cy1 = -coefficient * ry * x1 / rx;
+    let cx = cos_phi * cx1 - sin_phi * cy1 + (from.0 + to.0) / 2.0;
+    let cy = sin_phi * cx1 + cos_phi * cy1 + (from.1 + to.1) / 2.0;
+
+    // Compute start angle and sweep angle
+    let start_vector = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let end_vector = ((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+    let start_angle = vector_angle((1.0, 0.0), start_vector);
+    let mut sweep_angle = vector_angle(start_vector, end_vector);
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    }
+
+    let segment_count = (sweep_angle.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let segment_angle = sweep_angle / segment_count as f32;
+    let t = 4.0 / 3.0 * (segment_angle / 4.0).tan();
+    let map_point = |(x, y): (f32, f32)| {
+        (
+            cx + rx * x * cos_phi - ry * y * sin_phi,
+            cy + rx * x * sin_phi + ry * y * cos_phi,
+        )
+    };
+
+    for index in 0..segment_count {
+        let angle1 = start_angle + index as f32 * segment_angle;
+        let angle2 = angle1 + segment_angle;
+        let (sin1, cos1) = angle1.sin_cos();
+        let (sin2, cos2) = angle2.sin_cos();
+
+        let control1 = map_point((cos1 - t * sin1, sin1 + t * cos1));
+        let control2 = map_point((cos2 + t * sin2, sin2 - t * cos2));
+        let point = if index == segment_count - 1 {
+            to
+        } else {
+            map_point((cos2, sin2))
+        };
+
+        builder.cubic_to(
+            control1.0, control1.1, control2.0, control2.1, point.0, point.1,
+        );
+    }
+}
+
+fn vector_angle(u: (f32, f32), v: (f32, f32)) -> f32 {
+    (u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::{
+        string_to_tiny_skia_path, string_to_tiny_skia_path_lossy, tiny_skia_path_to_string,
+    };
+
+    fn parse(value: &str) -> String {
+        tiny_skia_path_to_string(&string_to_tiny_skia_path(value).unwrap())
+    }
+
+    #[test]
+    fn test_relative_commands_and_compact_numbers() {
+        assert_eq!(parse("m10,20l5-3"), "M 10 20 L 15 17");
+        assert_eq!(parse("M1.5.5L-1e1+2"), "M 1.5 0.5 L -10 2");
+    }
+
+    #[test]
+    fn test_implicit_repeats() {
+        assert_eq!(parse("m0 0 10 0 0 10z"), "M 0 0 L 10 0 L 10 10 Z");
+        assert_eq!(
+            parse("M0 0 H10 V10 h-10 v-10"),
+            "M 0 0 L 10 0 L 10 10 L 0 10 L 0 0"
+        );
+    }
+
+    #[test]
+    fn test_smooth_curves() {
+        assert_eq!(
+            parse("M0 0 C0 10 10 10 10 0 S20 -10 20 0"),
+            "M 0 0 C 0 10 10 10 10 0 C 10 -10 20 -10 20 0"
+        );
+        assert_eq!(
+            parse("M0 0 Q5 10 10 0 T20 0"),
+            "M 0 0 Q 5 10 10 0 Q 15 -10 20 0"
+        );
+    }
+
+    #[test]
+    fn test_arcs() {
+        let path = string_to_tiny_skia_path("M0 0 a10 10 0 00 20 0").unwrap();
+        let bounds = path.bounds();
+
+        assert_eq!(path.segments().count(), 3);
+        assert!((bounds.right() - 20.0).abs() < 0.001);
+        assert!((bounds.bottom() - 10.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            string_to_tiny_skia_path("M10 20 L5").unwrap_err(),
+            PathDataError {
+                position: 9,
+                kind: PathDataErrorKind::UnexpectedEnd
+            }
+        );
+        assert_eq!(
+            string_to_tiny_skia_path("L10 20").unwrap_err().kind,
+            PathDataErrorKind::ExpectedMoveTo
+        );
+        assert_eq!(
+            string_to_tiny_skia_path("M10 20 X").unwrap_err(),
+            PathDataError {
+                position: 7,
+                kind: PathDataErrorKind::UnexpectedCharacter('X')
+            }
+        );
+    }
+
+    #[test]
+    fn test_lossy() {
+        assert_eq!(
+            tiny_skia_path_to_string(&string_to_tiny_skia_path_lossy("M0 0 L10 10 L5")),
+            "M 0 0 L 10 10"
+        );
+        assert_eq!(
+            tiny_skia_path_to_string(&string_to_tiny_skia_path_lossy("foo")),
+            "M 0 0 L 0 0"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::reference_id::ReferenceIdOrEntity;
 use dyn_attributed_string::{
     dyn_fonts_book::font::{
         info::FontFamily,
@@ -6,12 +7,16 @@ use dyn_attributed_string::{
     text_attrs::{TextAttrs, TextAttrsInterval},
 };
 use dyn_utils::{
-    properties::size::Size,
-    units::{abs::Abs, font_unit::FontUnit},
+    properties::{color::Color, size::Size},
+    units::{abs::Abs, font_unit::FontUnit, ratio::Ratio},
 };
 use glam::Vec2;
 
-#[derive(Debug, Default, Copy, Clone)]
+/// Version of the artboard (DTIF) schema.
+///
+/// Variants must be declared in chronological order
+/// as the ordering is used to determine which migrations to apply.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 #[cfg_attr(
     feature = "specta_support",
     derive(serde::Serialize, serde::Deserialize, specta::Type)
@@ -22,6 +27,11 @@ pub enum ArbVersion {
     V000001, // v0.0.1
 }
 
+impl ArbVersion {
+    /// The version the current schema corresponds to.
+    pub const LATEST: ArbVersion = ArbVersion::V000001;
+}
+
 #[derive(Debug, Default, Copy, Clone)]
 #[cfg_attr(
     feature = "specta_support",
@@ -99,6 +109,93 @@ pub struct TextAttributes {
     pub line_height: Option<FontUnit>,
 }
 
+/// Overrides properties of a node or style within the component of an instance.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct InstanceOverride {
+    /// The node or style within the component to override.
+    pub target_id: ReferenceIdOrEntity,
+    /// Replaces the text of a text node.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub text: Option<String>,
+    /// Replaces the paint of a fill or stroke style.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub paint_id: Option<ReferenceIdOrEntity>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub visible: Option<bool>,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub size: Option<Size>,
+}
+
+/// Typed value of a variable (design token).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(tag = "type")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum VariableValue {
+    Color { value: Color },
+    Number { value: f32 },
+    String { value: String },
+    FontFamily { value: FontFamily },
+}
+
+/// Property of a node, style or paint whose value can be bound to a variable.
+///
+/// Only the targets listed per property are supported,
+/// bindings to any other target (e.g. the color of a gradient stop or inner shadow)
+/// are ignored with a warning.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum VariableProperty {
+    /// Color of a solid paint or drop shadow style.
+    Color,
+    /// Opacity of a node or style (between 0.0 and 1.0).
+    Opacity,
+    /// Width of a node.
+    Width,
+    /// Height of a node.
+    Height,
+    /// Width of a stroke style.
+    StrokeWidth,
+    /// Text of a text node.
+    Text,
+    /// Font size of all attribute intervals of a text node.
+    FontSize,
+    /// Font family of all attribute intervals of a text node.
+    FontFamily,
+}
+
+impl VariableProperty {
+    /// Whether a variable with the given value can be bound to this property.
+    pub fn accepts(&self, value: &VariableValue) -> bool {
+        match self {
+            VariableProperty::Color => matches!(value, VariableValue::Color { .. }),
+            VariableProperty::Opacity
+            | VariableProperty::Width
+            | VariableProperty::Height
+            | VariableProperty::StrokeWidth
+            | VariableProperty::FontSize => matches!(value, VariableValue::Number { .. }),
+            VariableProperty::Text => matches!(value, VariableValue::String { .. }),
+            VariableProperty::FontFamily => matches!(
+                value,
+                VariableValue::FontFamily { .. } | VariableValue::String { .. }
+            ),
+        }
+    }
+}
+
 /// Used to control how child nodes are aligned.
 /// For Flexbox it controls alignment in the cross axis
 /// For Grid it controls alignment in the block axis
@@ -283,3 +380,159 @@ impl From<FlexDirection> for taffy::FlexDirection {
         }
     }
 }
+
+/// Controls whether flex items are forced onto one line or can wrap onto multiple lines.
+///
+/// The default behavior is [`FlexWrap::NoWrap`].
+///
+/// [Specification](https://www.w3.org/TR/css-flexbox-1/#flex-wrap-property)
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum FlexWrap {
+    /// Items will not wrap and stay on a single line
+    #[default]
+    NoWrap,
+    /// Items will wrap according to this item's [`FlexDirection`]
+    Wrap,
+    /// Items will wrap in the opposite direction to this item's [`FlexDirection`]
+    WrapReverse,
+}
+
+impl From<FlexWrap> for taffy::FlexWrap {
+    fn from(value: FlexWrap) -> Self {
+        match value {
+            FlexWrap::NoWrap => taffy::FlexWrap::NoWrap,
+            FlexWrap::Wrap => taffy::FlexWrap::Wrap,
+            FlexWrap::WrapReverse => taffy::FlexWrap::WrapReverse,
+        }
+    }
+}
+
+/// The layout algorithm used to position the children of a static layout parent.
+///
+/// The default behavior is [`Display::Flex`].
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/display)
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum Display {
+    /// Children are laid out one after another along the main axis
+    /// defined by the [`FlexDirection`].
+    #[default]
+    Flex,
+    /// Children are placed into the cells of a grid
+    /// defined by the template rows and columns.
+    Grid,
+}
+
+impl From<Display> for taffy::Display {
+    fn from(value: Display) -> Self {
+        match value {
+            Display::Flex => taffy::Display::Flex,
+            Display::Grid => taffy::Display::Grid,
+        }
+    }
+}
+
+/// The size of a single track (row or column) of a grid layout.
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-columns)
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(tag = "type")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum GridTrack {
+    /// A fixed size.
+    Abs { value: Abs },
+    /// A size relative to the size of the grid parent.
+    Ratio { value: Ratio },
+    /// A share of the free space left after all other tracks have been sized
+    /// (similar to the `fr` unit in CSS).
+    Fraction { value: f32 },
+    /// Sized to fit the content of the track.
+    Auto,
+}
+
+impl From<GridTrack> for taffy::TrackSizingFunction {
+    fn from(value: GridTrack) -> Self {
+        match value {
+            GridTrack::Abs { value } => taffy::style_helpers::length(value.to_pt()),
+            GridTrack::Ratio { value } => taffy::style_helpers::percent(value.get()),
+            // Unlike the CSS `fr` unit the track has no content based minimum size,
+            // so that fraction tracks are always distributed exactly by their ratio
+            GridTrack::Fraction { value } => taffy::style_helpers::minmax(
+                taffy::MinTrackSizingFunction::Fixed(taffy::LengthPercentage::Length(0.0)),
+                taffy::style_helpers::fr(value),
+            ),
+            GridTrack::Auto => taffy::style_helpers::auto(),
+        }
+    }
+}
+
+/// A grid line an element edge is placed at.
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-row-start)
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(tag = "type")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum GridLine {
+    /// Placed by the auto-placement algorithm.
+    #[default]
+    Auto,
+    /// Placed at the line with the given index.
+    ///
+    /// Lines are 1-based, negative indices count backwards from the last line.
+    Line { index: i16 },
+    /// Placed the given number of tracks away from the opposite edge.
+    Span { count: u16 },
+}
+
+impl From<GridLine> for taffy::GridPlacement {
+    fn from(value: GridLine) -> Self {
+        match value {
+            GridLine::Auto => taffy::GridPlacement::Auto,
+            GridLine::Line { index } => taffy::style_helpers::line(index),
+            GridLine::Span { count } => taffy::GridPlacement::Span(count),
+        }
+    }
+}
+
+/// The placement of an element along one axis of a grid layout.
+///
+/// [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-row)
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct GridPlacement {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub start: GridLine,
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub end: GridLine,
+}
+
+impl From<GridPlacement> for taffy::Line<taffy::GridPlacement> {
+    fn from(value: GridPlacement) -> Self {
+        taffy::Line {
+            start: value.start.into(),
+            end: value.end.into(),
+        }
+    }
+}
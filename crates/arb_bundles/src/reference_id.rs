@@ -17,6 +17,12 @@ impl ReferenceId {
     }
 }
 
+impl std::fmt::Display for ReferenceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(
     feature = "specta_support",
@@ -63,6 +63,14 @@ pub fn length_to_taffy(value: Length) -> taffy::LengthPercentage {
     }
 }
 
+pub fn optional_length_to_taffy(value: Option<Length>) -> taffy::Dimension {
+    match value {
+        Some(Length::Abs { value }) => taffy::Dimension::Length(value.to_pt()),
+        Some(Length::Ratio { value }) => taffy::Dimension::Percent(value.get()),
+        None => taffy::Dimension::Auto,
+    }
+}
+
 pub fn auto_length_to_taffy(value: AutoLength) -> taffy::LengthPercentageAuto {
     match value {
         AutoLength::Abs { value } => taffy::LengthPercentageAuto::Length(value.to_pt()),
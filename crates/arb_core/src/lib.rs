@@ -1,6 +1,8 @@
 mod debug;
 pub mod resources;
 mod systems;
+#[cfg(all(test, feature = "specta_support"))]
+mod test_utils;
 
 use bevy_app::{App, First, Last, Plugin, Update};
 use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet};
@@ -11,25 +13,34 @@ use dyn_arb_bundles::{
     properties::{ArbVersion, Viewport},
 };
 use dyn_utils::properties::size::Size;
-use resources::{artboard::ArtboardRes, layout::LayoutRes, referencer::ReferencerRes, tick::TickRes};
+use resources::{
+    artboard::ArtboardRes, layout::LayoutRes, referencer::ReferencerRes, tick::TickRes,
+    variables::VariablesRes,
+};
 use systems::{
     cleanup::despawn_removed_entities_system,
     events::{
-        create_asset_input_system, create_node_input_system, create_paint_input_system,
-        delete_entity_input_system, focus_root_nodes_input_system, move_entity_input_system,
-        update_artboard_size_input_system, update_artboard_viewport_input_system,
-        update_drop_shadow_style_input_system, update_ellipse_node_input_system,
-        update_entity_blend_mode_input_system, update_entity_children_input_system,
-        update_entity_corner_radii_input_system, update_entity_opacity_input_system,
-        update_entity_rotation_input_system, update_entity_size_input_system,
-        update_entity_transform_input_system, update_entity_visibility_input_system,
-        update_fill_style_input_system, update_frame_node_input_system,
-        update_gradient_paint_input_system, update_image_paint_input_system,
+        bind_variable_input_system, create_asset_input_system, create_node_input_system,
+        create_paint_input_system, create_style_input_system, create_variable_input_system,
+        delete_entity_input_system, delete_style_input_system, focus_root_nodes_input_system,
+        move_entity_input_system, reorder_styles_input_system, update_artboard_size_input_system,
+        update_artboard_viewport_input_system, update_background_blur_style_input_system,
+        update_boolean_operation_node_input_system, update_drop_shadow_style_input_system,
+        update_ellipse_node_input_system, update_entity_blend_mode_input_system,
+        update_entity_children_input_system, update_entity_corner_radii_input_system,
+        update_entity_opacity_input_system, update_entity_rotation_input_system,
+        update_entity_size_input_system, update_entity_transform_input_system,
+        update_entity_visibility_input_system, update_fill_style_input_system,
+        update_frame_node_input_system, update_gradient_paint_input_system,
+        update_image_paint_input_system, update_inner_shadow_style_input_system,
+        update_instance_node_input_system, update_layer_blur_style_input_system,
         update_polygon_node_input_system, update_solid_paint_input_system,
         update_star_node_input_system, update_storke_style_input_system,
-        update_text_node_input_system,
+        update_text_node_input_system, update_variable_input_system,
+        update_variable_mode_input_system,
     },
     hierarchy::{add_root_component_system, remove_root_component_system, update_hierarchy_levels},
+    instance::sync_instances_system,
     layout::{
         absolute_layout::{apply_pre_absolute_layout_properties, update_absolute_layout},
         static_layout::{
@@ -39,12 +50,14 @@ use systems::{
         },
     },
     outline::{
-        ellipse::outline_ellipse, polygon::outline_polygon, rectangle::outline_rectangle,
-        star::outline_star, text::outline_text,
+        boolean_operation::outline_boolean_operation, ellipse::outline_ellipse,
+        polygon::outline_polygon, rectangle::outline_rectangle, star::outline_star,
+        text::outline_text,
     },
     stroke::stroke_path_system,
     text::{compute_text_from_scratch, compute_text_on_size_change},
     tick::collect_first_tick,
+    variables::apply_variable_bindings_system,
     vector::resize_vector_node,
 };
 
@@ -52,6 +65,7 @@ pub struct ArbCorePlugin {
     pub version: Option<ArbVersion>,
     pub size: Size,
     pub viewport: Option<Viewport>,
+    pub sub_pixel_layout: bool,
 }
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -95,10 +109,12 @@ impl Plugin for ArbCorePlugin {
         app.init_resource::<LayoutRes>();
         app.init_resource::<TickRes>();
         app.init_resource::<ReferencerRes>();
+        app.init_resource::<VariablesRes>();
         app.insert_resource(ArtboardRes {
             version: self.version.unwrap_or_default(),
             viewport: self.viewport.unwrap_or_default(),
             size: self.size,
+            sub_pixel_layout: self.sub_pixel_layout,
         });
         #[cfg(feature = "lua_scripts")]
         app.init_resource::<resources::lua::LuaRes>();
@@ -140,10 +156,14 @@ impl Plugin for ArbCorePlugin {
             Update,
             (
                 create_asset_input_system.in_set(ArbCoreSystemSet::PreCreateInputEvents),
+                create_variable_input_system.in_set(ArbCoreSystemSet::PreCreateInputEvents),
                 create_paint_input_system
                     .in_set(ArbCoreSystemSet::PreCreateInputEvents)
                     .after(create_asset_input_system),
                 create_node_input_system.in_set(ArbCoreSystemSet::CreateInputEvents),
+                create_style_input_system
+                    .in_set(ArbCoreSystemSet::CreateInputEvents)
+                    .after(create_node_input_system),
             ),
         );
         app.add_systems(
@@ -163,15 +183,26 @@ impl Plugin for ArbCorePlugin {
                 update_star_node_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
                 update_polygon_node_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
                 update_text_node_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                update_instance_node_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                update_boolean_operation_node_input_system
+                    .in_set(ArbCoreSystemSet::UpdateInputEvents),
             ),
         );
         app.add_systems(
             Update,
             (
                 // Style
+                delete_style_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                reorder_styles_input_system
+                    .in_set(ArbCoreSystemSet::UpdateInputEvents)
+                    .after(delete_style_input_system),
                 update_fill_style_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
                 update_storke_style_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
                 update_drop_shadow_style_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                update_inner_shadow_style_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                update_layer_blur_style_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                update_background_blur_style_input_system
+                    .in_set(ArbCoreSystemSet::UpdateInputEvents),
             ),
         );
         app.add_systems(
@@ -199,11 +230,31 @@ impl Plugin for ArbCorePlugin {
                 update_entity_children_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
             ),
         );
+        app.add_systems(
+            Update,
+            (
+                // Variable
+                update_variable_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                update_variable_mode_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+                bind_variable_input_system.in_set(ArbCoreSystemSet::UpdateInputEvents),
+            ),
+        );
+        app.add_systems(
+            Update,
+            (
+                apply_variable_bindings_system.in_set(ArbCoreSystemSet::PreCompute),
+                sync_instances_system
+                    .in_set(ArbCoreSystemSet::PreCompute)
+                    .after(apply_variable_bindings_system),
+            ),
+        );
         app.add_systems(
             Update,
             (
                 compute_text_from_scratch.in_set(ArbCoreSystemSet::Compute),
-                compute_text_on_size_change.in_set(ArbCoreSystemSet::Compute),
+                // Text is measured during the static layout,
+                // thus the glyphs are positioned once the final size is known
+                compute_text_on_size_change.in_set(ArbCoreSystemSet::Prepare),
             ),
         );
         app.add_systems(
@@ -227,6 +278,15 @@ impl Plugin for ArbCorePlugin {
                 outline_star.in_set(ArbCoreSystemSet::Outline),
                 outline_polygon.in_set(ArbCoreSystemSet::Outline),
                 outline_text.in_set(ArbCoreSystemSet::Outline),
+                // Boolean operations combine the outlines of their children
+                outline_boolean_operation
+                    .in_set(ArbCoreSystemSet::Outline)
+                    .after(resize_vector_node)
+                    .after(outline_rectangle)
+                    .after(outline_ellipse)
+                    .after(outline_star)
+                    .after(outline_polygon)
+                    .after(outline_text),
                 stroke_path_system.in_set(ArbCoreSystemSet::PostOutline),
             ),
         );
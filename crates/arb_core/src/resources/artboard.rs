@@ -7,4 +7,7 @@ pub struct ArtboardRes {
     pub version: ArbVersion,
     pub viewport: Viewport,
     pub size: Size,
+    /// Whether the static layout keeps sub-pixel precision
+    /// instead of rounding to whole pixels.
+    pub sub_pixel_layout: bool,
 }
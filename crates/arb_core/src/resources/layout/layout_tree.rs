@@ -3,6 +3,7 @@ use bevy_ecs::entity::Entity;
 use dyn_arb_bundles::components::mixins::{
     LayoutElementSizingMode, LayoutParentSizingMode, StaticLayoutElement, StaticLayoutParent,
 };
+use dyn_attributed_string::layout::TextSizingMode;
 use dyn_utils::properties::size::Size;
 use std::collections::HashMap;
 use taffy::{prelude::*, TaffyError};
@@ -59,16 +60,43 @@ impl LayoutTree {
             .map_err(|e| LayoutError::TaffyError(e))
     }
 
-    pub fn compute_layout(
+    /// Computes the layout of the node and its descendants.
+    ///
+    /// The measure function determines the intrinsic size of leaf nodes
+    /// whose size depends on their content (e.g. text).
+    pub fn compute_layout<F>(
         &mut self,
         node_id: NodeId,
         available_space: taffy::Size<taffy::AvailableSpace>,
-    ) -> Result<(), LayoutError> {
+        mut measure_function: F,
+    ) -> Result<(), LayoutError>
+    where
+        F: FnMut(
+            taffy::Size<Option<f32>>,
+            taffy::Size<taffy::AvailableSpace>,
+            NodeId,
+        ) -> taffy::Size<f32>,
+    {
         self.taffy_tree
-            .compute_layout(node_id, available_space)
+            .compute_layout_with_measure(
+                node_id,
+                available_space,
+                |known_dimensions, available_space, node_id, _, _| {
+                    measure_function(known_dimensions, available_space, node_id)
+                },
+            )
             .map_err(|e| LayoutError::TaffyError(e))
     }
 
+    /// Sets whether the computed layout is rounded to whole pixels.
+    pub fn set_rounding(&mut self, enabled: bool) {
+        if enabled {
+            self.taffy_tree.enable_rounding();
+        } else {
+            self.taffy_tree.disable_rounding();
+        }
+    }
+
     pub fn get_layout(&self, node_id: NodeId) -> Result<&Layout, LayoutError> {
         self.taffy_tree
             .layout(node_id)
@@ -79,6 +107,7 @@ impl LayoutTree {
         maybe_layout_parent: Option<&StaticLayoutParent>,
         maybe_static_layout_element: Option<&StaticLayoutElement>,
         size: &Size,
+        maybe_text_sizing_mode: Option<TextSizingMode>,
     ) -> Style {
         let mut style = Style::default();
 
@@ -94,26 +123,74 @@ impl LayoutTree {
             style.align_self = layout_element_style.align_self;
             style.justify_self = layout_element_style.justify_self;
             style.margin = layout_element_style.margin;
+            style.grid_row = layout_element_style.grid_row;
+            style.grid_column = layout_element_style.grid_column;
+            style.min_size = layout_element_style.min_size;
+            style.max_size = layout_element_style.max_size;
+            style.aspect_ratio = layout_element_style.aspect_ratio;
+            style.flex_grow = layout_element_style.flex_grow;
+            style.flex_shrink = layout_element_style.flex_shrink;
 
             taffy_size.width = match static_layout_element.horizontal_sizing_mode {
                 LayoutElementSizingMode::Fixed => Dimension::Length(size.width()),
-                LayoutElementSizingMode::Fill => Dimension::Percent(100.0),
+                LayoutElementSizingMode::Fill => Dimension::Percent(1.0),
             };
             taffy_size.height = match static_layout_element.vertical_sizing_mode {
                 LayoutElementSizingMode::Fixed => Dimension::Length(size.height()),
-                LayoutElementSizingMode::Fill => Dimension::Percent(100.0),
+                LayoutElementSizingMode::Fill => Dimension::Percent(1.0),
+            };
+
+            // Derive the fixed axis from the filling axis so that the aspect ratio is preserved
+            if static_layout_element.aspect_ratio.is_some() {
+                match (
+                    static_layout_element.horizontal_sizing_mode,
+                    static_layout_element.vertical_sizing_mode,
+                ) {
+                    (LayoutElementSizingMode::Fill, LayoutElementSizingMode::Fixed) => {
+                        taffy_size.height = Dimension::Auto;
+                    }
+                    (LayoutElementSizingMode::Fixed, LayoutElementSizingMode::Fill) => {
+                        taffy_size.width = Dimension::Auto;
+                    }
+                    _ => {}
+                }
+
+                // Stretching the derived axis along the cross axis would override the ratio
+                if taffy_size.width == Dimension::Auto || taffy_size.height == Dimension::Auto {
+                    style.align_self = style.align_self.or(Some(AlignSelf::Start));
+                }
+            }
+        }
+
+        // Leave the axes that depend on the text content to the measure function
+        if let Some(text_sizing_mode) = maybe_text_sizing_mode {
+            let is_fixed = |sizing_mode: Option<LayoutElementSizingMode>| {
+                sizing_mode.unwrap_or_default() == LayoutElementSizingMode::Fixed
             };
+            if text_sizing_mode == TextSizingMode::WidthAndHeight
+                && is_fixed(maybe_static_layout_element.map(|e| e.horizontal_sizing_mode))
+            {
+                taffy_size.width = Dimension::Auto;
+            }
+            if text_sizing_mode != TextSizingMode::Fixed
+                && is_fixed(maybe_static_layout_element.map(|e| e.vertical_sizing_mode))
+            {
+                taffy_size.height = Dimension::Auto;
+            }
         }
 
         if let Some(static_layout_parent) = maybe_layout_parent {
             let layout_parent_style = static_layout_parent.to_style();
 
-            style.display = Display::Flex;
+            style.display = layout_parent_style.display;
             style.align_items = layout_parent_style.align_items;
             style.justify_content = layout_parent_style.justify_content;
             style.gap = layout_parent_style.gap;
             style.padding = layout_parent_style.padding;
             style.flex_direction = layout_parent_style.flex_direction;
+            style.flex_wrap = layout_parent_style.flex_wrap;
+            style.grid_template_rows = layout_parent_style.grid_template_rows;
+            style.grid_template_columns = layout_parent_style.grid_template_columns;
 
             // TODO: Does this conflict with the layout element?
             taffy_size.width = match static_layout_parent.horizontal_sizing_mode {
@@ -141,3 +218,298 @@ pub enum LayoutError {
     InvalidHierarchy,
     TaffyError(TaffyError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyn_arb_bundles::properties::{Display, FlexWrap, GridLine, GridPlacement, GridTrack};
+    use dyn_utils::units::{abs::Abs, axes::Axes, length::Length, ratio::Ratio};
+
+    /// Lays out the children of the given size within a parent of the given size
+    /// and returns their position and size.
+    fn compute_children(
+        parent: &StaticLayoutParent,
+        size: Size,
+        child_size: Size,
+        children: &[StaticLayoutElement],
+    ) -> Vec<(f32, f32, f32, f32)> {
+        let mut tree = LayoutTree::new();
+        let parent_id = tree
+            .new_leaf(LayoutTree::merge_layout_parent_with_element(
+                Some(parent),
+                None,
+                &size,
+                None,
+            ))
+            .unwrap();
+        let child_ids: Vec<NodeId> = children
+            .iter()
+            .map(|child| {
+                tree.new_leaf(LayoutTree::merge_layout_parent_with_element(
+                    None,
+                    Some(child),
+                    &child_size,
+                    None,
+                ))
+                .unwrap()
+            })
+            .collect();
+        tree.update_children(parent_id, &child_ids).unwrap();
+        tree.compute_layout(parent_id, taffy::Size::MAX_CONTENT, |_, _, _| {
+            taffy::Size::ZERO
+        })
+        .unwrap();
+
+        return child_ids
+            .iter()
+            .map(|child_id| {
+                let layout = tree.get_layout(*child_id).unwrap();
+                (
+                    layout.location.x,
+                    layout.location.y,
+                    layout.size.width,
+                    layout.size.height,
+                )
+            })
+            .collect();
+    }
+
+    /// Creates an element filling the grid area it's placed in.
+    fn grid_element(grid_column: GridPlacement, grid_row: GridPlacement) -> StaticLayoutElement {
+        let mut element = StaticLayoutElement::default();
+        element.horizontal_sizing_mode = LayoutElementSizingMode::Fill;
+        element.vertical_sizing_mode = LayoutElementSizingMode::Fill;
+        element.grid_column = grid_column;
+        element.grid_row = grid_row;
+        return element;
+    }
+
+    fn size(width: f32, height: f32) -> Size {
+        Size::new(Abs::pt(width), Abs::pt(height))
+    }
+
+    fn fraction(value: f32) -> GridTrack {
+        GridTrack::Fraction { value }
+    }
+
+    fn line(index: i16) -> GridLine {
+        GridLine::Line { index }
+    }
+
+    #[test]
+    fn test_fill_element_takes_parent_size() {
+        let mut element = StaticLayoutElement::default();
+        element.horizontal_sizing_mode = LayoutElementSizingMode::Fill;
+        element.vertical_sizing_mode = LayoutElementSizingMode::Fill;
+
+        let mut tree = LayoutTree::new();
+        let parent = tree
+            .new_leaf(LayoutTree::merge_layout_parent_with_element(
+                Some(&StaticLayoutParent::default()),
+                None,
+                &Size::new(Abs::pt(200.0), Abs::pt(100.0)),
+                None,
+            ))
+            .unwrap();
+        let child = tree
+            .new_leaf(LayoutTree::merge_layout_parent_with_element(
+                None,
+                Some(&element),
+                &Size::new(Abs::pt(10.0), Abs::pt(10.0)),
+                None,
+            ))
+            .unwrap();
+        tree.update_children(parent, &vec![child]).unwrap();
+        tree.compute_layout(parent, taffy::Size::MAX_CONTENT, |_, _, _| {
+            taffy::Size::ZERO
+        })
+        .unwrap();
+
+        let layout = tree.get_layout(child).unwrap();
+        assert_eq!(layout.size.width, 200.0);
+        assert_eq!(layout.size.height, 100.0);
+    }
+
+    #[test]
+    fn test_grid_template_tracks_and_gaps() {
+        let parent = StaticLayoutParent {
+            display: Display::Grid,
+            gap: Axes::new(Length::abs(Abs::pt(10.0)), Length::abs(Abs::pt(20.0))),
+            grid_template_columns: vec![
+                GridTrack::Abs {
+                    value: Abs::pt(50.0),
+                },
+                fraction(1.0),
+                fraction(3.0),
+            ],
+            grid_template_rows: vec![
+                GridTrack::Ratio {
+                    value: Ratio::new(0.25),
+                },
+                fraction(1.0),
+            ],
+            ..Default::default()
+        };
+        let children = vec![grid_element(GridPlacement::default(), GridPlacement::default()); 6];
+
+        // The fraction tracks share the space left by the fixed tracks and gaps
+        assert_eq!(
+            compute_children(&parent, size(290.0, 200.0), size(10.0, 10.0), &children),
+            vec![
+                (0.0, 0.0, 50.0, 50.0),
+                (60.0, 0.0, 55.0, 50.0),
+                (125.0, 0.0, 165.0, 50.0),
+                (0.0, 70.0, 50.0, 130.0),
+                (60.0, 70.0, 55.0, 130.0),
+                (125.0, 70.0, 165.0, 130.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grid_line_based_placement() {
+        let parent = StaticLayoutParent {
+            display: Display::Grid,
+            grid_template_columns: vec![fraction(1.0); 3],
+            grid_template_rows: vec![fraction(1.0); 3],
+            ..Default::default()
+        };
+        let children = vec![
+            // From the second to the last line
+            grid_element(
+                GridPlacement {
+                    start: line(2),
+                    end: line(4),
+                },
+                GridPlacement {
+                    start: line(1),
+                    end: GridLine::Auto,
+                },
+            ),
+            // Negative lines count backwards from the last line
+            grid_element(
+                GridPlacement {
+                    start: line(-2),
+                    end: line(-1),
+                },
+                GridPlacement {
+                    start: line(2),
+                    end: GridLine::Span { count: 2 },
+                },
+            ),
+            grid_element(
+                GridPlacement {
+                    start: line(1),
+                    end: GridLine::Span { count: 2 },
+                },
+                GridPlacement {
+                    start: line(3),
+                    end: GridLine::Auto,
+                },
+            ),
+            // Auto-placed into the first free cell
+            grid_element(GridPlacement::default(), GridPlacement::default()),
+        ];
+
+        assert_eq!(
+            compute_children(&parent, size(300.0, 300.0), size(10.0, 10.0), &children),
+            vec![
+                (100.0, 0.0, 200.0, 100.0),
+                (200.0, 100.0, 100.0, 200.0),
+                (0.0, 200.0, 200.0, 100.0),
+                (0.0, 0.0, 100.0, 100.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_max_size() {
+        let parent = StaticLayoutParent::default();
+
+        let mut max_element = StaticLayoutElement::default();
+        max_element.horizontal_sizing_mode = LayoutElementSizingMode::Fill;
+        max_element.vertical_sizing_mode = LayoutElementSizingMode::Fill;
+        max_element.max_size = Axes::new(Some(Length::abs(Abs::pt(100.0))), None);
+
+        let mut min_element = StaticLayoutElement::default();
+        min_element.min_size = Axes::new(
+            Some(Length::ratio(Ratio::new(0.5))),
+            Some(Length::abs(Abs::pt(40.0))),
+        );
+
+        // Filling elements are clamped to their maximum size
+        // and fixed elements are extended to their minimum size (relative to the parent)
+        assert_eq!(
+            compute_children(
+                &parent,
+                size(300.0, 100.0),
+                size(10.0, 10.0),
+                &[max_element, min_element]
+            ),
+            vec![(0.0, 0.0, 100.0, 100.0), (100.0, 0.0, 150.0, 40.0)]
+        );
+    }
+
+    #[test]
+    fn test_flex_wrap() {
+        let mut parent = StaticLayoutParent {
+            flex_wrap: FlexWrap::Wrap,
+            vertical_sizing_mode: LayoutParentSizingMode::Hug,
+            ..Default::default()
+        };
+        let children = vec![StaticLayoutElement::default(); 3];
+
+        // Elements overflowing the row wrap into the next one
+        assert_eq!(
+            compute_children(&parent, size(100.0, 0.0), size(40.0, 40.0), &children),
+            vec![
+                (0.0, 0.0, 40.0, 40.0),
+                (40.0, 0.0, 40.0, 40.0),
+                (0.0, 40.0, 40.0, 40.0),
+            ]
+        );
+
+        // Without wrapping the elements shrink to fit into the row
+        parent.flex_wrap = FlexWrap::NoWrap;
+        let layouts = compute_children(&parent, size(100.0, 0.0), size(40.0, 40.0), &children);
+        assert!(layouts
+            .iter()
+            .all(|(_, y, _, height)| *y == 0.0 && *height == 40.0));
+        assert_eq!(
+            layouts.iter().map(|(_, _, width, _)| width).sum::<f32>(),
+            100.0
+        );
+    }
+
+    #[test]
+    fn test_aspect_ratio() {
+        let parent = StaticLayoutParent::default();
+
+        // The fixed axis is derived from the filling axis
+        let mut fill_width_element = StaticLayoutElement::default();
+        fill_width_element.horizontal_sizing_mode = LayoutElementSizingMode::Fill;
+        fill_width_element.aspect_ratio = Some(2.0);
+        assert_eq!(
+            compute_children(
+                &parent,
+                size(120.0, 100.0),
+                size(10.0, 10.0),
+                &[fill_width_element]
+            ),
+            vec![(0.0, 0.0, 120.0, 60.0)]
+        );
+
+        let mut fill_height_element = StaticLayoutElement::default();
+        fill_height_element.vertical_sizing_mode = LayoutElementSizingMode::Fill;
+        fill_height_element.aspect_ratio = Some(0.5);
+        assert_eq!(
+            compute_children(
+                &parent,
+                size(120.0, 100.0),
+                size(10.0, 10.0),
+                &[fill_height_element]
+            ),
+            vec![(0.0, 0.0, 50.0, 100.0)]
+        );
+    }
+}
@@ -22,6 +22,10 @@ impl LuaRes {
         self.scripts.insert(id, script);
     }
 
+    pub fn get_scripts(&self) -> &HashMap<ReferenceId, LuaScript> {
+        &self.scripts
+    }
+
     fn load_code(lua: &mut Lua, executor: &StashedExecutor, code: &str) -> Result<(), StaticError> {
         lua.try_enter(|ctx| {
             let closure = Closure::load(ctx, None, code.as_bytes())?;
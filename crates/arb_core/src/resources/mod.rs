@@ -4,3 +4,4 @@ pub mod layout;
 pub mod lua;
 pub mod referencer;
 pub mod tick;
+pub mod variables;
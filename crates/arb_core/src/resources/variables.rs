@@ -0,0 +1,49 @@
+use bevy_ecs::system::Resource;
+use dyn_arb_bundles::{properties::VariableValue, reference_id::ReferenceId, Variable};
+use std::collections::HashMap;
+
+#[derive(Resource, Default, Debug)]
+pub struct VariablesRes {
+    variables: HashMap<ReferenceId, Variable>,
+    mode: Option<String>,
+}
+
+impl VariablesRes {
+    pub fn new() -> Self {
+        Self {
+            variables: HashMap::new(),
+            mode: None,
+        }
+    }
+
+    pub fn insert_variable(&mut self, variable: Variable) {
+        self.variables.insert(variable.id.clone(), variable);
+    }
+
+    pub fn get_variable(&self, id: &ReferenceId) -> Option<&Variable> {
+        self.variables.get(id)
+    }
+
+    pub fn get_variable_mut(&mut self, id: &ReferenceId) -> Option<&mut Variable> {
+        self.variables.get_mut(id)
+    }
+
+    pub fn iter_variables(&self) -> impl Iterator<Item = &Variable> {
+        self.variables.values()
+    }
+
+    pub fn get_mode(&self) -> Option<&str> {
+        self.mode.as_deref()
+    }
+
+    pub fn set_mode(&mut self, mode: Option<String>) {
+        self.mode = mode;
+    }
+
+    /// Resolves the value of the variable in the active mode.
+    pub fn resolve(&self, id: &ReferenceId) -> Option<&VariableValue> {
+        self.variables
+            .get(id)
+            .map(|variable| variable.resolve(self.mode.as_deref()))
+    }
+}
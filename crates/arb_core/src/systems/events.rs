@@ -1,4 +1,4 @@
-use crate::resources::{artboard::ArtboardRes, referencer::ReferencerRes};
+use crate::resources::{artboard::ArtboardRes, referencer::ReferencerRes, variables::VariablesRes};
 use bevy_ecs::{
     entity::Entity,
     event::{EventReader, EventWriter},
@@ -13,24 +13,36 @@ use dyn_arb_bundles::{
     components::{
         marker::{Removed, Root},
         mixins::{
-            BlendModeMixin, CornerRadiiMixin, ImageAssetMixin, OpacityMixin, PaintChildMixin,
-            PaintParentMixin, SizeMixin, StyleChildrenMixin, StyleParentMixin, VisibilityMixin,
+            BlendModeMixin, CornerRadiiMixin, ImageAssetMixin, InstanceMixin, OpacityMixin,
+            PaintChildMixin, PaintParentMixin, SizeMixin, StyleChildrenMixin, StyleParentMixin,
+            VariableBindingsMixin, VisibilityMixin,
+        },
+        nodes::{
+            ArbNode, BooleanOperationArbNode, EllipseArbNode, FrameArbNode, PolygonArbNode,
+            StarArbNode, TextArbNode,
         },
-        nodes::{ArbNode, EllipseArbNode, FrameArbNode, PolygonArbNode, StarArbNode, TextArbNode},
         paints::{ArbPaint, GradientArbPaint, ImageArbPaint, SolidArbPaint},
-        styles::{DropShadowArbStyle, FillArbStyle, StrokeArbStyle},
+        styles::{
+            ArbStyle, BackgroundBlurArbStyle, DropShadowArbStyle, FillArbStyle,
+            InnerShadowArbStyle, LayerBlurArbStyle, StrokeArbStyle,
+        },
     },
     events::{
-        CreateAssetInputEvent, CreateNodeInputEvent, CreatePaintInputEvent, DeleteEntityInputEvent,
-        FocusRootNodesInputEvent, MoveEntityInputEvent, UpdateArtboardSizeInputEvent,
-        UpdateArtboardViewportInputEvent, UpdateDropShadowStyleInputEvent,
-        UpdateEllipseNodeInputEvent, UpdateEntityBlendModeInputEvent,
-        UpdateEntityChildrenInputEvent, UpdateEntityCornerRadiiInputEvent,
-        UpdateEntityOpacityInputEvent, UpdateEntityRotationInputEvent, UpdateEntitySizeInputEvent,
+        BindVariableInputEvent, CreateAssetInputEvent, CreateNodeInputEvent, CreatePaintInputEvent,
+        CreateStyleInputEvent, CreateVariableInputEvent, DeleteEntityInputEvent,
+        DeleteStyleInputEvent, FocusRootNodesInputEvent, MoveEntityInputEvent,
+        ReorderStylesInputEvent, UpdateArtboardSizeInputEvent, UpdateArtboardViewportInputEvent,
+        UpdateBackgroundBlurStyleInputEvent, UpdateBooleanOperationNodeInputEvent,
+        UpdateDropShadowStyleInputEvent, UpdateEllipseNodeInputEvent,
+        UpdateEntityBlendModeInputEvent, UpdateEntityChildrenInputEvent,
+        UpdateEntityCornerRadiiInputEvent, UpdateEntityOpacityInputEvent,
+        UpdateEntityRotationInputEvent, UpdateEntitySizeInputEvent,
         UpdateEntityTransformInputEvent, UpdateEntityVisibilityInputEvent,
         UpdateFillStyleInputEvent, UpdateFrameNodeInputEvent, UpdateGradientPaintInputEvent,
-        UpdateImagePaintInputEvent, UpdatePolygonNodeInputEvent, UpdateSolidPaintInputEvent,
+        UpdateImagePaintInputEvent, UpdateInnerShadowStyleInputEvent, UpdateInstanceNodeInputEvent,
+        UpdateLayerBlurStyleInputEvent, UpdatePolygonNodeInputEvent, UpdateSolidPaintInputEvent,
         UpdateStarNodeInputEvent, UpdateStorkeStyleInputEvent, UpdateTextNodeInputEvent,
+        UpdateVariableInputEvent, UpdateVariableModeInputEvent,
     },
     properties::Viewport,
     reference_id::ReferenceIdOrEntity,
@@ -40,6 +52,7 @@ use dyn_arb_bundles::{
 use dyn_utils::{math::matrix::rotate_around_point, properties::size::Size, units::abs::Abs};
 use glam::{Vec2, Vec3};
 use smallvec::SmallVec;
+use std::collections::HashMap;
 
 // =============================================================================
 // Artboard
@@ -151,11 +164,16 @@ pub fn create_node_input_system(
             Node::Polygon(n) => n.spawn(&mut commands),
             Node::Text(n) => n.spawn(&mut commands),
             Node::Vector(n) => n.spawn(&mut commands),
+            Node::BooleanOperation(n) => n.spawn(&mut commands),
+            Node::Component(n) => n.spawn(&mut commands),
+            Node::Instance(n) => n.spawn(&mut commands),
         };
         let node_entity = node_entity_commands.id();
 
         let maybe_children = match node {
             Node::Frame(node) => Some(&node.children),
+            Node::BooleanOperation(node) => Some(&node.children),
+            Node::Component(node) => Some(&node.children),
             _ => None,
         };
 
@@ -177,6 +195,8 @@ pub fn create_node_input_system(
             Node::Polygon(n) => Some(&n.styles),
             Node::Text(n) => Some(&n.styles),
             Node::Vector(n) => Some(&n.styles),
+            Node::BooleanOperation(n) => Some(&n.styles),
+            Node::Component(n) => Some(&n.styles),
             _ => None,
         };
 
@@ -184,45 +204,13 @@ pub fn create_node_input_system(
         let mut style_children_mixin = StyleChildrenMixin(SmallVec::new());
         if let Some(styles) = maybe_styles {
             for style in styles {
-                let maybe_paint_entity = match style {
-                    Style::Fill(s) => s
-                        .paint_id
-                        .get_entity(referencer_res.get_reference_id_to_entity_map()),
-                    Style::Stroke(s) => s
-                        .paint_id
-                        .get_entity(referencer_res.get_reference_id_to_entity_map()),
-                    _ => None,
-                };
-
-                // Spawn style
-                let mut style_entity_commands = match style {
-                    Style::Fill(s) => s.spawn(&mut commands),
-                    Style::Stroke(s) => s.spawn(&mut commands),
-                    Style::DropShadow(s) => s.spawn(&mut commands),
-                };
-                let style_entity = style_entity_commands.id();
-
-                // Establish parent child relation between node and style (1)
-                style_entity_commands.insert(StyleParentMixin(node_entity));
-                style_children_mixin.0.push(style_entity);
-
-                // Establish parent child relation between style and paint
-                if let Some(paint_entity) = maybe_paint_entity {
-                    if let Ok(mut paint_parent_mixin) = paint_parent_query.get_mut(paint_entity) {
-                        paint_parent_mixin.0.push(style_entity);
-                        style_entity_commands.insert(PaintChildMixin(paint_entity));
-                    }
-                }
-
-                // Reference style entity
-                let maybe_style_id = match style {
-                    Style::DropShadow(s) => s.id.clone(),
-                    Style::Fill(s) => s.id.clone(),
-                    Style::Stroke(s) => s.id.clone(),
-                };
-                if let Some(style_id) = maybe_style_id {
-                    referencer_res.reference_entity(style_id, style_entity);
-                }
+                style_children_mixin.0.push(spawn_style(
+                    &mut commands,
+                    &mut referencer_res,
+                    &mut paint_parent_query,
+                    style,
+                    node_entity,
+                ));
             }
         }
 
@@ -238,6 +226,9 @@ pub fn create_node_input_system(
             Node::Polygon(n) => n.id.clone(),
             Node::Text(n) => n.id.clone(),
             Node::Vector(n) => n.id.clone(),
+            Node::BooleanOperation(n) => n.id.clone(),
+            Node::Component(n) => n.id.clone(),
+            Node::Instance(n) => n.id.clone(),
         };
         if let Some(node_id) = maybe_node_id {
             referencer_res.reference_entity(node_id, node_entity);
@@ -377,10 +368,208 @@ pub fn update_text_node_input_system(
     }
 }
 
+pub fn update_instance_node_input_system(
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<UpdateInstanceNodeInputEvent>,
+    mut query: Query<&mut InstanceMixin>,
+) {
+    for UpdateInstanceNodeInputEvent {
+        id,
+        component_id: maybe_component_id,
+        overrides: maybe_overrides,
+    } in event_reader.read()
+    {
+        if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok(mut instance_mixin) = query.get_mut(entity) {
+                if let Some(component_id) = maybe_component_id {
+                    instance_mixin.component_id = component_id.clone();
+                }
+                if let Some(overrides) = maybe_overrides {
+                    instance_mixin.overrides = SmallVec::from_vec(overrides.clone());
+                }
+            }
+        }
+    }
+}
+
+pub fn update_boolean_operation_node_input_system(
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<UpdateBooleanOperationNodeInputEvent>,
+    mut query: Query<&mut BooleanOperationArbNode>,
+) {
+    for UpdateBooleanOperationNodeInputEvent {
+        id,
+        operation: maybe_operation,
+    } in event_reader.read()
+    {
+        if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok(mut boolean_operation_arb_node) = query.get_mut(entity) {
+                if let Some(operation) = maybe_operation {
+                    boolean_operation_arb_node.operation = *operation;
+                }
+            }
+        }
+    }
+}
+
 // =============================================================================
 // Style
 // =============================================================================
 
+/// Spawns the style and links it to its node and paint.
+/// Note that the style isn't added to the `StyleChildrenMixin` of the node.
+fn spawn_style(
+    commands: &mut Commands,
+    referencer_res: &mut ReferencerRes,
+    paint_parent_query: &mut Query<&mut PaintParentMixin>,
+    style: &Style,
+    node_entity: Entity,
+) -> Entity {
+    let maybe_paint_entity = match style {
+        Style::Fill(s) => s
+            .paint_id
+            .get_entity(referencer_res.get_reference_id_to_entity_map()),
+        Style::Stroke(s) => s
+            .paint_id
+            .get_entity(referencer_res.get_reference_id_to_entity_map()),
+        _ => None,
+    };
+
+    // Spawn style
+    let mut style_entity_commands = match style {
+        Style::Fill(s) => s.spawn(commands),
+        Style::Stroke(s) => s.spawn(commands),
+        Style::DropShadow(s) => s.spawn(commands),
+        Style::InnerShadow(s) => s.spawn(commands),
+        Style::LayerBlur(s) => s.spawn(commands),
+        Style::BackgroundBlur(s) => s.spawn(commands),
+    };
+    let style_entity = style_entity_commands.id();
+
+    // Establish parent child relation between node and style
+    style_entity_commands.insert(StyleParentMixin(node_entity));
+
+    // Establish parent child relation between style and paint
+    if let Some(paint_entity) = maybe_paint_entity {
+        if let Ok(mut paint_parent_mixin) = paint_parent_query.get_mut(paint_entity) {
+            paint_parent_mixin.0.push(style_entity);
+            style_entity_commands.insert(PaintChildMixin(paint_entity));
+        }
+    }
+
+    // Reference style entity
+    let maybe_style_id = match style {
+        Style::DropShadow(s) => s.id.clone(),
+        Style::Fill(s) => s.id.clone(),
+        Style::Stroke(s) => s.id.clone(),
+        Style::InnerShadow(s) => s.id.clone(),
+        Style::LayerBlur(s) => s.id.clone(),
+        Style::BackgroundBlur(s) => s.id.clone(),
+    };
+    if let Some(style_id) = maybe_style_id {
+        referencer_res.reference_entity(style_id, style_entity);
+    }
+
+    return style_entity;
+}
+
+pub fn create_style_input_system(
+    mut commands: Commands,
+    mut referencer_res: ResMut<ReferencerRes>,
+    mut event_reader: EventReader<CreateStyleInputEvent>,
+    mut node_query: Query<&mut StyleChildrenMixin, With<ArbNode>>,
+    mut paint_parent_query: Query<&mut PaintParentMixin>,
+) {
+    for CreateStyleInputEvent {
+        node_id,
+        style,
+        index: maybe_index,
+    } in event_reader.read()
+    {
+        if let Some(node_entity) =
+            node_id.get_entity(referencer_res.get_reference_id_to_entity_map())
+        {
+            if let Ok(mut style_children_mixin) = node_query.get_mut(node_entity) {
+                let style_entity = spawn_style(
+                    &mut commands,
+                    &mut referencer_res,
+                    &mut paint_parent_query,
+                    style,
+                    node_entity,
+                );
+                let index = maybe_index.unwrap_or(0).min(style_children_mixin.0.len());
+                style_children_mixin.0.insert(index, style_entity);
+            }
+        }
+    }
+}
+
+pub fn delete_style_input_system(
+    mut commands: Commands,
+    mut referencer_res: ResMut<ReferencerRes>,
+    mut event_reader: EventReader<DeleteStyleInputEvent>,
+    style_query: Query<(&StyleParentMixin, Option<&PaintChildMixin>), With<ArbStyle>>,
+    mut node_query: Query<&mut StyleChildrenMixin, With<ArbNode>>,
+    mut paint_parent_query: Query<&mut PaintParentMixin>,
+) {
+    for DeleteStyleInputEvent { id } in event_reader.read() {
+        if let Some(style_entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok((StyleParentMixin(node_entity), maybe_paint_child_mixin)) =
+                style_query.get(style_entity)
+            {
+                // Dissolve node-to-style relationship
+                if let Ok(mut style_children_mixin) = node_query.get_mut(*node_entity) {
+                    style_children_mixin.0.retain(|e| *e != style_entity);
+                }
+
+                // Dissolve style-to-paint relationship
+                if let Some(PaintChildMixin(paint_entity)) = maybe_paint_child_mixin {
+                    if let Ok(mut paint_parent_mixin) = paint_parent_query.get_mut(*paint_entity) {
+                        paint_parent_mixin.0.retain(|e| *e != style_entity);
+                    }
+                }
+
+                // Dereference style entity
+                referencer_res.remove_by_entity(&style_entity);
+
+                commands.entity(style_entity).insert(Removed);
+            }
+        }
+    }
+}
+
+pub fn reorder_styles_input_system(
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<ReorderStylesInputEvent>,
+    mut node_query: Query<&mut StyleChildrenMixin, With<ArbNode>>,
+) {
+    for ReorderStylesInputEvent { id, styles } in event_reader.read() {
+        if let Some(node_entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok(mut style_children_mixin) = node_query.get_mut(node_entity) {
+                let mut new_style_entities: SmallVec<[Entity; 2]> = SmallVec::new();
+                for style_entity in styles.iter().filter_map(|style_id| {
+                    style_id.get_entity(referencer_res.get_reference_id_to_entity_map())
+                }) {
+                    if style_children_mixin.0.contains(&style_entity)
+                        && !new_style_entities.contains(&style_entity)
+                    {
+                        new_style_entities.push(style_entity);
+                    }
+                }
+                for style_entity in style_children_mixin.0.iter() {
+                    if !new_style_entities.contains(style_entity) {
+                        new_style_entities.push(*style_entity);
+                    }
+                }
+
+                if new_style_entities != style_children_mixin.0 {
+                    style_children_mixin.0 = new_style_entities;
+                }
+            }
+        }
+    }
+}
+
 pub fn update_fill_style_input_system(
     referencer_res: Res<ReferencerRes>,
     mut event_reader: EventReader<UpdateFillStyleInputEvent>,
@@ -424,6 +613,12 @@ pub fn update_storke_style_input_system(
         id,
         paint_id: maybe_paint_id,
         width: maybe_width,
+        alignment: maybe_alignment,
+        cap: maybe_cap,
+        join: maybe_join,
+        miter_limit: maybe_miter_limit,
+        dash_array: maybe_dash_array,
+        dash_offset: maybe_dash_offset,
     } in event_reader.read()
     {
         if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
@@ -446,6 +641,25 @@ pub fn update_storke_style_input_system(
                 if let Some(width) = maybe_width {
                     stroke_arb_style.stroke.width = width.to_pt();
                 }
+                if let Some(alignment) = maybe_alignment {
+                    stroke_arb_style.alignment = *alignment;
+                }
+                if let Some(cap) = maybe_cap {
+                    stroke_arb_style.stroke.line_cap = (*cap).into();
+                }
+                if let Some(join) = maybe_join {
+                    stroke_arb_style.stroke.line_join = (*join).into();
+                }
+                if let Some(miter_limit) = maybe_miter_limit {
+                    stroke_arb_style.stroke.miter_limit = *miter_limit;
+                }
+                if let Some(dash_array) = maybe_dash_array {
+                    stroke_arb_style.dash_array =
+                        dash_array.iter().map(|length| length.to_pt()).collect();
+                }
+                if let Some(dash_offset) = maybe_dash_offset {
+                    stroke_arb_style.dash_offset = dash_offset.to_pt();
+                }
             }
         }
     }
@@ -454,7 +668,7 @@ pub fn update_storke_style_input_system(
 pub fn update_drop_shadow_style_input_system(
     referencer_res: Res<ReferencerRes>,
     mut event_reader: EventReader<UpdateDropShadowStyleInputEvent>,
-    mut query: Query<&mut DropShadowArbStyle, With<StrokeArbStyle>>,
+    mut query: Query<&mut DropShadowArbStyle>,
 ) {
     for UpdateDropShadowStyleInputEvent {
         id,
@@ -483,6 +697,78 @@ pub fn update_drop_shadow_style_input_system(
     }
 }
 
+pub fn update_inner_shadow_style_input_system(
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<UpdateInnerShadowStyleInputEvent>,
+    mut query: Query<&mut InnerShadowArbStyle>,
+) {
+    for UpdateInnerShadowStyleInputEvent {
+        id,
+        color: maybe_color,
+        position: maybe_position,
+        spread: maybe_spread,
+        blur: maybe_blur,
+    } in event_reader.read()
+    {
+        if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok(mut inner_shadow_arb_style) = query.get_mut(entity) {
+                if let Some(color) = maybe_color {
+                    inner_shadow_arb_style.color = *color;
+                }
+                if let Some(position) = maybe_position {
+                    inner_shadow_arb_style.position = *position;
+                }
+                if let Some(spread) = maybe_spread {
+                    inner_shadow_arb_style.spread = *spread;
+                }
+                if let Some(blur) = maybe_blur {
+                    inner_shadow_arb_style.blur = *blur;
+                }
+            }
+        }
+    }
+}
+
+pub fn update_layer_blur_style_input_system(
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<UpdateLayerBlurStyleInputEvent>,
+    mut query: Query<&mut LayerBlurArbStyle>,
+) {
+    for UpdateLayerBlurStyleInputEvent {
+        id,
+        blur: maybe_blur,
+    } in event_reader.read()
+    {
+        if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok(mut layer_blur_arb_style) = query.get_mut(entity) {
+                if let Some(blur) = maybe_blur {
+                    layer_blur_arb_style.blur = *blur;
+                }
+            }
+        }
+    }
+}
+
+pub fn update_background_blur_style_input_system(
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<UpdateBackgroundBlurStyleInputEvent>,
+    mut query: Query<&mut BackgroundBlurArbStyle>,
+) {
+    for UpdateBackgroundBlurStyleInputEvent {
+        id,
+        blur: maybe_blur,
+    } in event_reader.read()
+    {
+        if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            if let Ok(mut background_blur_arb_style) = query.get_mut(entity) {
+                if let Some(blur) = maybe_blur {
+                    background_blur_arb_style.blur = *blur;
+                }
+            }
+        }
+    }
+}
+
 // =============================================================================
 // Paint
 // =============================================================================
@@ -556,6 +842,7 @@ pub fn update_image_paint_input_system(
         id,
         scale_mode: maybe_scale_mode,
         image_id: maybe_image_id,
+        filters: maybe_filters,
     } in event_reader.read()
     {
         if let Some(entity) = id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
@@ -566,6 +853,9 @@ pub fn update_image_paint_input_system(
                 if let Some(image_id) = maybe_image_id {
                     image_asset_mixin.0 = Some(*image_id);
                 }
+                if let Some(filters) = maybe_filters {
+                    image_arb_paint.filters = *filters;
+                }
             }
         }
     }
@@ -615,6 +905,80 @@ pub fn create_asset_input_system(
     }
 }
 
+// =============================================================================
+// Variable
+// =============================================================================
+
+pub fn create_variable_input_system(
+    mut variables_res: ResMut<VariablesRes>,
+    mut event_reader: EventReader<CreateVariableInputEvent>,
+) {
+    for CreateVariableInputEvent { variable } in event_reader.read() {
+        variables_res.insert_variable(variable.clone());
+    }
+}
+
+pub fn update_variable_input_system(
+    mut variables_res: ResMut<VariablesRes>,
+    mut event_reader: EventReader<UpdateVariableInputEvent>,
+) {
+    for UpdateVariableInputEvent { id, value, modes } in event_reader.read() {
+        if let Some(variable) = variables_res.get_variable_mut(id) {
+            if let Some(value) = value {
+                variable.value = value.clone();
+            }
+            if let Some(modes) = modes {
+                variable.modes = modes.clone();
+            }
+        }
+    }
+}
+
+pub fn update_variable_mode_input_system(
+    mut variables_res: ResMut<VariablesRes>,
+    mut event_reader: EventReader<UpdateVariableModeInputEvent>,
+) {
+    if let Some(UpdateVariableModeInputEvent { mode }) = event_reader.read().last() {
+        if variables_res.get_mode() != mode.as_deref() {
+            variables_res.set_mode(mode.clone());
+        }
+    }
+}
+
+pub fn bind_variable_input_system(
+    mut commands: Commands,
+    referencer_res: Res<ReferencerRes>,
+    mut event_reader: EventReader<BindVariableInputEvent>,
+    mut query: Query<&mut VariableBindingsMixin>,
+) {
+    let mut to_insert_bindings: HashMap<Entity, VariableBindingsMixin> = HashMap::new();
+
+    for BindVariableInputEvent {
+        target_id,
+        property,
+        variable_id,
+    } in event_reader.read()
+    {
+        let entity = match target_id.get_entity(referencer_res.get_reference_id_to_entity_map()) {
+            Some(entity) => entity,
+            None => continue,
+        };
+
+        let bindings = match query.get_mut(entity) {
+            Ok(bindings_mixin) => &mut bindings_mixin.into_inner().0,
+            Err(_) => &mut to_insert_bindings.entry(entity).or_default().0,
+        };
+        bindings.retain(|(bound_property, _)| bound_property != property);
+        if let Some(variable_id) = variable_id {
+            bindings.push((*property, variable_id.clone()));
+        }
+    }
+
+    for (entity, bindings_mixin) in to_insert_bindings {
+        commands.entity(entity).insert(bindings_mixin);
+    }
+}
+
 // =============================================================================
 // Entity
 // =============================================================================
@@ -871,3 +1235,102 @@ pub fn execute_lua_script_input_system(
         }
     });
 }
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_app, find_entity, reference_id_or_entity};
+    use bevy_app::App;
+
+    fn get_styles(app: &App, node_entity: Entity) -> Vec<Entity> {
+        app.world
+            .get::<StyleChildrenMixin>(node_entity)
+            .unwrap()
+            .0
+            .to_vec()
+    }
+
+    fn get_paint_styles(app: &App, paint_entity: Entity) -> Vec<Entity> {
+        app.world
+            .get::<PaintParentMixin>(paint_entity)
+            .unwrap()
+            .0
+            .to_vec()
+    }
+
+    #[test]
+    fn test_create_delete_and_reorder_styles() {
+        let mut app = create_app(100.0, 100.0, false);
+
+        app.world.send_event(CreatePaintInputEvent {
+            paint: serde_json::from_value(serde_json::json!({
+                "type": "Solid", "id": "paint", "color": [0, 0, 0]
+            }))
+            .unwrap(),
+        });
+        app.update();
+        app.world.send_event(CreateNodeInputEvent {
+            node: serde_json::from_value(serde_json::json!({
+                "type": "Rectangle", "id": "rect", "size": [10, 10],
+                "styles": [{ "type": "Fill", "id": "fill", "paintId": reference_id_or_entity("paint") }]
+            }))
+            .unwrap(),
+        });
+        app.update();
+
+        let rect = find_entity(&app, "rect").unwrap();
+        let paint = find_entity(&app, "paint").unwrap();
+        let fill = find_entity(&app, "fill").unwrap();
+
+        // Create styles below and above the fill
+        app.world.send_event(CreateStyleInputEvent {
+            node_id: reference_id_or_entity("rect"),
+            style: serde_json::from_value(serde_json::json!({
+                "type": "Stroke", "id": "stroke", "width": 1, "paintId": reference_id_or_entity("paint")
+            }))
+            .unwrap(),
+            index: Some(1),
+        });
+        app.world.send_event(CreateStyleInputEvent {
+            node_id: reference_id_or_entity("rect"),
+            style: serde_json::from_value(serde_json::json!({
+                "type": "LayerBlur", "id": "blur", "blur": 2
+            }))
+            .unwrap(),
+            index: None,
+        });
+        app.update();
+
+        let stroke = find_entity(&app, "stroke").unwrap();
+        let blur = find_entity(&app, "blur").unwrap();
+        assert_eq!(get_styles(&app, rect), vec![blur, fill, stroke]);
+        for style in [blur, fill, stroke] {
+            assert_eq!(app.world.get::<StyleParentMixin>(style).unwrap().0, rect);
+        }
+        assert_eq!(get_paint_styles(&app, paint), vec![fill, stroke]);
+        assert_eq!(app.world.get::<PaintChildMixin>(stroke).unwrap().0, paint);
+        assert!(app.world.get::<PaintChildMixin>(blur).is_none());
+
+        // Reorder the styles, unlisted styles keep their relative order below
+        app.world.send_event(ReorderStylesInputEvent {
+            id: reference_id_or_entity("rect"),
+            styles: vec![
+                reference_id_or_entity("stroke"),
+                reference_id_or_entity("unknown"),
+            ],
+        });
+        app.update();
+        assert_eq!(get_styles(&app, rect), vec![stroke, blur, fill]);
+
+        // Delete the fill
+        app.world.send_event(DeleteStyleInputEvent {
+            id: reference_id_or_entity("fill"),
+        });
+        app.update();
+
+        assert_eq!(get_styles(&app, rect), vec![stroke, blur]);
+        assert_eq!(get_paint_styles(&app, paint), vec![stroke]);
+        assert_eq!(find_entity(&app, "fill"), None);
+        assert!(app.world.get_entity(fill).is_none());
+    }
+}
@@ -0,0 +1,632 @@
+use crate::resources::{referencer::ReferencerRes, tick::TickRes};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    query::{Changed, Or, With, Without},
+    system::{Query, Res, SystemState},
+    world::{Ref, World},
+};
+use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+use bevy_transform::components::{GlobalTransform, Transform};
+use dyn_arb_bundles::{
+    components::{
+        marker::{InstanceContent, Removed},
+        mixins::{
+            AbsoluteLayoutElementMixin, BlendModeMixin, ComponentMixin, CornerRadiiMixin,
+            InstanceMixin, OpacityMixin, PaintChildMixin, PaintParentMixin, PathMixin, SizeMixin,
+            StaticLayoutElementMixin, StaticLayoutParentMixin, StyleChildrenMixin,
+            StyleParentMixin, VisibilityMixin,
+        },
+        nodes::{
+            ArbNode, BooleanOperationArbNode, EllipseArbNode, FrameArbNode, PolygonArbNode,
+            RectangleArbNode, StarArbNode, TextArbNode, VectorArbNode,
+        },
+        styles::{
+            ArbStyle, BackgroundBlurArbStyle, DropShadowArbStyle, FillArbStyle,
+            InnerShadowArbStyle, LayerBlurArbStyle, StrokeArbStyle,
+        },
+    },
+    properties::InstanceOverride,
+};
+use smallvec::SmallVec;
+use std::collections::{HashMap, HashSet};
+
+// Paints aren't part of the content as the styles of an instance
+// share the paints of the component, so paint changes apply to both without a rebuild
+type ChangedContentFilter = (
+    Without<InstanceContent>,
+    Or<(
+        // Node variants (nested as 'Or' supports at most 15 filters)
+        Or<(
+            Changed<FrameArbNode>,
+            Changed<RectangleArbNode>,
+            Changed<EllipseArbNode>,
+            Changed<StarArbNode>,
+            Changed<PolygonArbNode>,
+            Changed<TextArbNode>,
+            Changed<VectorArbNode>,
+            Changed<BooleanOperationArbNode>,
+        )>,
+        // Style variants
+        Or<(
+            Changed<FillArbStyle>,
+            Changed<StrokeArbStyle>,
+            Changed<DropShadowArbStyle>,
+            Changed<InnerShadowArbStyle>,
+            Changed<LayerBlurArbStyle>,
+            Changed<BackgroundBlurArbStyle>,
+        )>,
+        Changed<CornerRadiiMixin>,
+        Changed<VisibilityMixin>,
+        Changed<BlendModeMixin>,
+        Changed<OpacityMixin>,
+        Changed<StaticLayoutParentMixin>,
+        Changed<StaticLayoutElementMixin>,
+        Changed<AbsoluteLayoutElementMixin>,
+        Changed<PaintChildMixin>,
+    )>,
+);
+
+type ChangedHierarchyFilter = (
+    Without<InstanceContent>,
+    Or<(
+        Changed<Children>,
+        Changed<StyleChildrenMixin>,
+        Changed<InstanceMixin>,
+    )>,
+);
+
+type ChangedLayoutFilter = (
+    Without<InstanceContent>,
+    Or<(Changed<Transform>, Changed<SizeMixin>)>,
+);
+
+type ChangedPathFilter = (Without<InstanceContent>, Changed<PathMixin>);
+
+/// Rebuilds the content of instances whose component (or the instance itself)
+/// has been changed during the current update cycle.
+///
+/// The content of an instance is managed by the instance,
+/// thus changes made directly to it are discarded on the next rebuild.
+pub fn sync_instances_system(
+    world: &mut World,
+    system_state: &mut SystemState<(
+        Res<ReferencerRes>,
+        Res<TickRes>,
+        Query<(Entity, &InstanceMixin)>,
+        Query<
+            Entity,
+            (
+                With<InstanceMixin>,
+                Without<InstanceContent>,
+                Changed<InstanceMixin>,
+            ),
+        >,
+        Query<Entity, ChangedContentFilter>,
+        Query<Entity, ChangedHierarchyFilter>,
+        Query<(Entity, Ref<Transform>, Ref<SizeMixin>), ChangedLayoutFilter>,
+        Query<(Entity, Ref<PathMixin>), ChangedPathFilter>,
+        Query<&Parent>,
+        Query<&StyleParentMixin>,
+        Query<(), With<ComponentMixin>>,
+    )>,
+) {
+    let mut to_sync_instances: Vec<Entity> = Vec::new();
+
+    {
+        let (
+            referencer_res,
+            tick_res,
+            instance_query,
+            changed_instance_query,
+            changed_content_query,
+            changed_hierarchy_query,
+            changed_layout_query,
+            changed_path_query,
+            parent_query,
+            style_parent_query,
+            component_query,
+        ) = system_state.get(world);
+
+        // Changes to Transform or Size from the previous update cycle are residual updates
+        // of the layout systems and don't need to be propagated,
+        // as the instance content is laid out on its own
+        let changed_layout_entities =
+            changed_layout_query
+                .iter()
+                .filter_map(|(entity, transform, size_mixin)| {
+                    if transform.last_changed().get() > tick_res.first_in_cycle.get()
+                        || size_mixin.last_changed().get() > tick_res.first_in_cycle.get()
+                    {
+                        Some(entity)
+                    } else {
+                        None
+                    }
+                });
+
+        // Same applies to paths, which are residual updates of the outline systems,
+        // unless the path is the content itself (e.g. of a vector node)
+        let changed_path_entities = changed_path_query
+            .iter()
+            .filter_map(|(entity, path_mixin)| {
+                if path_mixin.last_changed().get() > tick_res.first_in_cycle.get() {
+                    Some(entity)
+                } else {
+                    None
+                }
+            });
+
+        // Find components whose content has changed
+        let mut changed_components: HashSet<Entity> = HashSet::new();
+        for entity in changed_content_query
+            .iter()
+            .chain(changed_hierarchy_query.iter())
+            .chain(changed_layout_entities)
+            .chain(changed_path_entities)
+        {
+            if let Some(component) = find_component_ancestor(
+                entity,
+                &parent_query,
+                &style_parent_query,
+                &component_query,
+            ) {
+                changed_components.insert(component);
+            }
+        }
+
+        let changed_instances: HashSet<Entity> = changed_instance_query.iter().collect();
+
+        for (entity, instance_mixin) in instance_query.iter() {
+            let maybe_component = instance_mixin
+                .component_id
+                .get_entity(referencer_res.get_reference_id_to_entity_map());
+            if changed_instances.contains(&entity)
+                || maybe_component
+                    .map_or(false, |component| changed_components.contains(&component))
+            {
+                to_sync_instances.push(entity);
+            }
+        }
+    }
+
+    for instance in to_sync_instances {
+        // Instance might have been removed while rebuilding an outer instance
+        if world.get::<Removed>(instance).is_none() {
+            instantiate_component(world, instance, &mut Vec::new());
+        }
+    }
+}
+
+fn find_component_ancestor(
+    entity: Entity,
+    parent_query: &Query<&Parent>,
+    style_parent_query: &Query<&StyleParentMixin>,
+    component_query: &Query<(), With<ComponentMixin>>,
+) -> Option<Entity> {
+    let mut current = match style_parent_query.get(entity) {
+        Ok(StyleParentMixin(node)) => *node,
+        Err(_) => entity,
+    };
+
+    loop {
+        if component_query.contains(current) {
+            return Some(current);
+        }
+        current = parent_query.get(current).ok()?.get();
+    }
+}
+
+fn instantiate_component(world: &mut World, instance: Entity, component_stack: &mut Vec<Entity>) {
+    let instance_mixin = match world.get::<InstanceMixin>(instance) {
+        Some(instance_mixin) => instance_mixin.clone(),
+        None => return,
+    };
+
+    remove_instance_content(world, instance);
+
+    let maybe_component = instance_mixin
+        .component_id
+        .get_entity(
+            world
+                .resource::<ReferencerRes>()
+                .get_reference_id_to_entity_map(),
+        )
+        .filter(|component| {
+            world.get::<ComponentMixin>(*component).is_some()
+                && world.get::<Removed>(*component).is_none()
+        });
+    let component = match maybe_component {
+        Some(component) => component,
+        None => {
+            log::warn!(
+                "Failed to instantiate component {:?} because it doesn't exist!",
+                instance_mixin.component_id
+            );
+            return;
+        }
+    };
+
+    if component_stack.contains(&component) {
+        log::warn!(
+            "Failed to instantiate component {:?} because it contains an instance of itself!",
+            instance_mixin.component_id
+        );
+        return;
+    }
+    component_stack.push(component);
+
+    let mut entity_map: HashMap<Entity, Entity> = HashMap::new();
+    entity_map.insert(component, instance);
+
+    // Take over content of component root
+    clone_component::<FrameArbNode>(world, component, instance);
+    clone_component::<CornerRadiiMixin>(world, component, instance);
+    match world.get::<StaticLayoutParentMixin>(component).cloned() {
+        Some(static_layout_parent_mixin) => {
+            world
+                .entity_mut(instance)
+                .insert(static_layout_parent_mixin);
+        }
+        None => {
+            world
+                .entity_mut(instance)
+                .remove::<StaticLayoutParentMixin>();
+        }
+    }
+    clone_styles(world, component, instance, &mut entity_map);
+
+    // Clone children of component root
+    let children = get_children(world, component);
+    let mut cloned_children = Vec::with_capacity(children.len());
+    for child in children {
+        cloned_children.push(clone_node(world, child, &mut entity_map, component_stack));
+    }
+    world.entity_mut(instance).push_children(&cloned_children);
+
+    apply_overrides(world, &instance_mixin.overrides, &entity_map);
+
+    component_stack.pop();
+}
+
+fn remove_instance_content(world: &mut World, instance: Entity) {
+    for child in get_children(world, instance) {
+        mark_node_as_removed(world, child);
+    }
+    world.entity_mut(instance).clear_children();
+
+    remove_styles(world, instance);
+    world
+        .entity_mut(instance)
+        .insert(StyleChildrenMixin(SmallVec::new()));
+}
+
+fn mark_node_as_removed(world: &mut World, node: Entity) {
+    remove_styles(world, node);
+    world.entity_mut(node).insert(Removed);
+
+    for child in get_children(world, node) {
+        mark_node_as_removed(world, child);
+    }
+}
+
+fn remove_styles(world: &mut World, node: Entity) {
+    let styles = match world.get::<StyleChildrenMixin>(node) {
+        Some(StyleChildrenMixin(styles)) => styles.clone(),
+        None => return,
+    };
+
+    for style in styles {
+        unlink_style_from_paint(world, style);
+        world.entity_mut(style).insert(Removed);
+    }
+}
+
+fn clone_node(
+    world: &mut World,
+    source: Entity,
+    entity_map: &mut HashMap<Entity, Entity>,
+    component_stack: &mut Vec<Entity>,
+) -> Entity {
+    let target = world
+        .spawn((InstanceContent, GlobalTransform::default()))
+        .id();
+    entity_map.insert(source, target);
+
+    clone_component::<ArbNode>(world, source, target);
+    clone_component::<FrameArbNode>(world, source, target);
+    clone_component::<RectangleArbNode>(world, source, target);
+    clone_component::<EllipseArbNode>(world, source, target);
+    clone_component::<StarArbNode>(world, source, target);
+    clone_component::<PolygonArbNode>(world, source, target);
+    clone_component::<TextArbNode>(world, source, target);
+    clone_component::<VectorArbNode>(world, source, target);
+    clone_component::<BooleanOperationArbNode>(world, source, target);
+    clone_component::<Transform>(world, source, target);
+    clone_component::<SizeMixin>(world, source, target);
+    clone_component::<CornerRadiiMixin>(world, source, target);
+    clone_component::<VisibilityMixin>(world, source, target);
+    clone_component::<BlendModeMixin>(world, source, target);
+    clone_component::<OpacityMixin>(world, source, target);
+    clone_component::<PathMixin>(world, source, target);
+    clone_component::<StaticLayoutParentMixin>(world, source, target);
+    clone_component::<StaticLayoutElementMixin>(world, source, target);
+    clone_component::<AbsoluteLayoutElementMixin>(world, source, target);
+    clone_component::<InstanceMixin>(world, source, target);
+    clone_styles(world, source, target, entity_map);
+
+    // Nested instances build their content from their own component
+    if world.get::<InstanceMixin>(source).is_some() {
+        instantiate_component(world, target, component_stack);
+    } else {
+        let children = get_children(world, source);
+        let mut cloned_children = Vec::with_capacity(children.len());
+        for child in children {
+            cloned_children.push(clone_node(world, child, entity_map, component_stack));
+        }
+        world.entity_mut(target).push_children(&cloned_children);
+    }
+
+    return target;
+}
+
+fn clone_styles(
+    world: &mut World,
+    source: Entity,
+    target: Entity,
+    entity_map: &mut HashMap<Entity, Entity>,
+) {
+    let styles = match world.get::<StyleChildrenMixin>(source) {
+        Some(StyleChildrenMixin(styles)) => styles.clone(),
+        None => return,
+    };
+
+    let mut cloned_styles = SmallVec::new();
+    for style in styles {
+        if world.get::<Removed>(style).is_some() {
+            continue;
+        }
+
+        let cloned_style = world
+            .spawn((InstanceContent, StyleParentMixin(target)))
+            .id();
+        entity_map.insert(style, cloned_style);
+
+        clone_component::<ArbStyle>(world, style, cloned_style);
+        clone_component::<FillArbStyle>(world, style, cloned_style);
+        clone_component::<StrokeArbStyle>(world, style, cloned_style);
+        clone_component::<DropShadowArbStyle>(world, style, cloned_style);
+        clone_component::<InnerShadowArbStyle>(world, style, cloned_style);
+        clone_component::<LayerBlurArbStyle>(world, style, cloned_style);
+        clone_component::<BackgroundBlurArbStyle>(world, style, cloned_style);
+        clone_component::<VisibilityMixin>(world, style, cloned_style);
+        clone_component::<BlendModeMixin>(world, style, cloned_style);
+        clone_component::<OpacityMixin>(world, style, cloned_style);
+
+        if let Some(PaintChildMixin(paint)) = world.get::<PaintChildMixin>(style).cloned() {
+            link_style_to_paint(world, cloned_style, paint);
+        }
+
+        cloned_styles.push(cloned_style);
+    }
+
+    world
+        .entity_mut(target)
+        .insert(StyleChildrenMixin(cloned_styles));
+}
+
+fn apply_overrides(
+    world: &mut World,
+    overrides: &[InstanceOverride],
+    entity_map: &HashMap<Entity, Entity>,
+) {
+    for instance_override in overrides {
+        let maybe_target = instance_override
+            .target_id
+            .get_entity(
+                world
+                    .resource::<ReferencerRes>()
+                    .get_reference_id_to_entity_map(),
+            )
+            .and_then(|source| entity_map.get(&source).copied());
+        let target = match maybe_target {
+            Some(target) => target,
+            None => {
+                log::warn!(
+                    "Override target {:?} isn't part of the component!",
+                    instance_override.target_id
+                );
+                continue;
+            }
+        };
+
+        if let Some(text) = &instance_override.text {
+            if let Some(mut text_arb_node) = world.get_mut::<TextArbNode>(target) {
+                text_arb_node.replace_text(text.clone());
+            }
+        }
+
+        if let Some(paint_id) = &instance_override.paint_id {
+            let maybe_paint = paint_id.get_entity(
+                world
+                    .resource::<ReferencerRes>()
+                    .get_reference_id_to_entity_map(),
+            );
+            if let Some(paint) = maybe_paint {
+                if world.get::<PaintChildMixin>(target).is_some() {
+                    unlink_style_from_paint(world, target);
+                    link_style_to_paint(world, target, paint);
+                }
+            }
+        }
+
+        if let Some(visible) = instance_override.visible {
+            if let Some(mut visibility_mixin) = world.get_mut::<VisibilityMixin>(target) {
+                visibility_mixin.0 = visible;
+            }
+        }
+
+        if let Some(size) = instance_override.size {
+            if let Some(mut size_mixin) = world.get_mut::<SizeMixin>(target) {
+                size_mixin.0 = size;
+            }
+        }
+    }
+}
+
+fn link_style_to_paint(world: &mut World, style: Entity, paint: Entity) {
+    if let Some(mut paint_parent_mixin) = world.get_mut::<PaintParentMixin>(paint) {
+        paint_parent_mixin.0.push(style);
+        world.entity_mut(style).insert(PaintChildMixin(paint));
+    }
+}
+
+fn unlink_style_from_paint(world: &mut World, style: Entity) {
+    if let Some(PaintChildMixin(paint)) = world.get::<PaintChildMixin>(style).cloned() {
+        if let Some(mut paint_parent_mixin) = world.get_mut::<PaintParentMixin>(paint) {
+            paint_parent_mixin.0.retain(|e| *e != style);
+        }
+    }
+}
+
+fn get_children(world: &World, entity: Entity) -> Vec<Entity> {
+    match world.get::<Children>(entity) {
+        Some(children) => children
+            .iter()
+            .copied()
+            .filter(|child| world.get::<Removed>(*child).is_none())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn clone_component<C: Component + Clone>(world: &mut World, source: Entity, target: Entity) {
+    if let Some(component) = world.get::<C>(source).cloned() {
+        world.entity_mut(target).insert(component);
+    }
+}
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_app, get_entity, reference_id};
+    use bevy_app::{App, Update};
+    use bevy_ecs::{
+        schedule::IntoSystemConfigs,
+        system::{ResMut, Resource},
+    };
+    use dyn_arb_bundles::{
+        components::paints::SolidArbPaint,
+        events::{
+            CreateNodeInputEvent, CreatePaintInputEvent, UpdateEntityCornerRadiiInputEvent,
+            UpdateSolidPaintInputEvent,
+        },
+        mapper::{string_to_tiny_skia_path, tiny_skia_path_to_string},
+        reference_id::{ReferenceId, ReferenceIdOrEntity},
+    };
+    use dyn_utils::{
+        properties::{color::Color, corner_radii::CornerRadii},
+        units::angle::Angle,
+    };
+
+    /// Path to set on a node from within the update cycle, like an input event would.
+    #[derive(Resource, Default)]
+    struct PendingPath(Option<(Entity, tiny_skia_path::Path)>);
+
+    fn apply_pending_path(mut pending_path: ResMut<PendingPath>, mut query: Query<&mut PathMixin>) {
+        if let Some((entity, path)) = pending_path.0.take() {
+            if let Ok(mut path_mixin) = query.get_mut(entity) {
+                path_mixin.path = path;
+            }
+        }
+    }
+
+    fn get_instance_content(app: &App) -> (Entity, Entity) {
+        let children = app
+            .world
+            .get::<Children>(get_entity(app, "instance"))
+            .unwrap();
+        return (children[0], children[1]);
+    }
+
+    #[test]
+    fn test_sync_instance_on_component_change() {
+        let mut app = create_app(100.0, 100.0, false);
+        app.init_resource::<PendingPath>();
+        app.add_systems(Update, apply_pending_path.before(sync_instances_system));
+
+        let nodes = serde_json::json!([
+            { "type": "Component", "id": "component", "size": [50, 50],
+                "children": [reference_id("rect"), reference_id("vector")] },
+            { "type": "Rectangle", "id": "rect", "size": [10, 10],
+                "styles": [{ "type": "Fill", "paintId": reference_id("paint") }] },
+            { "type": "Vector", "id": "vector", "path": "M0 0 L10 10",
+                "windingRule": "Nonzero", "size": [10, 10] },
+            { "type": "Instance", "id": "instance", "componentId": reference_id("component"),
+                "size": [50, 50] }
+        ]);
+        for node in serde_json::from_value::<Vec<_>>(nodes).unwrap() {
+            app.world.send_event(CreateNodeInputEvent { node });
+        }
+        app.world.send_event(CreatePaintInputEvent {
+            paint: serde_json::from_value(serde_json::json!({
+                "type": "Solid", "id": "paint", "color": [0, 0, 0]
+            }))
+            .unwrap(),
+        });
+        app.update();
+
+        let (instance_rect, instance_vector) = get_instance_content(&app);
+        assert!(app.world.get::<InstanceContent>(instance_rect).is_some());
+        assert!(app.world.get::<RectangleArbNode>(instance_rect).is_some());
+        assert!(app.world.get::<VectorArbNode>(instance_vector).is_some());
+
+        // Change the path of the component vector
+        app.world.resource_mut::<PendingPath>().0 = Some((
+            get_entity(&app, "vector"),
+            string_to_tiny_skia_path("M0 0 L10 0 L10 10 Z").unwrap(),
+        ));
+        app.update();
+
+        let (_, instance_vector) = get_instance_content(&app);
+        assert_eq!(
+            tiny_skia_path_to_string(&app.world.get::<PathMixin>(instance_vector).unwrap().path),
+            "M 0 0 L 10 0 L 10 10 Z"
+        );
+
+        // Change the corner radii of the component rectangle and its paint
+        let corner_radii = CornerRadii::new(
+            Angle::deg(4.0),
+            Angle::deg(4.0),
+            Angle::deg(4.0),
+            Angle::deg(4.0),
+        );
+        app.world.send_event(UpdateEntityCornerRadiiInputEvent {
+            id: ReferenceIdOrEntity::reference_id(ReferenceId::new(String::from("rect"))),
+            corner_radii,
+        });
+        app.world.send_event(UpdateSolidPaintInputEvent {
+            id: ReferenceIdOrEntity::reference_id(ReferenceId::new(String::from("paint"))),
+            color: Color::new_rgb(255, 0, 0),
+        });
+        app.update();
+
+        let (instance_rect, _) = get_instance_content(&app);
+        assert_eq!(
+            app.world.get::<CornerRadiiMixin>(instance_rect).unwrap().0,
+            corner_radii
+        );
+
+        // Paint is shared with the component
+        let instance_style = app
+            .world
+            .get::<StyleChildrenMixin>(instance_rect)
+            .unwrap()
+            .0[0];
+        let PaintChildMixin(paint) = app.world.get::<PaintChildMixin>(instance_style).unwrap();
+        assert_eq!(
+            app.world.get::<SolidArbPaint>(*paint).unwrap().color,
+            Color::new_rgb(255, 0, 0)
+        );
+    }
+}
@@ -2,14 +2,18 @@ use crate::resources::tick::TickRes;
 use bevy_ecs::{
     change_detection::DetectChanges,
     entity::Entity,
-    query::{Changed, Or, With},
+    query::{Changed, Has, Or, With},
     system::{Commands, ParamSet, Query, Res},
     world::{Mut, Ref},
 };
 use bevy_hierarchy::{Children, Parent};
 use bevy_transform::components::Transform;
-use dyn_arb_bundles::components::mixins::{
-    AbsoluteLayoutElementMixin, Constraint, Constraints, PreAbsoluteLayoutProperties, SizeMixin,
+use dyn_arb_bundles::components::{
+    marker::StaleStaticLayout,
+    mixins::{
+        AbsoluteLayoutElementMixin, Constraint, Constraints, PreAbsoluteLayoutProperties,
+        SizeMixin, StaticLayoutNodeId,
+    },
 };
 use dyn_utils::{properties::size::Size, units::abs::Abs};
 
@@ -55,6 +59,7 @@ pub fn apply_pre_absolute_layout_properties(
 }
 
 pub fn update_absolute_layout(
+    mut commands: Commands,
     mut query_set: ParamSet<(
         Query<(&Children, &SizeMixin), Changed<SizeMixin>>,
         Query<(Entity, &Parent), (With<AbsoluteLayoutElementMixin>, Changed<SizeMixin>)>,
@@ -64,7 +69,9 @@ pub fn update_absolute_layout(
             &AbsoluteLayoutElementMixin,
             &mut Transform,
             &PreAbsoluteLayoutProperties,
+            Has<StaticLayoutNodeId>,
         )>,
+        Query<&Children>,
     )>,
 ) {
     let mut to_update_children: Vec<(Entity, Size)> = Vec::new();
@@ -89,14 +96,16 @@ pub fn update_absolute_layout(
     }
 
     // Compute absolute layout and apply it to the children
-    for (child, parent_size) in to_update_children.iter() {
-        if let Ok((
+    while let Some((child, parent_size)) = to_update_children.pop() {
+        let maybe_resized_size = if let Ok((
             mut size_mixin,
             AbsoluteLayoutElementMixin(absolute_layout_element),
             mut transform,
             layout_metric,
-        )) = query_set.p3().get_mut(*child)
+            is_static_layout_parent,
+        )) = query_set.p3().get_mut(child)
         {
+            let previous_size = size_mixin.0;
             apply_horizontal_constraint(
                 &absolute_layout_element.constraints,
                 &mut transform,
@@ -111,6 +120,27 @@ pub fn update_absolute_layout(
                 &parent_size,
                 layout_metric,
             );
+            let resized = size_mixin.0 != previous_size;
+
+            // Static layout parents have to lay out their children again,
+            // since they are the root of their own static layout
+            if resized && is_static_layout_parent {
+                commands.entity(child).insert(StaleStaticLayout);
+            }
+
+            resized.then_some(size_mixin.0)
+        } else {
+            None
+        };
+
+        // Propagate the resize to the grandchildren within the same update cycle,
+        // so that nested constraints (e.g. scaled frames in scaled frames) compose
+        if let Some(size) = maybe_resized_size {
+            if let Ok(children) = query_set.p4().get(child) {
+                for grandchild in children.iter() {
+                    to_update_children.push((*grandchild, size));
+                }
+            }
         }
     }
 }
@@ -149,8 +179,9 @@ fn apply_horizontal_constraint(
             child_size_mixin.0.width = pre_size.width + Abs::pt(right - left);
         }
         Constraint::Scale => {
-            // TODO
-            log::warn!("Horizontal 'Scale' constraint currently not supported!");
+            let scale_x = scale_factor(parent_size.width(), pre_parent_size.unwrap().width());
+            child_transform.translation.x = pre_translation.x * scale_x;
+            child_size_mixin.0.width = pre_size.width * scale_x;
         }
     }
 }
@@ -190,8 +221,89 @@ fn apply_vertical_constraint(
             child_size_mixin.0.height = pre_size.height + Abs::pt(bottom - top);
         }
         Constraint::Scale => {
-            // TODO
-            log::warn!("Vertical 'Scale' constraint currently not supported!");
+            let scale_y = scale_factor(parent_size.height(), pre_parent_size.unwrap().height());
+            child_transform.translation.y = pre_translation.y * scale_y;
+            child_size_mixin.0.height = pre_size.height * scale_y;
+        }
+    }
+}
+
+/// Helper function to calculate how much the parent has been scaled
+/// since the pre absolute layout properties were captured.
+fn scale_factor(parent_length: f32, pre_parent_length: f32) -> f32 {
+    if pre_parent_length > 0.0 {
+        parent_length / pre_parent_length
+    } else {
+        1.0
+    }
+}
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_app, get_layout, reference_id_or_entity};
+    use bevy_app::App;
+    use dyn_arb_bundles::events::{CreateNodeInputEvent, UpdateEntitySizeInputEvent};
+    use glam::Vec2;
+
+    fn resize(app: &mut App, id: &str, width: f32, height: f32) {
+        app.world.send_event(UpdateEntitySizeInputEvent {
+            id: reference_id_or_entity(id),
+            size: Size::new(Abs::pt(width), Abs::pt(height)),
+        });
+        app.update();
+    }
+
+    #[test]
+    fn test_nested_scale_constraints() {
+        let mut app = create_app(100.0, 100.0, false);
+
+        let scale = serde_json::json!({
+            "type": "Absolute",
+            "constraints": { "horizontal": "Scale", "vertical": "Scale" }
+        });
+        for node in [
+            serde_json::json!({
+                "type": "Frame", "id": "root", "size": [100, 100],
+                "children": [{ "type": "ReferenceId", "referenceId": "outer" }]
+            }),
+            serde_json::json!({
+                "type": "Frame", "id": "outer", "translation": [10, 20], "size": [40, 40],
+                "layoutElement": scale,
+                "children": [{ "type": "ReferenceId", "referenceId": "inner" }]
+            }),
+            serde_json::json!({
+                "type": "Rectangle", "id": "inner", "translation": [10, 5], "size": [20, 10],
+                "layoutElement": scale
+            }),
+        ] {
+            app.world.send_event(CreateNodeInputEvent {
+                node: serde_json::from_value(node).unwrap(),
+            });
         }
+        app.update();
+        app.update();
+
+        // The scale of the outer frame composes with the scale of the inner node
+        resize(&mut app, "root", 200.0, 300.0);
+        assert_eq!(
+            get_layout(&app, "outer"),
+            (Vec2::new(20.0, 60.0), Vec2::new(80.0, 120.0))
+        );
+        assert_eq!(
+            get_layout(&app, "inner"),
+            (Vec2::new(20.0, 15.0), Vec2::new(40.0, 30.0))
+        );
+
+        // Scaling relative to the initial size doesn't accumulate rounding errors
+        resize(&mut app, "root", 50.0, 50.0);
+        assert_eq!(
+            get_layout(&app, "outer"),
+            (Vec2::new(5.0, 10.0), Vec2::new(20.0, 20.0))
+        );
+        assert_eq!(
+            get_layout(&app, "inner"),
+            (Vec2::new(5.0, 2.5), Vec2::new(10.0, 5.0))
+        );
     }
 }
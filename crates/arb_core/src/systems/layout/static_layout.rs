@@ -1,11 +1,12 @@
 use crate::resources::{
+    artboard::ArtboardRes,
     layout::{debug::create_taffy_to_entity_map, layout_tree::LayoutTree, LayoutRes},
     tick::TickRes,
 };
 use bevy_ecs::{
     change_detection::DetectChanges,
     entity::{Entity, EntityHashMap, EntityHashSet},
-    query::{Added, Changed, Or, With, Without},
+    query::{Added, Changed, Has, Or, With, Without},
     system::{Commands, Query, Res, ResMut},
     world::Ref,
 };
@@ -14,11 +15,19 @@ use bevy_transform::components::Transform;
 use dyn_arb_bundles::components::{
     marker::{Removed, StaleStaticLayout},
     mixins::{
-        AbsoluteLayoutElementMixin, LayoutParentSizingMode, SizeMixin, StaticLayoutElementMixin,
-        StaticLayoutNodeId, StaticLayoutParentMixin,
+        AbsoluteLayoutElementMixin, AttributedStringMixin, LayoutParentSizingMode, SizeMixin,
+        StaticLayoutElementMixin, StaticLayoutNodeId, StaticLayoutParentMixin,
     },
+    nodes::TextArbNode,
 };
-use dyn_utils::units::abs::Abs;
+use dyn_attributed_string::{
+    layout::{
+        layouter::{Layouter, LayouterConfig},
+        LayoutSize, TextSizingMode,
+    },
+    AttributedString,
+};
+use dyn_utils::units::{abs::Abs, auto_length::AutoLength};
 use glam::Vec3;
 use std::collections::HashMap;
 
@@ -34,6 +43,8 @@ pub fn discover_new_static_layout_parent_nodes(
             Option<&StaticLayoutParentMixin>,
             Option<&StaticLayoutElementMixin>,
             &SizeMixin,
+            Option<&TextArbNode>,
+            Has<AbsoluteLayoutElementMixin>,
         ),
         (
             Without<StaticLayoutNodeId>,
@@ -51,6 +62,8 @@ pub fn discover_new_static_layout_parent_nodes(
             maybe_static_layout_parent_mixin,
             maybe_static_layout_element_mixin,
             SizeMixin(size),
+            maybe_text,
+            _,
         )) = layout_mixin_query.get(entity)
         {
             // Process and potentially update or create a new layout node
@@ -59,6 +72,7 @@ pub fn discover_new_static_layout_parent_nodes(
                     maybe_static_layout_parent_mixin.map(|m| &m.0),
                     maybe_static_layout_element_mixin.map(|m| &m.0),
                     &size,
+                    maybe_text.map(|text| text.sizing_mode),
                 );
 
                 // Create or update layout node based on presence of an existing node id
@@ -82,6 +96,8 @@ pub fn discover_new_static_layout_parent_nodes(
                         maybe_child_static_layout_parent_mixin,
                         maybe_child_static_layout_elment_mixin,
                         SizeMixin(child_size),
+                        maybe_child_text,
+                        false,
                     )) = layout_mixin_query.get(*child).ok()
                     {
                         Some(*inserted_layout_nodes.entry(*child).or_insert_with(|| {
@@ -89,6 +105,7 @@ pub fn discover_new_static_layout_parent_nodes(
                                 maybe_child_static_layout_parent_mixin.map(|m| &m.0),
                                 maybe_child_static_layout_elment_mixin.map(|m| &m.0),
                                 child_size,
+                                maybe_child_text.map(|text| text.sizing_mode),
                             );
                             let new_layout_node_id = layout_res.tree.new_leaf(style).unwrap();
 
@@ -137,6 +154,7 @@ pub fn update_static_layout_parent_nodes_children(
             Option<&StaticLayoutParentMixin>,
             Option<&StaticLayoutElementMixin>,
             &SizeMixin,
+            Option<&TextArbNode>,
         ),
         (Without<AbsoluteLayoutElementMixin>,),
     >,
@@ -151,6 +169,7 @@ pub fn update_static_layout_parent_nodes_children(
                     maybe_child_static_layout_parent_mixin,
                     maybe_child_static_layout_elment_mixin,
                     SizeMixin(size),
+                    maybe_child_text,
                 )) = layout_mixin_query.get(*child).ok()
                 {
                     if let Some(StaticLayoutNodeId(child_layout_node_id)) =
@@ -162,6 +181,7 @@ pub fn update_static_layout_parent_nodes_children(
                             maybe_child_static_layout_parent_mixin.map(|mixin| &mixin.0),
                             maybe_child_static_layout_elment_mixin.map(|mixin| &mixin.0),
                             size,
+                            maybe_child_text.map(|text| text.sizing_mode),
                         );
                         let new_layout_node_id = layout_res.tree.new_leaf(style).unwrap();
 
@@ -208,6 +228,7 @@ pub fn mark_nodes_with_static_layout_change_as_stale(
             Option<&StaticLayoutElementMixin>,
             Ref<Transform>,
             Ref<SizeMixin>,
+            Option<Ref<TextArbNode>>,
         ),
         (
             With<StaticLayoutNodeId>,
@@ -217,6 +238,7 @@ pub fn mark_nodes_with_static_layout_change_as_stale(
                 Changed<StaticLayoutElementMixin>,
                 Changed<Transform>,
                 Changed<SizeMixin>,
+                Changed<TextArbNode>,
             )>,
         ),
     >,
@@ -228,6 +250,7 @@ pub fn mark_nodes_with_static_layout_change_as_stale(
         maybe_static_layout_element_mixin,
         transform,
         size_mixin,
+        maybe_text,
     ) in query.iter()
     {
         // Check if Transform or Size has been altered during the current update cycle or the previous one.
@@ -240,13 +263,18 @@ pub fn mark_nodes_with_static_layout_change_as_stale(
         //
         // We monitor Transform changes even if not directly utilized, to uphold and enforce layout-driven positioning,
         // ensuring that all spatial adjustments align strictly with the intended layout specifications.
+        //
+        // Text changes are never caused by the layout system,
+        // but might change the intrinsic size of the text.
         if transform.last_changed().get() > tick_res.first_in_cycle.get()
             || size_mixin.last_changed().get() > tick_res.first_in_cycle.get()
+            || maybe_text.as_ref().is_some_and(|text| text.is_changed())
         {
             let new_style = LayoutTree::merge_layout_parent_with_element(
                 maybe_static_layout_parent_mixin.map(|mixin| &mixin.0),
                 maybe_static_layout_element_mixin.map(|mixin| &mixin.0),
                 &size_mixin.0,
+                maybe_text.map(|text| text.sizing_mode),
             );
             layout_res.tree.update_leaf(*layout_node_id, new_style);
             commands.entity(entity).insert(StaleStaticLayout);
@@ -268,8 +296,12 @@ pub fn remove_stale_layout_nodes(
 pub fn update_static_layout(
     mut commands: Commands,
     mut layout_res: ResMut<LayoutRes>,
+    arb_res: Res<ArtboardRes>,
     stale_nodes_query: Query<Entity, (Added<StaleStaticLayout>, With<StaticLayoutNodeId>)>,
-    parent_query: Query<(Entity, Option<&Parent>), With<StaticLayoutNodeId>>,
+    parent_query: Query<
+        (Entity, Option<&Parent>, Has<AbsoluteLayoutElementMixin>),
+        With<StaticLayoutNodeId>,
+    >,
     children_query: Query<&Children, With<StaticLayoutNodeId>>,
     mut to_update_nodes_query: Query<
         (
@@ -277,10 +309,12 @@ pub fn update_static_layout(
             &mut SizeMixin,
             &mut Transform,
             Option<&StaticLayoutParentMixin>,
+            Has<AbsoluteLayoutElementMixin>,
         ),
         With<StaticLayoutNodeId>,
     >,
     layout_node_id_query: Query<(Entity, &StaticLayoutNodeId)>,
+    text_query: Query<(&TextArbNode, &AttributedStringMixin)>,
 ) {
     if stale_nodes_query.is_empty() {
         return;
@@ -296,7 +330,17 @@ pub fn update_static_layout(
     }
 
     // Recompute layout
+    layout_res.tree.set_rounding(!arb_res.sub_pixel_layout);
     let taffy_to_entity = create_taffy_to_entity_map(&layout_node_id_query);
+    let mut measure_function = |known_dimensions, available_space, node_id| {
+        taffy_to_entity
+            .get(&node_id)
+            .and_then(|entity| text_query.get(*entity).ok())
+            .map(|(text, AttributedStringMixin(attributed_string))| {
+                measure_text(text, attributed_string, known_dimensions, available_space)
+            })
+            .unwrap_or(taffy::Size::ZERO)
+    };
     for parent in to_recompute_parents.iter() {
         update_node_layout_recursive(
             *parent,
@@ -305,24 +349,77 @@ pub fn update_static_layout(
             &mut layout_res,
             true,
             &taffy_to_entity,
+            &mut measure_function,
         );
     }
 }
 
+/// Measures the size of a text node
+/// based on the dimensions already known to the static layout.
+fn measure_text(
+    text: &TextArbNode,
+    attributed_string: &AttributedString,
+    known_dimensions: taffy::Size<Option<f32>>,
+    available_space: taffy::Size<taffy::AvailableSpace>,
+) -> taffy::Size<f32> {
+    let width = match (
+        known_dimensions.width,
+        text.sizing_mode,
+        available_space.width,
+    ) {
+        (Some(width), ..) => AutoLength::abs(Abs::pt(width)),
+        // Text that grows horizontally is never wrapped
+        (None, TextSizingMode::WidthAndHeight, _) => AutoLength::Auto,
+        (None, _, taffy::AvailableSpace::Definite(width)) => AutoLength::abs(Abs::pt(width)),
+        (None, ..) => AutoLength::Auto,
+    };
+    let height = match known_dimensions.height {
+        Some(height) => AutoLength::abs(Abs::pt(height)),
+        None => AutoLength::Auto,
+    };
+
+    let mut layouter = Layouter::new(LayouterConfig {
+        size: LayoutSize::new(width, height),
+        line_wrap: text.line_wrap,
+        horizontal_text_alignment: text.horizontal_text_alignment,
+        vertical_text_alignment: text.vertical_text_alignment,
+    });
+    let container_size = layouter.measure(attributed_string.get_spans());
+
+    return taffy::Size {
+        width: container_size.width(),
+        height: container_size.height(),
+    };
+}
+
 fn find_topmost_parent(
     entity: Entity,
-    parent_query: &Query<(Entity, Option<&Parent>), With<StaticLayoutNodeId>>,
+    parent_query: &Query<
+        (Entity, Option<&Parent>, Has<AbsoluteLayoutElementMixin>),
+        With<StaticLayoutNodeId>,
+    >,
 ) -> Entity {
     let mut next = entity;
     let mut current = entity;
-    while let Ok((current_entity, Some(current_parent))) = parent_query.get(next) {
+    while let Ok((current_entity, maybe_parent, is_absolute)) = parent_query.get(next) {
         current = current_entity;
-        next = current_parent.get();
+
+        // Absolute layout elements are excluded from the flow of their parent,
+        // thus they are the root of their own static layout
+        if is_absolute {
+            break;
+        }
+
+        if let Some(parent) = maybe_parent {
+            next = parent.get();
+        } else {
+            break;
+        }
     }
     return current;
 }
 
-fn update_node_layout_recursive(
+fn update_node_layout_recursive<F>(
     entity: Entity,
     to_update_nodes_query: &mut Query<
         (
@@ -330,6 +427,7 @@ fn update_node_layout_recursive(
             &mut SizeMixin,
             &mut Transform,
             Option<&StaticLayoutParentMixin>,
+            Has<AbsoluteLayoutElementMixin>,
         ),
         With<StaticLayoutNodeId>,
     >,
@@ -337,16 +435,42 @@ fn update_node_layout_recursive(
     layout_res: &mut ResMut<LayoutRes>,
     is_root: bool,
     taffy_to_entity: &HashMap<taffy::NodeId, Entity>,
-) {
+    measure_function: &mut F,
+) where
+    F: FnMut(
+        taffy::Size<Option<f32>>,
+        taffy::Size<taffy::AvailableSpace>,
+        taffy::NodeId,
+    ) -> taffy::Size<f32>,
+{
     if let Ok((
         StaticLayoutNodeId(layout_node_id),
         mut size_mixin,
         mut transform,
         maybe_static_layout_parent_mixin,
+        is_absolute,
     )) = to_update_nodes_query.get_mut(entity)
     {
+        // Absolute layout elements aren't part of the parent's layout computation
+        // and are positioned by the absolute layout instead
+        if is_absolute && !is_root {
+            return;
+        }
+
         // Root nodes have their layout computed only once at the beginning.
         if is_root {
+            // The size of absolute layout elements might have been changed by their constraints
+            // since the layout node was last updated
+            if is_absolute {
+                let style = LayoutTree::merge_layout_parent_with_element(
+                    maybe_static_layout_parent_mixin.map(|mixin| &mixin.0),
+                    None,
+                    &size_mixin.0,
+                    None,
+                );
+                layout_res.tree.update_leaf(*layout_node_id, style);
+            }
+
             let mut taffy_size = taffy::Size {
                 width: taffy::AvailableSpace::Definite(size_mixin.0.width()),
                 height: taffy::AvailableSpace::Definite(size_mixin.0.height()),
@@ -369,7 +493,11 @@ fn update_node_layout_recursive(
                 };
             };
 
-            if let Err(e) = layout_res.tree.compute_layout(*layout_node_id, taffy_size) {
+            if let Err(e) =
+                layout_res
+                    .tree
+                    .compute_layout(*layout_node_id, taffy_size, &mut *measure_function)
+            {
                 log::error!(
                     "Failed to compute layout for node {:?}: {:?}",
                     layout_node_id,
@@ -383,9 +511,6 @@ fn update_node_layout_recursive(
         }
 
         // Apply computed layout to the node's properties
-        //
-        // TODO: Taffy rounds all floating numbers to whole numbers
-        // See: https://github.com/DioxusLabs/taffy/issues/77
         if let Ok(layout) = layout_res.tree.get_layout(*layout_node_id) {
             // log::info!(
             //     "[update_node_layout_recursive] {:?}: {:?}",
@@ -416,8 +541,198 @@ fn update_node_layout_recursive(
                     layout_res,
                     false,
                     taffy_to_entity,
+                    measure_function,
                 );
             }
         }
     }
 }
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_app, get_layout, reference_id};
+    use bevy_app::App;
+    use dyn_arb_asset::asset::{AssetContent, AssetContentType};
+    use dyn_arb_bundles::{
+        events::{CreateAssetInputEvent, CreateNodeInputEvent, UpdateEntitySizeInputEvent},
+        reference_id::{ReferenceId, ReferenceIdOrEntity},
+        AssetWithId,
+    };
+    use dyn_utils::properties::size::Size;
+    use glam::Vec2;
+
+    fn create_app_with_nodes(nodes: Vec<serde_json::Value>, sub_pixel_layout: bool) -> App {
+        let mut app = create_app(500.0, 500.0, sub_pixel_layout);
+        for node in nodes {
+            app.world.send_event(CreateNodeInputEvent {
+                node: serde_json::from_value(node).unwrap(),
+            });
+        }
+        return app;
+    }
+
+    #[test]
+    fn test_absolute_child_in_static_parent() {
+        let static_element = serde_json::json!({ "type": "Static" });
+        let mut app = create_app_with_nodes(
+            vec![
+                serde_json::json!({
+                    "type": "Frame", "id": "parent", "size": [200, 100], "layoutParent": {},
+                    "children": [reference_id("first"), reference_id("absolute"), reference_id("second")]
+                }),
+                serde_json::json!({
+                    "type": "Rectangle", "id": "first", "size": [40, 40],
+                    "layoutElement": static_element
+                }),
+                serde_json::json!({
+                    "type": "Rectangle", "id": "absolute", "translation": [150, 50], "size": [30, 30],
+                    "layoutElement": {
+                        "type": "Absolute",
+                        "constraints": { "horizontal": "End", "vertical": "End" }
+                    }
+                }),
+                serde_json::json!({
+                    "type": "Rectangle", "id": "second", "size": [40, 40],
+                    "layoutElement": static_element
+                }),
+            ],
+            false,
+        );
+        app.update();
+
+        // The absolute child is excluded from the flow of its siblings,
+        // which is laid out bottom-most child first
+        assert_eq!(
+            get_layout(&app, "second"),
+            (Vec2::new(0.0, 0.0), Vec2::new(40.0, 40.0))
+        );
+        assert_eq!(
+            get_layout(&app, "first"),
+            (Vec2::new(40.0, 0.0), Vec2::new(40.0, 40.0))
+        );
+        assert_eq!(
+            get_layout(&app, "absolute"),
+            (Vec2::new(150.0, 50.0), Vec2::new(30.0, 30.0))
+        );
+
+        // and positioned by its constraints instead
+        app.world.send_event(UpdateEntitySizeInputEvent {
+            id: ReferenceIdOrEntity::reference_id(ReferenceId::new(String::from("parent"))),
+            size: Size::new(Abs::pt(300.0), Abs::pt(200.0)),
+        });
+        app.update();
+
+        assert_eq!(
+            get_layout(&app, "absolute"),
+            (Vec2::new(250.0, 150.0), Vec2::new(30.0, 30.0))
+        );
+        assert_eq!(
+            get_layout(&app, "first"),
+            (Vec2::new(40.0, 0.0), Vec2::new(40.0, 40.0))
+        );
+    }
+
+    #[test]
+    fn test_hug_text_in_single_update() {
+        let padding = serde_json::json!({ "type": "Abs", "value": 10 });
+        let mut app = create_app_with_nodes(
+            vec![
+                serde_json::json!({
+                    "type": "Frame", "id": "parent", "size": [1, 1],
+                    "layoutParent": {
+                        "horizontalSizingMode": "Hug",
+                        "verticalSizingMode": "Hug",
+                        "padding": { "left": padding, "right": padding, "top": padding, "bottom": padding }
+                    },
+                    "children": [reference_id("text")]
+                }),
+                serde_json::json!({
+                    "type": "Text", "id": "text", "text": "Hello World", "size": [1, 1],
+                    "attributes": [{ "start": 0, "end": 11, "attributes": { "fontFamily": { "Named": "Fira Mono" }, "fontSize": 20 } }],
+                    "sizingMode": "WidthAndHeight",
+                    "layoutElement": { "type": "Static" }
+                }),
+            ],
+            false,
+        );
+        app.world.send_event(CreateAssetInputEvent {
+            asset: AssetWithId {
+                id: None,
+                content: AssetContent::Binary {
+                    content: include_bytes!(
+                        "../../../../attributed_string/tests/assets/fonts/FiraMono-Medium.ttf"
+                    )
+                    .to_vec(),
+                },
+                content_type: AssetContentType::Ttf,
+            },
+        });
+
+        // The text is measured and the parent hugs it within the same update cycle
+        app.update();
+
+        let (text_translation, text_size) = get_layout(&app, "text");
+        let (_, parent_size) = get_layout(&app, "parent");
+        assert!(text_size.x > 100.0 && text_size.y > 20.0);
+        assert_eq!(text_translation, Vec2::new(10.0, 10.0));
+        assert_eq!(parent_size, text_size + Vec2::splat(20.0));
+    }
+
+    fn create_growing_row_app(sub_pixel_layout: bool) -> App {
+        let growing_element = serde_json::json!({ "type": "Static", "flexGrow": 1 });
+        return create_app_with_nodes(
+            vec![
+                serde_json::json!({
+                    "type": "Frame", "id": "parent", "size": [100, 10], "layoutParent": {},
+                    "children": [reference_id("first"), reference_id("second"), reference_id("third")]
+                }),
+                serde_json::json!({
+                    "type": "Rectangle", "id": "first", "size": [10, 10],
+                    "layoutElement": growing_element
+                }),
+                serde_json::json!({
+                    "type": "Rectangle", "id": "second", "size": [10, 10],
+                    "layoutElement": growing_element
+                }),
+                serde_json::json!({
+                    "type": "Rectangle", "id": "third", "size": [10, 10],
+                    "layoutElement": growing_element
+                }),
+            ],
+            sub_pixel_layout,
+        );
+    }
+
+    #[test]
+    fn test_sub_pixel_layout() {
+        let mut app = create_growing_row_app(true);
+        app.update();
+
+        // Each child takes a third of the parent width
+        for (id, x) in [
+            ("third", 0.0),
+            ("second", 100.0 / 3.0),
+            ("first", 200.0 / 3.0),
+        ] {
+            let (translation, size) = get_layout(&app, id);
+            assert!((translation.x - x).abs() < 0.001, "{id}: {translation}");
+            assert!((size.x - 100.0 / 3.0).abs() < 0.001, "{id}: {size}");
+        }
+    }
+
+    #[test]
+    fn test_rounded_layout() {
+        let mut app = create_growing_row_app(false);
+        app.update();
+
+        let mut width = 0.0;
+        for id in ["third", "second", "first"] {
+            let (translation, size) = get_layout(&app, id);
+            assert_eq!(translation.x, width);
+            assert_eq!(size.x, size.x.round());
+            width += size.x;
+        }
+        assert_eq!(width, 100.0);
+    }
+}
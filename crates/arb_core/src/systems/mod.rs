@@ -1,9 +1,11 @@
 pub mod cleanup;
 pub mod events;
 pub mod hierarchy;
+pub mod instance;
 pub mod layout;
 pub mod outline;
 pub mod stroke;
 pub mod text;
 pub mod tick;
+pub mod variables;
 pub mod vector;
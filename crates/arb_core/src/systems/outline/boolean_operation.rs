@@ -0,0 +1,187 @@
+use super::path_overlay::{flatten_path, normalize_contours, shapes_to_path, Contour, Shapes};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    system::{Commands, Query},
+    world::Ref,
+};
+use bevy_hierarchy::{Children, HierarchyQueryExt, Parent};
+use bevy_transform::components::Transform;
+use dyn_arb_bundles::{
+    components::{
+        mixins::{PathMixin, VisibilityMixin, WindingRule},
+        nodes::{BooleanOperation, BooleanOperationArbNode},
+    },
+    mapper::empty_tiny_skia_path,
+};
+use i_overlay::{
+    core::{fill_rule::FillRule, overlay_rule::OverlayRule},
+    float::single::SingleFloatOverlay,
+};
+use std::collections::HashMap;
+
+pub fn outline_boolean_operation(
+    mut commands: Commands,
+    query: Query<(Entity, Ref<BooleanOperationArbNode>, Option<Ref<Children>>)>,
+    child_query: Query<(Ref<PathMixin>, Ref<Transform>, Ref<VisibilityMixin>)>,
+    parent_query: Query<&Parent>,
+) {
+    // Outline nested boolean operations first as their paths are operands of their parents
+    let mut entities: Vec<(Entity, usize)> = query
+        .iter()
+        .map(|(entity, _, _)| (entity, parent_query.iter_ancestors(entity).count()))
+        .collect();
+    entities.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let mut outlined_paths: HashMap<Entity, tiny_skia_path::Path> = HashMap::new();
+
+    for (entity, _) in entities {
+        let (_, boolean_operation, maybe_children) = match query.get(entity) {
+            Ok(item) => item,
+            Err(_) => continue,
+        };
+        let children: &[Entity] = match &maybe_children {
+            Some(children) => children,
+            None => &[],
+        };
+
+        let is_dirty = boolean_operation.is_changed()
+            || maybe_children
+                .as_ref()
+                .is_some_and(|children| children.is_changed())
+            || children.iter().any(|child| {
+                outlined_paths.contains_key(child)
+                    || child_query
+                        .get(*child)
+                        .is_ok_and(|(path_mixin, transform, visibility)| {
+                            path_mixin.is_changed()
+                                || transform.is_changed()
+                                || visibility.is_changed()
+                        })
+            });
+        if !is_dirty {
+            continue;
+        }
+
+        // Collect operands from bottom to top (first child is the top-most one)
+        let mut operands: Vec<(Vec<Contour>, WindingRule)> = Vec::with_capacity(children.len());
+        for child in children.iter().rev() {
+            if let Ok((path_mixin, transform, visibility)) = child_query.get(*child) {
+                if !visibility.0 {
+                    continue;
+                }
+                let path = outlined_paths.get(child).unwrap_or(&path_mixin.path);
+                if let Some(path) = path.clone().transform(to_path_transform(&transform)) {
+                    operands.push((flatten_path(&path), path_mixin.winding_rule));
+                }
+            }
+        }
+
+        let path = shapes_to_path(&apply_boolean_operation(
+            boolean_operation.operation,
+            operands,
+        ))
+        .unwrap_or_else(empty_tiny_skia_path);
+
+        outlined_paths.insert(entity, path.clone());
+        commands.entity(entity).insert(PathMixin {
+            path,
+            winding_rule: WindingRule::Nonzero,
+        });
+    }
+}
+
+/// Applies the boolean operation to the operands (ordered from bottom to top).
+///
+/// For `Subtract` the bottom-most operand is the one the others are subtracted from.
+fn apply_boolean_operation(
+    operation: BooleanOperation,
+    operands: Vec<(Vec<Contour>, WindingRule)>,
+) -> Shapes {
+    let overlay_rule = match operation {
+        BooleanOperation::Union => OverlayRule::Union,
+        BooleanOperation::Subtract => OverlayRule::Difference,
+        BooleanOperation::Intersect => OverlayRule::Intersect,
+        BooleanOperation::Exclude => OverlayRule::Xor,
+    };
+
+    let mut maybe_result: Option<Shapes> = None;
+    for (contours, winding_rule) in operands {
+        let shapes = normalize_contours(&contours, winding_rule);
+
+        maybe_result = Some(match maybe_result {
+            None => shapes,
+            Some(result) if result.is_empty() || shapes.is_empty() => match overlay_rule {
+                OverlayRule::Union | OverlayRule::Xor if result.is_empty() => shapes,
+                OverlayRule::Intersect => Vec::new(),
+                _ => result,
+            },
+            Some(result) => result.overlay(&shapes, overlay_rule, FillRule::NonZero),
+        });
+    }
+
+    return maybe_result.unwrap_or_default();
+}
+
+/// Converts the local transform of a child into a transform to the parent's coordinate space.
+fn to_path_transform(transform: &Transform) -> tiny_skia_path::Transform {
+    let matrix = transform.compute_matrix();
+    tiny_skia_path::Transform::from_row(
+        matrix.x_axis.x,
+        matrix.x_axis.y,
+        matrix.y_axis.x,
+        matrix.y_axis.y,
+        matrix.w_axis.x,
+        matrix.w_axis.y,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> (Vec<Contour>, WindingRule) {
+        (
+            vec![vec![
+                [x, y],
+                [x + width, y],
+                [x + width, y + height],
+                [x, y + height],
+            ]],
+            WindingRule::Nonzero,
+        )
+    }
+
+    fn area(shapes: &Shapes) -> f32 {
+        shapes
+            .iter()
+            .flatten()
+            .map(|contour| {
+                let mut sum = 0.0;
+                for (i, [x1, y1]) in contour.iter().enumerate() {
+                    let [x2, y2] = contour[(i + 1) % contour.len()];
+                    sum += x1 * y2 - x2 * y1;
+                }
+                sum / 2.0
+            })
+            .sum::<f32>()
+            .abs()
+    }
+
+    #[test]
+    fn test_boolean_operations() {
+        let operands = || vec![rect(0.0, 0.0, 10.0, 10.0), rect(5.0, 0.0, 10.0, 10.0)];
+
+        let union = apply_boolean_operation(BooleanOperation::Union, operands());
+        assert_eq!(area(&union), 150.0);
+
+        let subtract = apply_boolean_operation(BooleanOperation::Subtract, operands());
+        assert_eq!(area(&subtract), 50.0);
+
+        let intersect = apply_boolean_operation(BooleanOperation::Intersect, operands());
+        assert_eq!(area(&intersect), 50.0);
+
+        let exclude = apply_boolean_operation(BooleanOperation::Exclude, operands());
+        assert_eq!(area(&exclude), 100.0);
+    }
+}
@@ -1,4 +1,6 @@
+pub mod boolean_operation;
 pub mod ellipse;
+pub mod path_overlay;
 pub mod polygon;
 pub mod rectangle;
 pub mod star;
@@ -0,0 +1,237 @@
+use dyn_arb_bundles::components::mixins::WindingRule;
+use i_overlay::{
+    core::{fill_rule::FillRule, overlay_rule::OverlayRule},
+    float::{overlay::FloatOverlay, single::SingleFloatOverlay},
+};
+use tiny_skia_path::{PathBuilder, PathSegment, Point};
+
+/// Maximum distance between a curve and the line segments it's flattened into.
+const FLATTEN_TOLERANCE: f32 = 0.05;
+/// Upper bound of line segments per curve, reached only by huge curves.
+const MAX_FLATTEN_SEGMENTS: f32 = 1024.0;
+
+pub type Contour = Vec<[f32; 2]>;
+pub type Shapes = Vec<Vec<Contour>>;
+
+/// Resolves the winding rule of the contours into shapes with consistently oriented contours,
+/// so that they can be combined with other shapes using a single fill rule.
+pub fn normalize_contours(contours: &[Contour], winding_rule: WindingRule) -> Shapes {
+    if contours.is_empty() {
+        return Vec::new();
+    }
+
+    return FloatOverlay::with_subj(contours)
+        .overlay(OverlayRule::Subject, to_fill_rule(winding_rule));
+}
+
+/// Combines the subject with the clip path using the overlay rule.
+pub fn overlay_paths(
+    subject: (&tiny_skia_path::Path, WindingRule),
+    clip: (&tiny_skia_path::Path, WindingRule),
+    overlay_rule: OverlayRule,
+) -> Option<tiny_skia_path::Path> {
+    let subject_shapes = normalize_contours(&flatten_path(subject.0), subject.1);
+    let clip_shapes = normalize_contours(&flatten_path(clip.0), clip.1);
+
+    let shapes = if clip_shapes.is_empty() {
+        match overlay_rule {
+            OverlayRule::Intersect => Vec::new(),
+            _ => subject_shapes,
+        }
+    } else if subject_shapes.is_empty() {
+        Vec::new()
+    } else {
+        subject_shapes.overlay(&clip_shapes, overlay_rule, FillRule::NonZero)
+    };
+
+    return shapes_to_path(&shapes);
+}
+
+pub fn to_fill_rule(winding_rule: WindingRule) -> FillRule {
+    match winding_rule {
+        WindingRule::Nonzero => FillRule::NonZero,
+        WindingRule::Evenodd => FillRule::EvenOdd,
+    }
+}
+
+/// Flattens the path into closed polygonal contours.
+pub fn flatten_path(path: &tiny_skia_path::Path) -> Vec<Contour> {
+    let mut contours: Vec<Contour> = Vec::new();
+    let mut contour: Contour = Vec::new();
+    let mut last_point = Point::zero();
+
+    let mut finish_contour = |contour: &mut Contour| {
+        // Drop the closing point as contours are implicitly closed
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if contour.len() >= 3 {
+            contours.push(std::mem::take(contour));
+        } else {
+            contour.clear();
+        }
+    };
+
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                finish_contour(&mut contour);
+                contour.push([p.x, p.y]);
+                last_point = p;
+            }
+            PathSegment::LineTo(p) => {
+                push_point(&mut contour, [p.x, p.y]);
+                last_point = p;
+            }
+            PathSegment::QuadTo(p1, p2) => {
+                // Wang's formula for quadratic curves
+                let steps = flatten_steps(0.25 * second_difference(last_point, p1, p2));
+                for step in 1..=steps {
+                    let t = step as f32 / steps as f32;
+                    let mt = 1.0 - t;
+                    push_point(
+                        &mut contour,
+                        [
+                            mt * mt * last_point.x + 2.0 * mt * t * p1.x + t * t * p2.x,
+                            mt * mt * last_point.y + 2.0 * mt * t * p1.y + t * t * p2.y,
+                        ],
+                    );
+                }
+                last_point = p2;
+            }
+            PathSegment::CubicTo(p1, p2, p3) => {
+                // Wang's formula for cubic curves
+                let steps = flatten_steps(
+                    0.75 * second_difference(last_point, p1, p2).max(second_difference(p1, p2, p3)),
+                );
+                for step in 1..=steps {
+                    let t = step as f32 / steps as f32;
+                    let mt = 1.0 - t;
+                    let (a, b, c, d) =
+                        (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+                    push_point(
+                        &mut contour,
+                        [
+                            a * last_point.x + b * p1.x + c * p2.x + d * p3.x,
+                            a * last_point.y + b * p1.y + c * p2.y + d * p3.y,
+                        ],
+                    );
+                }
+                last_point = p3;
+            }
+            PathSegment::Close => {
+                finish_contour(&mut contour);
+            }
+        }
+    }
+    finish_contour(&mut contour);
+
+    return contours;
+}
+
+/// Pushes the point unless it duplicates the previous one.
+fn push_point(contour: &mut Contour, point: [f32; 2]) {
+    if contour.last() != Some(&point) {
+        contour.push(point);
+    }
+}
+
+/// Length of the second difference of the control points,
+/// which bounds the curvature of the curve.
+fn second_difference(p0: Point, p1: Point, p2: Point) -> f32 {
+    let x = p0.x - 2.0 * p1.x + p2.x;
+    let y = p0.y - 2.0 * p1.y + p2.y;
+    return (x * x + y * y).sqrt();
+}
+
+/// Number of line segments needed to stay within the tolerance,
+/// given the scaled second difference of the curve.
+fn flatten_steps(scaled_second_difference: f32) -> usize {
+    (scaled_second_difference / FLATTEN_TOLERANCE)
+        .sqrt()
+        .ceil()
+        .clamp(1.0, MAX_FLATTEN_SEGMENTS) as usize
+}
+
+pub fn shapes_to_path(shapes: &Shapes) -> Option<tiny_skia_path::Path> {
+    let mut path_builder = PathBuilder::new();
+
+    for contour in shapes.iter().flatten() {
+        for (index, [x, y]) in contour.iter().enumerate() {
+            if index == 0 {
+                path_builder.move_to(*x, *y);
+            } else {
+                path_builder.line_to(*x, *y);
+            }
+        }
+        path_builder.close();
+    }
+
+    return path_builder.finish();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten_path() {
+        let mut path_builder = PathBuilder::new();
+        path_builder.move_to(0.0, 0.0);
+        path_builder.quad_to(5.0, 10.0, 10.0, 0.0);
+        path_builder.close();
+        let contours = flatten_path(&path_builder.finish().unwrap());
+
+        assert_eq!(contours.len(), 1);
+        assert!(contours[0].len() > 3);
+        assert_eq!(contours[0].last(), Some(&[10.0, 0.0]));
+
+        let mut path_builder = PathBuilder::new();
+        path_builder.move_to(0.0, 0.0);
+        path_builder.line_to(10.0, 0.0);
+        path_builder.line_to(10.0, 10.0);
+        path_builder.line_to(0.0, 0.0);
+        path_builder.close();
+        let contours = flatten_path(&path_builder.finish().unwrap());
+
+        assert_eq!(contours, vec![vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]]]);
+    }
+
+    #[test]
+    fn test_flatten_path_within_tolerance() {
+        for scale in [0.01, 1.0, 10.0] {
+            let (p1, p2) = ([50.0 * scale, 100.0 * scale], [100.0 * scale, 0.0]);
+            let mut path_builder = PathBuilder::new();
+            path_builder.move_to(0.0, 0.0);
+            path_builder.quad_to(p1[0], p1[1], p2[0], p2[1]);
+            path_builder.close();
+            let contours = flatten_path(&path_builder.finish().unwrap());
+            assert_eq!(contours.len(), 1);
+
+            // Curve points are equally spaced in t,
+            // so the furthest point of the curve from a segment lies at its middle t
+            let contour = &contours[0];
+            let steps = contour.len() - 1;
+            for index in 0..steps {
+                let t = (index as f32 + 0.5) / steps as f32;
+                let mt = 1.0 - t;
+                let curve_x = 2.0 * mt * t * p1[0] + t * t * p2[0];
+                let curve_y = 2.0 * mt * t * p1[1] + t * t * p2[1];
+                let [x0, y0] = contour[index];
+                let [x1, y1] = contour[index + 1];
+                let deviation = (curve_x - (x0 + x1) / 2.0).hypot(curve_y - (y0 + y1) / 2.0);
+                assert!(
+                    deviation <= FLATTEN_TOLERANCE + 0.001,
+                    "Segment deviates by {} from the curve scaled by {}",
+                    deviation,
+                    scale
+                );
+            }
+
+            // Small curves aren't flattened into more segments than needed
+            if scale == 0.01 {
+                assert!(steps <= 4);
+            }
+        }
+    }
+}
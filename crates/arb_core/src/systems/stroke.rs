@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use crate::systems::outline::path_overlay::overlay_paths;
 use bevy_ecs::{
     entity::Entity,
     query::Changed,
@@ -7,9 +8,10 @@ use bevy_ecs::{
 };
 use dyn_arb_bundles::components::{
     mixins::{PathMixin, StrokePathMixin, StyleChildrenMixin, StyleParentMixin, WindingRule},
-    styles::StrokeArbStyle,
+    styles::{StrokeAlignment, StrokeArbStyle},
 };
-use tiny_skia_path::PathStroker;
+use i_overlay::core::overlay_rule::OverlayRule;
+use tiny_skia_path::{PathSegment, PathStroker};
 
 pub fn stroke_path_system(
     mut commands: Commands,
@@ -19,6 +21,7 @@ pub fn stroke_path_system(
     >,
     path_query: Query<(&PathMixin, Option<&StyleChildrenMixin>), Changed<PathMixin>>,
     stroke_style_query: Query<&StrokeArbStyle>,
+    parent_path_query: Query<&PathMixin>,
 ) {
     let mut processed_entities: HashSet<Entity> = HashSet::new();
 
@@ -26,20 +29,20 @@ pub fn stroke_path_system(
     for (entity, stroke_style, maybe_style_parent_mixin) in stroke_query.iter() {
         if processed_entities.insert(entity) {
             if let Some(StyleParentMixin(parent_entity)) = maybe_style_parent_mixin {
-                if let Ok((PathMixin { path, .. }, _)) = path_query.get(*parent_entity) {
-                    stroke_path(&mut commands, entity, path, stroke_style);
+                if let Ok(path_mixin) = parent_path_query.get(*parent_entity) {
+                    stroke_path(&mut commands, entity, path_mixin, stroke_style);
                 }
             }
         }
     }
 
     // Handle path changes
-    for (PathMixin { path, .. }, maybe_style_children_mixin) in path_query.iter() {
+    for (path_mixin, maybe_style_children_mixin) in path_query.iter() {
         if let Some(StyleChildrenMixin(style_entities)) = maybe_style_children_mixin {
             for style_entity in style_entities.iter() {
                 if processed_entities.insert(*style_entity) {
                     if let Ok(stroke_style) = stroke_style_query.get(*style_entity) {
-                        stroke_path(&mut commands, *style_entity, path, stroke_style);
+                        stroke_path(&mut commands, *style_entity, path_mixin, stroke_style);
                     }
                 }
             }
@@ -50,14 +53,259 @@ pub fn stroke_path_system(
 fn stroke_path(
     commands: &mut Commands,
     entity: Entity,
-    path: &tiny_skia_path::Path,
+    path_mixin: &PathMixin,
     stroke_style: &StrokeArbStyle,
 ) {
-    let mut stroker = PathStroker::new();
-    if let Some(stroke_path) = stroker.stroke(path, &stroke_style.stroke, 1.0) {
+    if let Some(stroke_path) = build_stroke_path(path_mixin, stroke_style) {
         commands.entity(entity).insert(StrokePathMixin {
             path: stroke_path,
             winding_rule: WindingRule::Nonzero,
         });
     }
 }
+
+/// Builds the outline of the stroke along the path.
+fn build_stroke_path(
+    path_mixin: &PathMixin,
+    stroke_style: &StrokeArbStyle,
+) -> Option<tiny_skia_path::Path> {
+    let PathMixin { path, winding_rule } = path_mixin;
+
+    // Dash the path first as the stroker ignores the stroke's dash pattern
+    let maybe_dash = stroke_style.get_dash();
+    if maybe_dash.is_none() && !stroke_style.dash_array.is_empty() {
+        log::warn!(
+            "Invalid dash array {:?} is drawn as solid stroke!",
+            stroke_style.dash_array
+        );
+    }
+    let maybe_dashed_path = maybe_dash.and_then(|dash| path.dash(&dash, 1.0));
+    let dashed_path = maybe_dashed_path.as_ref().unwrap_or(path);
+
+    // Inside and outside strokes are centered strokes of double the width
+    // clipped against the filled area of the shape
+    let maybe_overlay_rule = match stroke_style.alignment {
+        StrokeAlignment::Center => None,
+        // Open paths enclose no area the stroke could be aligned to
+        _ if !has_closed_contour(path) => None,
+        StrokeAlignment::Inside => Some(OverlayRule::Intersect),
+        StrokeAlignment::Outside => Some(OverlayRule::Difference),
+    };
+
+    let mut stroker = PathStroker::new();
+    if let Some(overlay_rule) = maybe_overlay_rule {
+        let mut stroke = stroke_style.stroke.clone();
+        stroke.width *= 2.0;
+        let maybe_aligned_stroke_path =
+            stroker
+                .stroke(dashed_path, &stroke, 1.0)
+                .and_then(|stroke_path| {
+                    overlay_paths(
+                        (&stroke_path, WindingRule::Nonzero),
+                        (path, *winding_rule),
+                        overlay_rule,
+                    )
+                });
+        if maybe_aligned_stroke_path.is_some() {
+            return maybe_aligned_stroke_path;
+        }
+        log::warn!(
+            "Failed to align stroke {:?}, it's drawn centered instead!",
+            stroke_style.alignment
+        );
+    }
+
+    return stroker.stroke(dashed_path, &stroke_style.stroke, 1.0);
+}
+
+fn has_closed_contour(path: &tiny_skia_path::Path) -> bool {
+    path.segments()
+        .any(|segment| matches!(segment, PathSegment::Close))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::outline::path_overlay::flatten_path;
+    use dyn_arb_bundles::mapper::string_to_tiny_skia_path;
+    use tiny_skia_path::{LineCap, LineJoin, PathSegment, Rect, Stroke};
+
+    fn build(path: &str, stroke_style: &StrokeArbStyle) -> tiny_skia_path::Path {
+        let path_mixin = PathMixin {
+            path: string_to_tiny_skia_path(path).unwrap(),
+            winding_rule: WindingRule::Nonzero,
+        };
+        return build_stroke_path(&path_mixin, stroke_style).unwrap();
+    }
+
+    fn stroke_style(width: f32) -> StrokeArbStyle {
+        StrokeArbStyle {
+            stroke: Stroke {
+                width,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn count_contours(path: &tiny_skia_path::Path) -> usize {
+        path.segments()
+            .filter(|segment| matches!(segment, PathSegment::MoveTo(_)))
+            .count()
+    }
+
+    fn assert_bounds_near(bounds: Rect, expected: (f32, f32, f32, f32)) {
+        let actual = (bounds.left(), bounds.top(), bounds.right(), bounds.bottom());
+        assert!(
+            (actual.0 - expected.0).abs() < 0.01
+                && (actual.1 - expected.1).abs() < 0.01
+                && (actual.2 - expected.2).abs() < 0.01
+                && (actual.3 - expected.3).abs() < 0.01,
+            "Expected bounds {:?} but got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_stroke_caps() {
+        let mut style = stroke_style(2.0);
+
+        style.stroke.line_cap = LineCap::Butt;
+        assert_bounds_near(build("M0 0 L10 0", &style).bounds(), (0.0, -1.0, 10.0, 1.0));
+
+        style.stroke.line_cap = LineCap::Square;
+        assert_bounds_near(
+            build("M0 0 L10 0", &style).bounds(),
+            (-1.0, -1.0, 11.0, 1.0),
+        );
+
+        style.stroke.line_cap = LineCap::Round;
+        assert_bounds_near(
+            build("M0 0 L10 0", &style).bounds(),
+            (-1.0, -1.0, 11.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn test_stroke_joins_and_miter_limit() {
+        // Sharp corner at (10, 0) whose miter is about 10 times the stroke width long
+        let path = "M0 0 L10 0 L0 2";
+        let mut style = stroke_style(2.0);
+
+        style.stroke.line_join = LineJoin::Bevel;
+        let bevel_bounds = build(path, &style).bounds();
+        assert!(bevel_bounds.right() < 11.0);
+
+        style.stroke.line_join = LineJoin::Round;
+        assert!((build(path, &style).bounds().right() - 11.0).abs() < 0.01);
+
+        // Miter exceeds the default limit (4) and is beveled
+        style.stroke.line_join = LineJoin::Miter;
+        assert_eq!(build(path, &style).bounds(), bevel_bounds);
+
+        style.stroke.miter_limit = 20.0;
+        assert!(build(path, &style).bounds().right() > 19.0);
+    }
+
+    #[test]
+    fn test_stroke_dashing() {
+        let mut style = stroke_style(2.0);
+
+        style.dash_array = vec![2.0, 2.0];
+        let stroke_path = build("M0 0 L10 0", &style);
+        assert_eq!(count_contours(&stroke_path), 3);
+        assert_bounds_near(stroke_path.bounds(), (0.0, -1.0, 10.0, 1.0));
+
+        style.dash_offset = 1.0;
+        let stroke_path = build("M0 0 L10 0", &style);
+        assert_eq!(count_contours(&stroke_path), 3);
+        assert_bounds_near(stroke_path.bounds(), (0.0, -1.0, 9.0, 1.0));
+
+        // Odd dash arrays are repeated
+        style.dash_array = vec![2.0];
+        style.dash_offset = 0.0;
+        assert_eq!(count_contours(&build("M0 0 L10 0", &style)), 3);
+
+        // Invalid dash arrays are drawn as solid stroke
+        for dash_array in [vec![2.0, -2.0], vec![0.0, 0.0]] {
+            style.dash_array = dash_array;
+            let stroke_path = build("M0 0 L10 0", &style);
+            assert_eq!(count_contours(&stroke_path), 1);
+            assert_bounds_near(stroke_path.bounds(), (0.0, -1.0, 10.0, 1.0));
+        }
+    }
+
+    /// Area enclosed by the polygonal contours, with holes subtracted
+    /// (requires consistently oriented contours like the result of an overlay).
+    fn area(path: &tiny_skia_path::Path) -> f32 {
+        let signed_area: f32 = flatten_path(path)
+            .iter()
+            .map(|contour| {
+                let mut area = 0.0;
+                for (index, [x0, y0]) in contour.iter().enumerate() {
+                    let [x1, y1] = contour[(index + 1) % contour.len()];
+                    area += x0 * y1 - x1 * y0;
+                }
+                area / 2.0
+            })
+            .sum();
+        return signed_area.abs();
+    }
+
+    #[test]
+    fn test_stroke_alignment() {
+        let square = "M0 0 L10 0 L10 10 L0 10 Z";
+        let mut style = stroke_style(2.0);
+
+        style.alignment = StrokeAlignment::Center;
+        let stroke_path = build(square, &style);
+        assert_bounds_near(stroke_path.bounds(), (-1.0, -1.0, 11.0, 11.0));
+
+        style.alignment = StrokeAlignment::Inside;
+        let stroke_path = build(square, &style);
+        assert_bounds_near(stroke_path.bounds(), (0.0, 0.0, 10.0, 10.0));
+        assert!((area(&stroke_path) - (100.0 - 36.0)).abs() < 0.01);
+
+        style.alignment = StrokeAlignment::Outside;
+        let stroke_path = build(square, &style);
+        assert_bounds_near(stroke_path.bounds(), (-2.0, -2.0, 12.0, 12.0));
+        assert!((area(&stroke_path) - (196.0 - 100.0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_stroke_alignment_of_curves() {
+        let circle = PathMixin {
+            path: tiny_skia_path::PathBuilder::from_circle(0.0, 0.0, 100.0).unwrap(),
+            winding_rule: WindingRule::Nonzero,
+        };
+        let mut style = stroke_style(4.0);
+        let ring_area =
+            |inner: f32, outer: f32| std::f32::consts::PI * (outer * outer - inner * inner);
+
+        style.alignment = StrokeAlignment::Inside;
+        let stroke_path = build_stroke_path(&circle, &style).unwrap();
+        assert_bounds_near(stroke_path.bounds(), (-100.0, -100.0, 100.0, 100.0));
+        assert!((area(&stroke_path) / ring_area(96.0, 100.0) - 1.0).abs() < 0.001);
+
+        style.alignment = StrokeAlignment::Outside;
+        let stroke_path = build_stroke_path(&circle, &style).unwrap();
+        assert_bounds_near(stroke_path.bounds(), (-104.0, -104.0, 104.0, 104.0));
+        assert!((area(&stroke_path) / ring_area(100.0, 104.0) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_stroke_alignment_of_open_paths() {
+        let mut style = stroke_style(2.0);
+
+        // Open paths enclose no area and are stroked centered for any alignment
+        for alignment in [StrokeAlignment::Inside, StrokeAlignment::Outside] {
+            style.alignment = alignment;
+            let stroke_path = build("M0 0 L10 0", &style);
+            assert_bounds_near(stroke_path.bounds(), (0.0, -1.0, 10.0, 1.0));
+
+            let stroke_path = build("M0 0 L10 0 L10 10", &style);
+            assert_bounds_near(stroke_path.bounds(), (0.0, -1.0, 11.0, 10.0));
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::resources::variables::VariablesRes;
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    entity::Entity,
+    system::{Query, Res},
+    world::Ref,
+};
+use dyn_arb_bundles::{
+    components::{
+        mixins::{OpacityMixin, SizeMixin, VariableBindingsMixin},
+        nodes::TextArbNode,
+        paints::SolidArbPaint,
+        styles::{DropShadowArbStyle, StrokeArbStyle},
+    },
+    properties::{VariableProperty, VariableValue},
+};
+use dyn_attributed_string::dyn_fonts_book::font::info::FontFamily;
+use dyn_utils::{properties::opacity::Opacity, units::abs::Abs};
+
+/// Writes the resolved variable values into the bound properties.
+///
+/// Runs for all bindings if a variable or the active mode has changed
+/// and otherwise only for bindings that have been added or changed.
+/// Properties are only written if their value differs,
+/// to not trigger change detection of dependent systems unnecessarily.
+pub fn apply_variable_bindings_system(
+    variables_res: Res<VariablesRes>,
+    bindings_query: Query<(Entity, Ref<VariableBindingsMixin>)>,
+    mut solid_paint_query: Query<&mut SolidArbPaint>,
+    mut drop_shadow_style_query: Query<&mut DropShadowArbStyle>,
+    mut stroke_style_query: Query<&mut StrokeArbStyle>,
+    mut opacity_query: Query<&mut OpacityMixin>,
+    mut size_query: Query<&mut SizeMixin>,
+    mut text_query: Query<&mut TextArbNode>,
+) {
+    let variables_changed = variables_res.is_changed();
+
+    for (entity, bindings_mixin) in bindings_query.iter() {
+        if !variables_changed && !bindings_mixin.is_changed() {
+            continue;
+        }
+
+        for (property, variable_id) in bindings_mixin.0.iter() {
+            let value = match variables_res.resolve(variable_id) {
+                Some(value) => value,
+                None => {
+                    log::warn!("Bound variable '{}' doesn't exist!", variable_id);
+                    continue;
+                }
+            };
+
+            let is_supported = match (property, value) {
+                (VariableProperty::Color, VariableValue::Color { value }) => {
+                    if let Ok(mut solid_paint) = solid_paint_query.get_mut(entity) {
+                        if solid_paint.color != *value {
+                            solid_paint.color = *value;
+                        }
+                        true
+                    } else if let Ok(mut drop_shadow_style) =
+                        drop_shadow_style_query.get_mut(entity)
+                    {
+                        if drop_shadow_style.color != *value {
+                            drop_shadow_style.color = *value;
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::Opacity, VariableValue::Number { value }) => {
+                    if let Ok(mut opacity_mixin) = opacity_query.get_mut(entity) {
+                        let opacity = Opacity::new(*value);
+                        if opacity_mixin.0 != opacity {
+                            opacity_mixin.0 = opacity;
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::Width, VariableValue::Number { value }) => {
+                    if let Ok(mut size_mixin) = size_query.get_mut(entity) {
+                        let width = Abs::pt(*value);
+                        if size_mixin.0.width != width {
+                            size_mixin.0.width = width;
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::Height, VariableValue::Number { value }) => {
+                    if let Ok(mut size_mixin) = size_query.get_mut(entity) {
+                        let height = Abs::pt(*value);
+                        if size_mixin.0.height != height {
+                            size_mixin.0.height = height;
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::StrokeWidth, VariableValue::Number { value }) => {
+                    if let Ok(mut stroke_style) = stroke_style_query.get_mut(entity) {
+                        if stroke_style.stroke.width != *value {
+                            stroke_style.stroke.width = *value;
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::Text, VariableValue::String { value }) => {
+                    if let Ok(mut text) = text_query.get_mut(entity) {
+                        if text.text != *value {
+                            text.replace_text(value.clone());
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::FontSize, VariableValue::Number { value }) => {
+                    if let Ok(mut text) = text_query.get_mut(entity) {
+                        let font_size = Some(Abs::pt(*value));
+                        if text
+                            .attributes
+                            .iter()
+                            .any(|interval| interval.attributes.font_size != font_size)
+                        {
+                            for interval in text.attributes.iter_mut() {
+                                interval.attributes.font_size = font_size;
+                            }
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                (VariableProperty::FontFamily, VariableValue::FontFamily { .. })
+                | (VariableProperty::FontFamily, VariableValue::String { .. }) => {
+                    if let Ok(mut text) = text_query.get_mut(entity) {
+                        let font_family = Some(match value {
+                            VariableValue::String { value } => FontFamily::Named(value.clone()),
+                            VariableValue::FontFamily { value } => value.clone(),
+                            _ => continue,
+                        });
+                        if text
+                            .attributes
+                            .iter()
+                            .any(|interval| interval.attributes.font_family != font_family)
+                        {
+                            for interval in text.attributes.iter_mut() {
+                                interval.attributes.font_family = font_family.clone();
+                            }
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                _ => {
+                    log::warn!(
+                        "Variable '{}' can't be bound to {:?} because of its type!",
+                        variable_id,
+                        property
+                    );
+                    continue;
+                }
+            };
+
+            // The binding event accepts any target,
+            // thus targets without the bound property are only reported here
+            if !is_supported {
+                log::warn!(
+                    "Variable '{}' can't be bound to {:?} of entity {:?} because it doesn't support the property!",
+                    variable_id,
+                    property,
+                    entity
+                );
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_app, get_entity, reference_id_or_entity};
+    use bevy_app::App;
+    use dyn_arb_bundles::{
+        events::{
+            BindVariableInputEvent, CreateNodeInputEvent, CreatePaintInputEvent,
+            CreateVariableInputEvent, UpdateVariableInputEvent, UpdateVariableModeInputEvent,
+        },
+        properties::VariableProperty,
+        reference_id::ReferenceId,
+        Variable,
+    };
+    use dyn_utils::properties::color::Color;
+    use std::collections::BTreeMap;
+
+    fn bind(app: &mut App, target_id: &str, property: VariableProperty, variable_id: &str) {
+        app.world.send_event(BindVariableInputEvent {
+            target_id: reference_id_or_entity(target_id),
+            property,
+            variable_id: Some(ReferenceId::new(String::from(variable_id))),
+        });
+    }
+
+    #[test]
+    fn test_resolve_bindings_on_variable_update() {
+        let mut app = create_app(100.0, 100.0, false);
+
+        app.world.send_event(CreateNodeInputEvent {
+            node: serde_json::from_value(serde_json::json!({
+                "type": "Rectangle", "id": "rect", "size": [10, 10]
+            }))
+            .unwrap(),
+        });
+        app.world.send_event(CreatePaintInputEvent {
+            paint: serde_json::from_value(serde_json::json!({
+                "type": "Solid", "id": "paint", "color": [0, 0, 0]
+            }))
+            .unwrap(),
+        });
+        app.world.send_event(CreateVariableInputEvent {
+            variable: Variable {
+                id: ReferenceId::new(String::from("primary")),
+                name: None,
+                value: VariableValue::Color {
+                    value: Color::new_rgb(255, 0, 0),
+                },
+                modes: BTreeMap::from([(
+                    String::from("dark"),
+                    VariableValue::Color {
+                        value: Color::new_rgb(0, 0, 255),
+                    },
+                )]),
+            },
+        });
+        app.world.send_event(CreateVariableInputEvent {
+            variable: Variable {
+                id: ReferenceId::new(String::from("width")),
+                name: None,
+                value: VariableValue::Number { value: 20.0 },
+                modes: BTreeMap::new(),
+            },
+        });
+        app.update();
+        bind(&mut app, "paint", VariableProperty::Color, "primary");
+        bind(&mut app, "rect", VariableProperty::Width, "width");
+        app.update();
+
+        let paint = get_entity(&app, "paint");
+        let rect = get_entity(&app, "rect");
+        assert_eq!(
+            app.world.get::<SolidArbPaint>(paint).unwrap().color,
+            Color::new_rgb(255, 0, 0)
+        );
+        assert_eq!(
+            app.world.get::<SizeMixin>(rect).unwrap().0.width,
+            Abs::pt(20.0)
+        );
+
+        // Update the value of the variables
+        app.world.send_event(UpdateVariableInputEvent {
+            id: ReferenceId::new(String::from("primary")),
+            value: Some(VariableValue::Color {
+                value: Color::new_rgb(0, 255, 0),
+            }),
+            modes: None,
+        });
+        app.world.send_event(UpdateVariableInputEvent {
+            id: ReferenceId::new(String::from("width")),
+            value: Some(VariableValue::Number { value: 40.0 }),
+            modes: None,
+        });
+        app.update();
+
+        assert_eq!(
+            app.world.get::<SolidArbPaint>(paint).unwrap().color,
+            Color::new_rgb(0, 255, 0)
+        );
+        assert_eq!(
+            app.world.get::<SizeMixin>(rect).unwrap().0.width,
+            Abs::pt(40.0)
+        );
+
+        // Switch to a mode only defined by one of the variables
+        app.world.send_event(UpdateVariableModeInputEvent {
+            mode: Some(String::from("dark")),
+        });
+        app.update();
+
+        assert_eq!(
+            app.world.get::<SolidArbPaint>(paint).unwrap().color,
+            Color::new_rgb(0, 0, 255)
+        );
+        assert_eq!(
+            app.world.get::<SizeMixin>(rect).unwrap().0.width,
+            Abs::pt(40.0)
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{resources::referencer::ReferencerRes, ArbCorePlugin};
+use bevy_app::App;
+use bevy_ecs::entity::Entity;
+use bevy_transform::components::Transform;
+use dyn_arb_bundles::{
+    components::mixins::SizeMixin,
+    reference_id::{ReferenceId, ReferenceIdOrEntity},
+};
+use dyn_utils::{properties::size::Size, units::abs::Abs};
+use glam::Vec2;
+
+/// Creates an app with the core plugin for an artboard of the given size.
+pub fn create_app(width: f32, height: f32, sub_pixel_layout: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins(ArbCorePlugin {
+        version: None,
+        size: Size::new(Abs::pt(width), Abs::pt(height)),
+        viewport: None,
+        sub_pixel_layout,
+    });
+    return app;
+}
+
+/// Reference to the element with the given id in DTIF JSON.
+pub fn reference_id(id: &str) -> serde_json::Value {
+    serde_json::json!({ "type": "ReferenceId", "referenceId": id })
+}
+
+/// Reference to the element with the given id in input events.
+pub fn reference_id_or_entity(id: &str) -> ReferenceIdOrEntity {
+    ReferenceIdOrEntity::reference_id(ReferenceId::new(String::from(id)))
+}
+
+pub fn find_entity(app: &App, id: &str) -> Option<Entity> {
+    app.world
+        .resource::<ReferencerRes>()
+        .get_reference_id_to_entity_map()
+        .get(&ReferenceId::new(String::from(id)))
+        .copied()
+}
+
+pub fn get_entity(app: &App, id: &str) -> Entity {
+    find_entity(app, id).unwrap()
+}
+
+/// Returns the translation and size of the node with the given id.
+pub fn get_layout(app: &App, id: &str) -> (Vec2, Vec2) {
+    let entity = get_entity(app, id);
+    return (
+        app.world
+            .get::<Transform>(entity)
+            .unwrap()
+            .translation
+            .truncate(),
+        app.world.get::<SizeMixin>(entity).unwrap().0.to_vec2(),
+    );
+}
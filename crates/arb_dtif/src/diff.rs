@@ -0,0 +1,1651 @@
+use crate::{validation::node_id_and_styles, DtifArtboard};
+use dyn_arb_bundles::{
+    components::mixins::{BlendMode, LayoutElement, StaticLayoutParent},
+    events::{
+        BindVariableInputEvent, CoreInputEvent, CreateAssetInputEvent, CreateNodeInputEvent,
+        CreatePaintInputEvent, CreateStyleInputEvent, CreateVariableInputEvent,
+        DeleteEntityInputEvent, DeleteStyleInputEvent, ReorderStylesInputEvent,
+        UpdateArtboardSizeInputEvent, UpdateArtboardViewportInputEvent,
+        UpdateBackgroundBlurStyleInputEvent, UpdateBooleanOperationNodeInputEvent,
+        UpdateDropShadowStyleInputEvent, UpdateEllipseNodeInputEvent,
+        UpdateEntityBlendModeInputEvent, UpdateEntityChildrenInputEvent,
+        UpdateEntityCornerRadiiInputEvent, UpdateEntityOpacityInputEvent,
+        UpdateEntitySizeInputEvent, UpdateEntityTransformInputEvent,
+        UpdateEntityVisibilityInputEvent, UpdateFillStyleInputEvent, UpdateFrameNodeInputEvent,
+        UpdateGradientPaintInputEvent, UpdateImagePaintInputEvent,
+        UpdateInnerShadowStyleInputEvent, UpdateInstanceNodeInputEvent,
+        UpdateLayerBlurStyleInputEvent, UpdatePolygonNodeInputEvent, UpdateSolidPaintInputEvent,
+        UpdateStarNodeInputEvent, UpdateStorkeStyleInputEvent, UpdateTextNodeInputEvent,
+        UpdateVariableInputEvent, UpdateVariableModeInputEvent,
+    },
+    reference_id::{ReferenceId, ReferenceIdOrEntity},
+    Node, Paint, Style,
+};
+use dyn_utils::{
+    properties::{corner_radii::CornerRadii, opacity::Opacity, size::Size},
+    units::angle::Angle,
+};
+use glam::Vec2;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Difference between two DTIF artboards.
+#[derive(Debug, Clone, Default)]
+pub struct DtifDiff {
+    /// Events that turn a world loaded from the `from` artboard
+    /// into one matching the `to` artboard.
+    pub events: Vec<CoreInputEvent>,
+    /// Human-readable changes (e.g. for reviewing template changes).
+    pub changes: Vec<DtifChange>,
+}
+
+impl DtifDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether some changes can't be expressed as events
+    /// and the artboard has to be reloaded to apply them.
+    pub fn requires_reload(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.kind == DtifChangeKind::Unsupported)
+    }
+}
+
+impl std::fmt::Display for DtifDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in self.changes.iter() {
+            writeln!(f, "{}", change)?;
+        }
+        return Ok(());
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DtifChange {
+    pub kind: DtifChangeKind,
+    /// The changed element (e.g. `node 'title'`).
+    pub element: String,
+    /// Human-readable description of the change (e.g. `text: "Hello" -> "World"`).
+    pub description: String,
+}
+
+impl std::fmt::Display for DtifChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            self.kind.as_str(),
+            self.element,
+            self.description
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DtifChangeKind {
+    Added,
+    Removed,
+    Updated,
+    /// The change can't be applied via events.
+    Unsupported,
+}
+
+impl DtifChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DtifChangeKind::Added => "+",
+            DtifChangeKind::Removed => "-",
+            DtifChangeKind::Updated => "~",
+            DtifChangeKind::Unsupported => "!",
+        }
+    }
+}
+
+/// Computes the events to turn the `from` artboard into the `to` artboard.
+///
+/// Elements are matched by their reference id,
+/// so elements without reference id can't be diffed and are reported as unsupported changes.
+pub fn diff(from: &DtifArtboard, to: &DtifArtboard) -> DtifDiff {
+    let mut differ = Differ::default();
+
+    differ.diff_artboard(from, to);
+    differ.diff_assets(from, to);
+    differ.diff_variables(from, to);
+    differ.diff_paints(from, to);
+    differ.diff_nodes(from, to);
+    differ.diff_variable_bindings(from, to);
+    #[cfg(feature = "lua_scripts")]
+    differ.diff_scripts(from, to);
+
+    return differ.diff;
+}
+
+#[derive(Default)]
+struct Differ {
+    diff: DtifDiff,
+}
+
+impl Differ {
+    fn push_change(&mut self, kind: DtifChangeKind, element: String, description: String) {
+        self.diff.changes.push(DtifChange {
+            kind,
+            element,
+            description,
+        });
+    }
+
+    fn push_update<T: Serialize>(&mut self, element: &str, property: &str, from: &T, to: &T) {
+        self.push_change(
+            DtifChangeKind::Updated,
+            element.to_string(),
+            format!("{}: {} -> {}", property, to_json(from), to_json(to)),
+        );
+    }
+
+    fn push_unsupported(&mut self, element: &str, description: String) {
+        self.push_change(
+            DtifChangeKind::Unsupported,
+            element.to_string(),
+            description,
+        );
+    }
+
+    /// Reports elements without reference id if they differ,
+    /// as they can't be matched between both artboards.
+    fn diff_elements_without_id<T: Serialize>(
+        &mut self,
+        element: &str,
+        from: Vec<&T>,
+        to: Vec<&T>,
+    ) {
+        if differs(&from, &to) {
+            self.push_unsupported(
+                element,
+                String::from("changed elements without reference id can't be matched"),
+            );
+        }
+    }
+
+    // =========================================================================
+    // Artboard
+    // =========================================================================
+
+    fn diff_artboard(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        if from.size != to.size {
+            self.push_update("artboard", "size", &from.size, &to.size);
+            self.diff.events.push(CoreInputEvent::UpdateArtboardSize(
+                UpdateArtboardSizeInputEvent { size: to.size },
+            ));
+        }
+
+        if differs(&from.viewport, &to.viewport) {
+            self.push_update("artboard", "viewport", &from.viewport, &to.viewport);
+            if let Some(viewport) = to.viewport {
+                self.diff
+                    .events
+                    .push(CoreInputEvent::UpdateArtboardViewport(
+                        UpdateArtboardViewportInputEvent { viewport },
+                    ));
+            } else {
+                self.push_unsupported(
+                    "artboard",
+                    String::from("viewport can't be reset to the default"),
+                );
+            }
+        }
+
+        // The rounding of the layout is only configured when the artboard is loaded
+        if from.sub_pixel_layout != to.sub_pixel_layout {
+            self.push_unsupported(
+                "artboard",
+                String::from("sub-pixel layout can't be toggled"),
+            );
+        }
+    }
+
+    // =========================================================================
+    // Asset
+    // =========================================================================
+
+    fn diff_assets(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        let from_assets = by_reference_id(from.assets.iter(), |asset| asset.id.as_ref());
+        let to_assets = by_reference_id(to.assets.iter(), |asset| asset.id.as_ref());
+
+        for (id, to_asset) in to_assets.matched.iter() {
+            let element = format!("asset '{}'", id);
+            match from_assets.get(id) {
+                Some(from_asset) => {
+                    if differs(from_asset, to_asset) {
+                        self.push_unsupported(&element, String::from("assets can't be replaced"));
+                    }
+                }
+                None => {
+                    self.push_change(DtifChangeKind::Added, element, String::from("asset"));
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::CreateAsset(CreateAssetInputEvent {
+                            asset: (*to_asset).clone(),
+                        }));
+                }
+            }
+        }
+
+        for (id, _) in from_assets.matched.iter() {
+            if to_assets.get(id).is_none() {
+                self.push_unsupported(
+                    &format!("asset '{}'", id),
+                    String::from("assets can't be removed"),
+                );
+            }
+        }
+
+        self.diff_elements_without_id("assets", from_assets.unmatched, to_assets.unmatched);
+    }
+
+    // =========================================================================
+    // Variable
+    // =========================================================================
+
+    fn diff_variables(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        let from_variables: HashMap<&ReferenceId, _> = from
+            .variables
+            .iter()
+            .map(|variable| (&variable.id, variable))
+            .collect();
+        let to_ids: HashSet<&ReferenceId> =
+            to.variables.iter().map(|variable| &variable.id).collect();
+
+        for to_variable in to.variables.iter() {
+            let element = format!("variable '{}'", to_variable.id);
+            let from_variable = match from_variables.get(&to_variable.id) {
+                Some(from_variable) => from_variable,
+                None => {
+                    self.push_change(
+                        DtifChangeKind::Added,
+                        element,
+                        format!("variable {}", to_json(&to_variable.value)),
+                    );
+                    self.diff.events.push(CoreInputEvent::CreateVariable(
+                        CreateVariableInputEvent {
+                            variable: to_variable.clone(),
+                        },
+                    ));
+                    continue;
+                }
+            };
+
+            let mut event = UpdateVariableInputEvent {
+                id: to_variable.id.clone(),
+                value: None,
+                modes: None,
+            };
+            if from_variable.value != to_variable.value {
+                self.push_update(&element, "value", &from_variable.value, &to_variable.value);
+                event.value = Some(to_variable.value.clone());
+            }
+            if from_variable.modes != to_variable.modes {
+                self.push_update(&element, "modes", &from_variable.modes, &to_variable.modes);
+                event.modes = Some(to_variable.modes.clone());
+            }
+            if from_variable.name != to_variable.name {
+                self.push_unsupported(&element, String::from("variables can't be renamed"));
+            }
+            if event.value.is_some() || event.modes.is_some() {
+                self.diff.events.push(CoreInputEvent::UpdateVariable(event));
+            }
+        }
+
+        for from_variable in from.variables.iter() {
+            if !to_ids.contains(&from_variable.id) {
+                self.push_unsupported(
+                    &format!("variable '{}'", from_variable.id),
+                    String::from("variables can't be removed"),
+                );
+            }
+        }
+
+        if from.variable_mode != to.variable_mode {
+            self.push_update(
+                "artboard",
+                "variableMode",
+                &from.variable_mode,
+                &to.variable_mode,
+            );
+            self.diff.events.push(CoreInputEvent::UpdateVariableMode(
+                UpdateVariableModeInputEvent {
+                    mode: to.variable_mode.clone(),
+                },
+            ));
+        }
+    }
+
+    fn diff_variable_bindings(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        let binding_key =
+            |target_id: &ReferenceIdOrEntity, property| (to_json(target_id), property);
+        let from_bindings: HashMap<_, _> = from
+            .variable_bindings
+            .iter()
+            .map(|binding| (binding_key(&binding.target_id, binding.property), binding))
+            .collect();
+        let to_bindings: HashMap<_, _> = to
+            .variable_bindings
+            .iter()
+            .map(|binding| (binding_key(&binding.target_id, binding.property), binding))
+            .collect();
+
+        for to_binding in to.variable_bindings.iter() {
+            let key = binding_key(&to_binding.target_id, to_binding.property);
+            let element = format!("binding {} {:?}", key.0, to_binding.property);
+            match from_bindings.get(&key) {
+                Some(from_binding) if from_binding.variable_id == to_binding.variable_id => {
+                    continue;
+                }
+                Some(from_binding) => self.push_update(
+                    &element,
+                    "variableId",
+                    &from_binding.variable_id,
+                    &to_binding.variable_id,
+                ),
+                None => self.push_change(
+                    DtifChangeKind::Added,
+                    element,
+                    format!("bound to variable '{}'", to_binding.variable_id),
+                ),
+            }
+            self.diff
+                .events
+                .push(CoreInputEvent::BindVariable(BindVariableInputEvent {
+                    target_id: to_binding.target_id.clone(),
+                    property: to_binding.property,
+                    variable_id: Some(to_binding.variable_id.clone()),
+                }));
+        }
+
+        for from_binding in from.variable_bindings.iter() {
+            let key = binding_key(&from_binding.target_id, from_binding.property);
+            if to_bindings.contains_key(&key) {
+                continue;
+            }
+            self.push_change(
+                DtifChangeKind::Removed,
+                format!("binding {} {:?}", key.0, from_binding.property),
+                format!("unbound from variable '{}'", from_binding.variable_id),
+            );
+            self.diff
+                .events
+                .push(CoreInputEvent::BindVariable(BindVariableInputEvent {
+                    target_id: from_binding.target_id.clone(),
+                    property: from_binding.property,
+                    variable_id: None,
+                }));
+        }
+    }
+
+    // =========================================================================
+    // Paint
+    // =========================================================================
+
+    fn diff_paints(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        let from_paints = by_reference_id(from.paints.iter(), paint_id);
+        let to_paints = by_reference_id(to.paints.iter(), paint_id);
+
+        for (id, to_paint) in to_paints.matched.iter() {
+            let element = format!("paint '{}'", id);
+            match from_paints.get(id) {
+                Some(from_paint) => self.diff_paint(id, &element, from_paint, to_paint),
+                None => {
+                    self.push_change(DtifChangeKind::Added, element, String::from("paint"));
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::CreatePaint(CreatePaintInputEvent {
+                            paint: (*to_paint).clone(),
+                        }));
+                }
+            }
+        }
+
+        for (id, _) in from_paints.matched.iter() {
+            if to_paints.get(id).is_none() {
+                self.push_change(
+                    DtifChangeKind::Removed,
+                    format!("paint '{}'", id),
+                    String::from("paint"),
+                );
+                self.diff
+                    .events
+                    .push(CoreInputEvent::DeleteEntity(DeleteEntityInputEvent {
+                        id: to_target_id(id),
+                    }));
+            }
+        }
+
+        self.diff_elements_without_id("paints", from_paints.unmatched, to_paints.unmatched);
+    }
+
+    fn diff_paint(&mut self, id: &ReferenceId, element: &str, from: &Paint, to: &Paint) {
+        match (from, to) {
+            (Paint::Solid(from), Paint::Solid(to)) => {
+                if from.color != to.color {
+                    self.push_update(element, "color", &from.color, &to.color);
+                    self.diff.events.push(CoreInputEvent::UpdateSolidPaint(
+                        UpdateSolidPaintInputEvent {
+                            id: to_target_id(id),
+                            color: to.color,
+                        },
+                    ));
+                }
+            }
+            (Paint::Image(from), Paint::Image(to)) => {
+                if differs(&from.image_id, &to.image_id) {
+                    // The event expects the id of the loaded image and not its reference id
+                    self.push_unsupported(element, String::from("image can't be replaced"));
+                }
+                let mut event = UpdateImagePaintInputEvent {
+                    id: to_target_id(id),
+                    scale_mode: None,
+                    image_id: None,
+                    filters: None,
+                };
+                if differs(&from.scale_mode, &to.scale_mode) {
+                    self.push_update(element, "scaleMode", &from.scale_mode, &to.scale_mode);
+                    event.scale_mode = Some(to.scale_mode);
+                }
+                if from.filters.unwrap_or_default() != to.filters.unwrap_or_default() {
+                    self.push_update(element, "filters", &from.filters, &to.filters);
+                    event.filters = Some(to.filters.unwrap_or_default());
+                }
+                if event.scale_mode.is_some() || event.filters.is_some() {
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::UpdateImagePaint(event));
+                }
+            }
+            (Paint::Gradient(from), Paint::Gradient(to)) => {
+                let mut event = UpdateGradientPaintInputEvent {
+                    id: to_target_id(id),
+                    variant: None,
+                    stops: None,
+                };
+                if differs(&from.variant, &to.variant) {
+                    self.push_update(element, "variant", &from.variant, &to.variant);
+                    event.variant = Some(to.variant);
+                }
+                if differs(&from.stops, &to.stops) {
+                    self.push_update(element, "stops", &from.stops, &to.stops);
+                    event.stops = Some(to.stops.clone());
+                }
+                if event.variant.is_some() || event.stops.is_some() {
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::UpdateGradientPaint(event));
+                }
+            }
+            _ => self.push_unsupported(element, String::from("paint type changed")),
+        }
+    }
+
+    // =========================================================================
+    // Node
+    // =========================================================================
+
+    fn diff_nodes(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        let from_nodes = by_reference_id(from.nodes.iter(), |node| {
+            node_id_and_styles(node).0.as_ref()
+        });
+        let to_nodes = by_reference_id(to.nodes.iter(), |node| node_id_and_styles(node).0.as_ref());
+        let from_parents = collect_parents(&from.nodes);
+
+        // Nodes can only be deleted together with their children
+        let is_removed = |id: &ReferenceId| to_nodes.get(id).is_none();
+        let has_removed_ancestor = |id: &ReferenceId| {
+            let mut current = id;
+            while let Some(parent) = from_parents.get(current) {
+                if is_removed(parent) {
+                    return true;
+                }
+                current = parent;
+            }
+            return false;
+        };
+
+        for (id, to_node) in to_nodes.matched.iter() {
+            let element = format!("node '{}'", id);
+            match from_nodes.get(id) {
+                Some(from_node) => {
+                    if has_removed_ancestor(id) {
+                        self.push_unsupported(
+                            &element,
+                            String::from("nodes can't be moved out of a removed node"),
+                        );
+                    }
+                    self.diff_node(id, &element, from_node, to_node);
+                }
+                None => {
+                    self.push_change(
+                        DtifChangeKind::Added,
+                        element,
+                        String::from(node_type_name(to_node)),
+                    );
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::CreateNode(CreateNodeInputEvent {
+                            node: (*to_node).clone(),
+                        }));
+                }
+            }
+        }
+
+        for (id, from_node) in from_nodes.matched.iter() {
+            if !is_removed(id) {
+                continue;
+            }
+            self.push_change(
+                DtifChangeKind::Removed,
+                format!("node '{}'", id),
+                String::from(node_type_name(from_node)),
+            );
+            if !has_removed_ancestor(id) {
+                self.diff
+                    .events
+                    .push(CoreInputEvent::DeleteEntity(DeleteEntityInputEvent {
+                        id: to_target_id(id),
+                    }));
+            }
+        }
+
+        self.diff_elements_without_id("nodes", from_nodes.unmatched, to_nodes.unmatched);
+    }
+
+    fn diff_node(&mut self, id: &ReferenceId, element: &str, from: &Node, to: &Node) {
+        let from_properties = NodeProperties::from_node(from);
+        let to_properties = NodeProperties::from_node(to);
+        let target_id = to_target_id(id);
+
+        if std::mem::discriminant(from) != std::mem::discriminant(to) {
+            self.push_unsupported(
+                element,
+                format!(
+                    "node type changed from {} to {}",
+                    node_type_name(from),
+                    node_type_name(to)
+                ),
+            );
+            return;
+        }
+
+        // Transform
+        let mut transform_event = UpdateEntityTransformInputEvent {
+            id: target_id.clone(),
+            x: None,
+            y: None,
+            rotation_deg: None,
+        };
+        if let (Some(from_translation), Some(to_translation)) =
+            (from_properties.translation, to_properties.translation)
+        {
+            if from_translation != to_translation {
+                self.push_update(element, "translation", &from_translation, &to_translation);
+                if from_translation.x != to_translation.x {
+                    transform_event.x = Some(to_translation.x);
+                }
+                if from_translation.y != to_translation.y {
+                    transform_event.y = Some(to_translation.y);
+                }
+            }
+        }
+        if let (Some(from_rotation), Some(to_rotation)) =
+            (from_properties.rotation_deg, to_properties.rotation_deg)
+        {
+            if from_rotation != to_rotation {
+                self.push_update(element, "rotationDeg", &from_rotation, &to_rotation);
+                transform_event.rotation_deg = Some(to_rotation);
+            }
+        }
+        if transform_event.x.is_some()
+            || transform_event.y.is_some()
+            || transform_event.rotation_deg.is_some()
+        {
+            self.diff
+                .events
+                .push(CoreInputEvent::UpdateEntityTransform(transform_event));
+        }
+
+        // Size
+        if from_properties.size != to_properties.size {
+            self.push_update(element, "size", &from_properties.size, &to_properties.size);
+            self.diff.events.push(CoreInputEvent::UpdateEntitySize(
+                UpdateEntitySizeInputEvent {
+                    id: target_id.clone(),
+                    size: to_properties.size,
+                },
+            ));
+        }
+
+        // Corner radii
+        if let (Some(from_corner_radii), Some(to_corner_radii)) =
+            (from_properties.corner_radii, to_properties.corner_radii)
+        {
+            if from_corner_radii != to_corner_radii {
+                self.push_update(element, "cornerRadii", &from_corner_radii, &to_corner_radii);
+                self.diff
+                    .events
+                    .push(CoreInputEvent::UpdateEntityCornerRadii(
+                        UpdateEntityCornerRadiiInputEvent {
+                            id: target_id.clone(),
+                            corner_radii: to_corner_radii,
+                        },
+                    ));
+            }
+        }
+
+        // Visibility, blend mode & opacity
+        self.diff_mixins(
+            element,
+            &target_id,
+            (
+                from_properties.visible,
+                from_properties.blend_mode,
+                from_properties.opacity,
+            ),
+            (
+                to_properties.visible,
+                to_properties.blend_mode,
+                to_properties.opacity,
+            ),
+        );
+
+        // Layout
+        if differs(
+            &from_properties.layout_element,
+            &to_properties.layout_element,
+        ) {
+            self.push_unsupported(element, String::from("layoutElement can't be updated"));
+        }
+        if differs(&from_properties.layout_parent, &to_properties.layout_parent) {
+            self.push_unsupported(element, String::from("layoutParent can't be updated"));
+        }
+
+        // Children
+        if let (Some(from_children), Some(to_children)) =
+            (from_properties.children, to_properties.children)
+        {
+            if differs(from_children, to_children) {
+                self.push_update(element, "children", from_children, to_children);
+                self.diff.events.push(CoreInputEvent::UpdateEntityChildren(
+                    UpdateEntityChildrenInputEvent {
+                        id: target_id.clone(),
+                        children: to_children.clone(),
+                    },
+                ));
+            }
+        }
+
+        // Styles
+        self.diff_styles(
+            element,
+            &target_id,
+            from_properties.styles,
+            to_properties.styles,
+        );
+
+        // Node specific properties
+        match (from, to) {
+            (Node::Frame(from), Node::Frame(to)) => {
+                self.diff_clip_content(element, &target_id, from.clip_content, to.clip_content);
+            }
+            (Node::Component(from), Node::Component(to)) => {
+                self.diff_clip_content(element, &target_id, from.clip_content, to.clip_content);
+            }
+            (Node::Ellipse(from), Node::Ellipse(to)) => {
+                let mut event = UpdateEllipseNodeInputEvent {
+                    id: target_id.clone(),
+                    starting_angle: None,
+                    ending_angle: None,
+                    inner_radius_ratio: None,
+                };
+                if from.starting_angle != to.starting_angle {
+                    self.push_update(
+                        element,
+                        "startingAngle",
+                        &from.starting_angle,
+                        &to.starting_angle,
+                    );
+                    event.starting_angle = Some(to.starting_angle);
+                }
+                if from.ending_angle != to.ending_angle {
+                    self.push_update(element, "endingAngle", &from.ending_angle, &to.ending_angle);
+                    event.ending_angle = Some(to.ending_angle);
+                }
+                if from.inner_radius_ratio != to.inner_radius_ratio {
+                    self.push_update(
+                        element,
+                        "innerRadiusRatio",
+                        &from.inner_radius_ratio,
+                        &to.inner_radius_ratio,
+                    );
+                    event.inner_radius_ratio = Some(to.inner_radius_ratio);
+                }
+                if event.starting_angle.is_some()
+                    || event.ending_angle.is_some()
+                    || event.inner_radius_ratio.is_some()
+                {
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::UpdateEllipseNode(event));
+                }
+            }
+            (Node::Star(from), Node::Star(to)) => {
+                let mut event = UpdateStarNodeInputEvent {
+                    id: target_id.clone(),
+                    point_count: None,
+                    inner_radius_ratio: None,
+                };
+                if from.point_count != to.point_count {
+                    self.push_update(element, "pointCount", &from.point_count, &to.point_count);
+                    event.point_count = Some(to.point_count);
+                }
+                if from.inner_radius_ratio != to.inner_radius_ratio {
+                    self.push_update(
+                        element,
+                        "innerRadiusRatio",
+                        &from.inner_radius_ratio,
+                        &to.inner_radius_ratio,
+                    );
+                    event.inner_radius_ratio = Some(to.inner_radius_ratio);
+                }
+                if event.point_count.is_some() || event.inner_radius_ratio.is_some() {
+                    self.diff.events.push(CoreInputEvent::UpdateStarNode(event));
+                }
+            }
+            (Node::Polygon(from), Node::Polygon(to)) if from.point_count != to.point_count => {
+                self.push_update(element, "pointCount", &from.point_count, &to.point_count);
+                self.diff.events.push(CoreInputEvent::UpdatePolygonNode(
+                    UpdatePolygonNodeInputEvent {
+                        id: target_id.clone(),
+                        point_count: Some(to.point_count),
+                    },
+                ));
+            }
+            (Node::Text(from), Node::Text(to)) => {
+                let mut event = UpdateTextNodeInputEvent {
+                    id: target_id.clone(),
+                    text: None,
+                    attributes: None,
+                    line_wrap: None,
+                    horizontal_text_alignment: None,
+                    vertical_text_alignment: None,
+                    sizing_mode: None,
+                };
+                if from.text != to.text {
+                    self.push_update(element, "text", &from.text, &to.text);
+                    event.text = Some(to.text.clone());
+                }
+                if differs(&from.attributes, &to.attributes) {
+                    self.push_update(element, "attributes", &from.attributes, &to.attributes);
+                    event.attributes = Some(to.attributes.clone());
+                }
+                if differs(&from.line_wrap, &to.line_wrap) {
+                    self.push_update(element, "lineWrap", &from.line_wrap, &to.line_wrap);
+                    event.line_wrap = Some(to.line_wrap);
+                }
+                if differs(
+                    &from.horizontal_text_alignment,
+                    &to.horizontal_text_alignment,
+                ) {
+                    self.push_update(
+                        element,
+                        "horizontalTextAlignment",
+                        &from.horizontal_text_alignment,
+                        &to.horizontal_text_alignment,
+                    );
+                    event.horizontal_text_alignment = Some(to.horizontal_text_alignment);
+                }
+                if differs(&from.vertical_text_alignment, &to.vertical_text_alignment) {
+                    self.push_update(
+                        element,
+                        "verticalTextAlignment",
+                        &from.vertical_text_alignment,
+                        &to.vertical_text_alignment,
+                    );
+                    event.vertical_text_alignment = Some(to.vertical_text_alignment);
+                }
+                if from.sizing_mode != to.sizing_mode {
+                    self.push_update(element, "sizingMode", &from.sizing_mode, &to.sizing_mode);
+                    event.sizing_mode = Some(to.sizing_mode);
+                }
+                if event.text.is_some()
+                    || event.attributes.is_some()
+                    || event.line_wrap.is_some()
+                    || event.horizontal_text_alignment.is_some()
+                    || event.vertical_text_alignment.is_some()
+                    || event.sizing_mode.is_some()
+                {
+                    self.diff.events.push(CoreInputEvent::UpdateTextNode(event));
+                }
+            }
+            (Node::Vector(from), Node::Vector(to))
+                if from.path != to.path || from.winding_rule != to.winding_rule =>
+            {
+                self.push_unsupported(element, String::from("vector path can't be updated"));
+            }
+            (Node::BooleanOperation(from), Node::BooleanOperation(to))
+                if from.operation != to.operation =>
+            {
+                self.push_update(element, "operation", &from.operation, &to.operation);
+                self.diff
+                    .events
+                    .push(CoreInputEvent::UpdateBooleanOperationNode(
+                        UpdateBooleanOperationNodeInputEvent {
+                            id: target_id.clone(),
+                            operation: Some(to.operation),
+                        },
+                    ));
+            }
+            (Node::Instance(from), Node::Instance(to)) => {
+                let mut event = UpdateInstanceNodeInputEvent {
+                    id: target_id.clone(),
+                    component_id: None,
+                    overrides: None,
+                };
+                if differs(&from.component_id, &to.component_id) {
+                    self.push_update(element, "componentId", &from.component_id, &to.component_id);
+                    event.component_id = Some(to.component_id.clone());
+                }
+                if differs(&from.overrides, &to.overrides) {
+                    self.push_update(element, "overrides", &from.overrides, &to.overrides);
+                    event.overrides = Some(to.overrides.clone());
+                }
+                if event.component_id.is_some() || event.overrides.is_some() {
+                    self.diff
+                        .events
+                        .push(CoreInputEvent::UpdateInstanceNode(event));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn diff_clip_content(
+        &mut self,
+        element: &str,
+        target_id: &ReferenceIdOrEntity,
+        from: bool,
+        to: bool,
+    ) {
+        if from != to {
+            self.push_update(element, "clipContent", &from, &to);
+            self.diff
+                .events
+                .push(CoreInputEvent::UpdateFrameNode(UpdateFrameNodeInputEvent {
+                    id: target_id.clone(),
+                    clip_content: Some(to),
+                }));
+        }
+    }
+
+    fn diff_mixins(
+        &mut self,
+        element: &str,
+        target_id: &ReferenceIdOrEntity,
+        from: (Option<bool>, Option<BlendMode>, Option<Opacity>),
+        to: (Option<bool>, Option<BlendMode>, Option<Opacity>),
+    ) {
+        if let (Some(from_visible), Some(to_visible)) = (from.0, to.0) {
+            if from_visible != to_visible {
+                self.push_update(element, "visible", &from_visible, &to_visible);
+                self.diff
+                    .events
+                    .push(CoreInputEvent::UpdateEntityVisibility(
+                        UpdateEntityVisibilityInputEvent {
+                            id: target_id.clone(),
+                            visible: to_visible,
+                        },
+                    ));
+            }
+        }
+        if let (Some(from_blend_mode), Some(to_blend_mode)) = (from.1, to.1) {
+            if differs(&from_blend_mode, &to_blend_mode) {
+                self.push_update(element, "blendMode", &from_blend_mode, &to_blend_mode);
+                self.diff.events.push(CoreInputEvent::UpdateEntityBlendMode(
+                    UpdateEntityBlendModeInputEvent {
+                        id: target_id.clone(),
+                        blend_mode: to_blend_mode,
+                    },
+                ));
+            }
+        }
+        if let (Some(from_opacity), Some(to_opacity)) = (from.2, to.2) {
+            if from_opacity != to_opacity {
+                self.push_update(element, "opacity", &from_opacity, &to_opacity);
+                self.diff.events.push(CoreInputEvent::UpdateEntityOpacity(
+                    UpdateEntityOpacityInputEvent {
+                        id: target_id.clone(),
+                        opacity: to_opacity,
+                    },
+                ));
+            }
+        }
+    }
+
+    // =========================================================================
+    // Style
+    // =========================================================================
+
+    fn diff_styles(
+        &mut self,
+        node_element: &str,
+        node_target_id: &ReferenceIdOrEntity,
+        from: &[Style],
+        to: &[Style],
+    ) {
+        // Styles without reference id can't be matched
+        let (from_ids, to_ids) = match (
+            from.iter()
+                .map(style_id)
+                .collect::<Option<Vec<&ReferenceId>>>(),
+            to.iter()
+                .map(style_id)
+                .collect::<Option<Vec<&ReferenceId>>>(),
+        ) {
+            (Some(from_ids), Some(to_ids)) => (from_ids, to_ids),
+            _ => {
+                if differs(&from, &to) {
+                    self.push_unsupported(node_element, String::from("styles can't be matched"));
+                }
+                return;
+            }
+        };
+
+        // Mirrors the style order of the node while the events are applied,
+        // as styles are created before they are deleted or reordered
+        let mut current_ids = from_ids.clone();
+
+        for (index, (id, to_style)) in to_ids.iter().zip(to.iter()).enumerate() {
+            if from_ids.contains(id) {
+                continue;
+            }
+            self.push_change(
+                DtifChangeKind::Added,
+                format!("style '{}'", id),
+                String::from("style"),
+            );
+            self.diff
+                .events
+                .push(CoreInputEvent::CreateStyle(CreateStyleInputEvent {
+                    node_id: node_target_id.clone(),
+                    style: to_style.clone(),
+                    index: Some(index),
+                }));
+            current_ids.insert(index.min(current_ids.len()), id);
+        }
+
+        for id in from_ids.iter() {
+            if to_ids.contains(id) {
+                continue;
+            }
+            self.push_change(
+                DtifChangeKind::Removed,
+                format!("style '{}'", id),
+                String::from("style"),
+            );
+            self.diff
+                .events
+                .push(CoreInputEvent::DeleteStyle(DeleteStyleInputEvent {
+                    id: to_target_id(id),
+                }));
+            current_ids.retain(|current_id| current_id != id);
+        }
+
+        if current_ids != to_ids {
+            self.push_update(node_element, "styles", &from_ids, &to_ids);
+            self.diff
+                .events
+                .push(CoreInputEvent::ReorderStyles(ReorderStylesInputEvent {
+                    id: node_target_id.clone(),
+                    styles: to_ids.iter().map(|id| to_target_id(id)).collect(),
+                }));
+        }
+
+        let from_styles: HashMap<&ReferenceId, &Style> =
+            from_ids.iter().copied().zip(from.iter()).collect();
+        for (id, to_style) in to_ids.iter().zip(to.iter()) {
+            let from_style = match from_styles.get(id) {
+                Some(from_style) => *from_style,
+                None => continue,
+            };
+            let element = format!("style '{}'", id);
+            let target_id = to_target_id(id);
+
+            match (from_style, to_style) {
+                (Style::Fill(from), Style::Fill(to)) => {
+                    if differs(&from.paint_id, &to.paint_id) {
+                        self.push_update(&element, "paintId", &from.paint_id, &to.paint_id);
+                        self.diff.events.push(CoreInputEvent::UpdateFillStyle(
+                            UpdateFillStyleInputEvent {
+                                id: target_id.clone(),
+                                paint_id: Some(to.paint_id.clone()),
+                            },
+                        ));
+                    }
+                    self.diff_mixins(
+                        &element,
+                        &target_id,
+                        (
+                            Some(from.visible),
+                            Some(from.blend_mode),
+                            Some(from.opacity),
+                        ),
+                        (Some(to.visible), Some(to.blend_mode), Some(to.opacity)),
+                    );
+                }
+                (Style::Stroke(from), Style::Stroke(to)) => {
+                    let mut event = UpdateStorkeStyleInputEvent {
+                        id: target_id.clone(),
+                        paint_id: None,
+                        width: None,
+                        alignment: None,
+                        cap: None,
+                        join: None,
+                        miter_limit: None,
+                        dash_array: None,
+                        dash_offset: None,
+                    };
+                    if differs(&from.paint_id, &to.paint_id) {
+                        self.push_update(&element, "paintId", &from.paint_id, &to.paint_id);
+                        event.paint_id = Some(to.paint_id.clone());
+                    }
+                    if from.width != to.width {
+                        self.push_update(&element, "width", &from.width, &to.width);
+                        event.width = Some(to.width);
+                    }
+                    if from.alignment != to.alignment {
+                        self.push_update(&element, "alignment", &from.alignment, &to.alignment);
+                        event.alignment = Some(to.alignment);
+                    }
+                    if from.cap != to.cap {
+                        self.push_update(&element, "cap", &from.cap, &to.cap);
+                        event.cap = Some(to.cap);
+                    }
+                    if from.join != to.join {
+                        self.push_update(&element, "join", &from.join, &to.join);
+                        event.join = Some(to.join);
+                    }
+                    if from.miter_limit != to.miter_limit {
+                        self.push_update(
+                            &element,
+                            "miterLimit",
+                            &from.miter_limit,
+                            &to.miter_limit,
+                        );
+                        event.miter_limit = Some(to.miter_limit);
+                    }
+                    if from.dash_array != to.dash_array {
+                        self.push_update(&element, "dashArray", &from.dash_array, &to.dash_array);
+                        event.dash_array = Some(to.dash_array.clone());
+                    }
+                    if from.dash_offset != to.dash_offset {
+                        self.push_update(
+                            &element,
+                            "dashOffset",
+                            &from.dash_offset,
+                            &to.dash_offset,
+                        );
+                        event.dash_offset = Some(to.dash_offset);
+                    }
+                    if event.paint_id.is_some()
+                        || event.width.is_some()
+                        || event.alignment.is_some()
+                        || event.cap.is_some()
+                        || event.join.is_some()
+                        || event.miter_limit.is_some()
+                        || event.dash_array.is_some()
+                        || event.dash_offset.is_some()
+                    {
+                        self.diff
+                            .events
+                            .push(CoreInputEvent::UpdateStrokeStyle(event));
+                    }
+                    self.diff_mixins(
+                        &element,
+                        &target_id,
+                        (
+                            Some(from.visible),
+                            Some(from.blend_mode),
+                            Some(from.opacity),
+                        ),
+                        (Some(to.visible), Some(to.blend_mode), Some(to.opacity)),
+                    );
+                }
+                (Style::DropShadow(from), Style::DropShadow(to)) => {
+                    let mut event = UpdateDropShadowStyleInputEvent {
+                        id: target_id.clone(),
+                        color: None,
+                        position: None,
+                        spread: None,
+                        blur: None,
+                    };
+                    if from.color != to.color {
+                        self.push_update(&element, "color", &from.color, &to.color);
+                        event.color = Some(to.color);
+                    }
+                    if from.position != to.position {
+                        self.push_update(&element, "position", &from.position, &to.position);
+                        event.position = Some(to.position);
+                    }
+                    if from.spread != to.spread {
+                        self.push_update(&element, "spread", &from.spread, &to.spread);
+                        event.spread = Some(to.spread);
+                    }
+                    if from.blur != to.blur {
+                        self.push_update(&element, "blur", &from.blur, &to.blur);
+                        event.blur = Some(to.blur);
+                    }
+                    if event.color.is_some()
+                        || event.position.is_some()
+                        || event.spread.is_some()
+                        || event.blur.is_some()
+                    {
+                        self.diff
+                            .events
+                            .push(CoreInputEvent::UpdateDropShadowStyle(event));
+                    }
+                    self.diff_mixins(
+                        &element,
+                        &target_id,
+                        (
+                            Some(from.visible),
+                            Some(from.blend_mode),
+                            Some(from.opacity),
+                        ),
+                        (Some(to.visible), Some(to.blend_mode), Some(to.opacity)),
+                    );
+                }
+                (Style::InnerShadow(from), Style::InnerShadow(to)) => {
+                    let mut event = UpdateInnerShadowStyleInputEvent {
+                        id: target_id.clone(),
+                        color: None,
+                        position: None,
+                        spread: None,
+                        blur: None,
+                    };
+                    if from.color != to.color {
+                        self.push_update(&element, "color", &from.color, &to.color);
+                        event.color = Some(to.color);
+                    }
+                    if from.position != to.position {
+                        self.push_update(&element, "position", &from.position, &to.position);
+                        event.position = Some(to.position);
+                    }
+                    if from.spread != to.spread {
+                        self.push_update(&element, "spread", &from.spread, &to.spread);
+                        event.spread = Some(to.spread);
+                    }
+                    if from.blur != to.blur {
+                        self.push_update(&element, "blur", &from.blur, &to.blur);
+                        event.blur = Some(to.blur);
+                    }
+                    if event.color.is_some()
+                        || event.position.is_some()
+                        || event.spread.is_some()
+                        || event.blur.is_some()
+                    {
+                        self.diff
+                            .events
+                            .push(CoreInputEvent::UpdateInnerShadowStyle(event));
+                    }
+                    self.diff_mixins(
+                        &element,
+                        &target_id,
+                        (
+                            Some(from.visible),
+                            Some(from.blend_mode),
+                            Some(from.opacity),
+                        ),
+                        (Some(to.visible), Some(to.blend_mode), Some(to.opacity)),
+                    );
+                }
+                (Style::LayerBlur(from), Style::LayerBlur(to)) => {
+                    if from.blur != to.blur {
+                        self.push_update(&element, "blur", &from.blur, &to.blur);
+                        self.diff.events.push(CoreInputEvent::UpdateLayerBlurStyle(
+                            UpdateLayerBlurStyleInputEvent {
+                                id: target_id.clone(),
+                                blur: Some(to.blur),
+                            },
+                        ));
+                    }
+                    self.diff_mixins(
+                        &element,
+                        &target_id,
+                        (Some(from.visible), None, None),
+                        (Some(to.visible), None, None),
+                    );
+                }
+                (Style::BackgroundBlur(from), Style::BackgroundBlur(to)) => {
+                    if from.blur != to.blur {
+                        self.push_update(&element, "blur", &from.blur, &to.blur);
+                        self.diff
+                            .events
+                            .push(CoreInputEvent::UpdateBackgroundBlurStyle(
+                                UpdateBackgroundBlurStyleInputEvent {
+                                    id: target_id.clone(),
+                                    blur: Some(to.blur),
+                                },
+                            ));
+                    }
+                    self.diff_mixins(
+                        &element,
+                        &target_id,
+                        (Some(from.visible), None, None),
+                        (Some(to.visible), None, None),
+                    );
+                }
+                _ => self.push_unsupported(&element, String::from("style type changed")),
+            }
+        }
+    }
+
+    // =========================================================================
+    // Script
+    // =========================================================================
+
+    #[cfg(feature = "lua_scripts")]
+    fn diff_scripts(&mut self, from: &DtifArtboard, to: &DtifArtboard) {
+        use dyn_arb_bundles::events::RegisterLuaScriptInputEvent;
+
+        let from_scripts: HashMap<&ReferenceId, _> = from
+            .scripts
+            .iter()
+            .map(|script| (&script.id, script))
+            .collect();
+        let to_ids: HashSet<&ReferenceId> = to.scripts.iter().map(|script| &script.id).collect();
+
+        for to_script in to.scripts.iter() {
+            let element = format!("script '{}'", to_script.id);
+            match from_scripts.get(&to_script.id) {
+                Some(from_script) if from_script.source == to_script.source => continue,
+                Some(_) => self.push_change(
+                    DtifChangeKind::Updated,
+                    element,
+                    String::from("source changed"),
+                ),
+                None => self.push_change(DtifChangeKind::Added, element, String::from("script")),
+            }
+            // Registering a script with an existing id replaces it
+            self.diff.events.push(CoreInputEvent::RegisterLuaScript(
+                RegisterLuaScriptInputEvent {
+                    script: to_script.clone(),
+                },
+            ));
+        }
+
+        for from_script in from.scripts.iter() {
+            if !to_ids.contains(&from_script.id) {
+                self.push_unsupported(
+                    &format!("script '{}'", from_script.id),
+                    String::from("scripts can't be removed"),
+                );
+            }
+        }
+    }
+}
+
+/// Properties shared by (most) node types.
+struct NodeProperties<'a> {
+    translation: Option<Vec2>,
+    rotation_deg: Option<Angle>,
+    size: Size,
+    visible: Option<bool>,
+    blend_mode: Option<BlendMode>,
+    opacity: Option<Opacity>,
+    corner_radii: Option<CornerRadii>,
+    layout_element: Option<&'a LayoutElement>,
+    layout_parent: Option<&'a Option<StaticLayoutParent>>,
+    children: Option<&'a Vec<ReferenceIdOrEntity>>,
+    styles: &'a [Style],
+}
+
+impl<'a> NodeProperties<'a> {
+    fn from_node(node: &'a Node) -> Self {
+        match node {
+            Node::Frame(node) => Self {
+                translation: Some(node.translation),
+                rotation_deg: Some(node.rotation_deg),
+                size: node.size,
+                visible: Some(node.visible),
+                blend_mode: Some(node.blend_mode),
+                opacity: Some(node.opacity),
+                corner_radii: Some(node.corner_radii),
+                layout_element: Some(&node.layout_element),
+                layout_parent: Some(&node.layout_parent),
+                children: Some(&node.children),
+                styles: &node.styles,
+            },
+            Node::Component(node) => Self {
+                translation: None,
+                rotation_deg: None,
+                size: node.size,
+                visible: None,
+                blend_mode: None,
+                opacity: None,
+                corner_radii: Some(node.corner_radii),
+                layout_element: None,
+                layout_parent: Some(&node.layout_parent),
+                children: Some(&node.children),
+                styles: &node.styles,
+            },
+            Node::Instance(node) => Self {
+                translation: Some(node.translation),
+                rotation_deg: Some(node.rotation_deg),
+                size: node.size,
+                visible: Some(node.visible),
+                blend_mode: Some(node.blend_mode),
+                opacity: Some(node.opacity),
+                corner_radii: None,
+                layout_element: Some(&node.layout_element),
+                layout_parent: None,
+                children: None,
+                styles: &[],
+            },
+            Node::Rectangle(node) => Self {
+                translation: Some(node.translation),
+                rotation_deg: Some(node.rotation_deg),
+                size: node.size,
+                visible: Some(node.visible),
+                blend_mode: Some(node.blend_mode),
+                opacity: Some(node.opacity),
+                corner_radii: Some(node.corner_radii),
+                layout_element: Some(&node.layout_element),
+                layout_parent: None,
+                children: None,
+                styles: &node.styles,
+            },
+            Node::Ellipse(node) => Self::from_shape(
+                node.translation,
+                node.rotation_deg,
+                node.size,
+                (node.visible, node.blend_mode, node.opacity),
+                &node.layout_element,
+                &node.styles,
+            ),
+            Node::Star(node) => Self::from_shape(
+                node.translation,
+                node.rotation_deg,
+                node.size,
+                (node.visible, node.blend_mode, node.opacity),
+                &node.layout_element,
+                &node.styles,
+            ),
+            Node::Polygon(node) => Self::from_shape(
+                node.translation,
+                node.rotation_deg,
+                node.size,
+                (node.visible, node.blend_mode, node.opacity),
+                &node.layout_element,
+                &node.styles,
+            ),
+            Node::Text(node) => Self::from_shape(
+                node.translation,
+                node.rotation_deg,
+                node.size,
+                (node.visible, node.blend_mode, node.opacity),
+                &node.layout_element,
+                &node.styles,
+            ),
+            Node::Vector(node) => Self::from_shape(
+                node.translation,
+                node.rotation_deg,
+                node.size,
+                (node.visible, node.blend_mode, node.opacity),
+                &node.layout_element,
+                &node.styles,
+            ),
+            Node::BooleanOperation(node) => Self {
+                children: Some(&node.children),
+                ..Self::from_shape(
+                    node.translation,
+                    node.rotation_deg,
+                    node.size,
+                    (node.visible, node.blend_mode, node.opacity),
+                    &node.layout_element,
+                    &node.styles,
+                )
+            },
+        }
+    }
+
+    fn from_shape(
+        translation: Vec2,
+        rotation_deg: Angle,
+        size: Size,
+        mixins: (bool, BlendMode, Opacity),
+        layout_element: &'a LayoutElement,
+        styles: &'a [Style],
+    ) -> Self {
+        Self {
+            translation: Some(translation),
+            rotation_deg: Some(rotation_deg),
+            size,
+            visible: Some(mixins.0),
+            blend_mode: Some(mixins.1),
+            opacity: Some(mixins.2),
+            corner_radii: None,
+            layout_element: Some(layout_element),
+            layout_parent: None,
+            children: None,
+            styles,
+        }
+    }
+}
+
+/// Elements of a list split into the ones with and without reference id.
+struct ElementsByReferenceId<'a, T> {
+    matched: Vec<(&'a ReferenceId, &'a T)>,
+    unmatched: Vec<&'a T>,
+}
+
+impl<'a, T> ElementsByReferenceId<'a, T> {
+    fn get(&self, id: &ReferenceId) -> Option<&'a T> {
+        self.matched
+            .iter()
+            .find(|(element_id, _)| *element_id == id)
+            .map(|(_, element)| *element)
+    }
+}
+
+fn by_reference_id<'a, T: 'a>(
+    elements: impl Iterator<Item = &'a T>,
+    get_id: impl Fn(&'a T) -> Option<&'a ReferenceId>,
+) -> ElementsByReferenceId<'a, T> {
+    let mut result = ElementsByReferenceId {
+        matched: Vec::new(),
+        unmatched: Vec::new(),
+    };
+    for element in elements {
+        match get_id(element) {
+            Some(id) => result.matched.push((id, element)),
+            None => result.unmatched.push(element),
+        }
+    }
+    return result;
+}
+
+fn collect_parents(nodes: &[Node]) -> HashMap<&ReferenceId, &ReferenceId> {
+    let mut parents = HashMap::new();
+    for node in nodes {
+        let (maybe_id, children) = match node {
+            Node::Frame(node) => (node.id.as_ref(), &node.children),
+            Node::Component(node) => (node.id.as_ref(), &node.children),
+            Node::BooleanOperation(node) => (node.id.as_ref(), &node.children),
+            _ => continue,
+        };
+        if let Some(id) = maybe_id {
+            for child in children {
+                if let ReferenceIdOrEntity::ReferenceId { reference_id } = child {
+                    parents.insert(reference_id, id);
+                }
+            }
+        }
+    }
+    return parents;
+}
+
+fn paint_id(paint: &Paint) -> Option<&ReferenceId> {
+    match paint {
+        Paint::Solid(paint) => paint.id.as_ref(),
+        Paint::Image(paint) => paint.id.as_ref(),
+        Paint::Gradient(paint) => paint.id.as_ref(),
+    }
+}
+
+fn style_id(style: &Style) -> Option<&ReferenceId> {
+    match style {
+        Style::Fill(style) => style.id.as_ref(),
+        Style::Stroke(style) => style.id.as_ref(),
+        Style::DropShadow(style) => style.id.as_ref(),
+        Style::InnerShadow(style) => style.id.as_ref(),
+        Style::LayerBlur(style) => style.id.as_ref(),
+        Style::BackgroundBlur(style) => style.id.as_ref(),
+    }
+}
+
+fn node_type_name(node: &Node) -> &'static str {
+    match node {
+        Node::Frame(_) => "Frame",
+        Node::Rectangle(_) => "Rectangle",
+        Node::Ellipse(_) => "Ellipse",
+        Node::Star(_) => "Star",
+        Node::Polygon(_) => "Polygon",
+        Node::Text(_) => "Text",
+        Node::Vector(_) => "Vector",
+        Node::BooleanOperation(_) => "BooleanOperation",
+        Node::Component(_) => "Component",
+        Node::Instance(_) => "Instance",
+    }
+}
+
+fn to_target_id(id: &ReferenceId) -> ReferenceIdOrEntity {
+    ReferenceIdOrEntity::reference_id(id.clone())
+}
+
+/// Compares values by their serialized representation,
+/// as not all DTIF properties implement `PartialEq`.
+fn differs<T: Serialize>(from: &T, to: &T) -> bool {
+    serde_json::to_value(from).ok() != serde_json::to_value(to).ok()
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artboard(value: serde_json::Value) -> DtifArtboard {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_diff_nodes() {
+        let from = artboard(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Frame", "id": "root", "size": [100, 100], "children": [
+                    { "type": "ReferenceId", "referenceId": "title" },
+                    { "type": "ReferenceId", "referenceId": "old" }
+                ] },
+                { "type": "Text", "id": "title", "text": "Hello", "attributes": [], "size": [50, 20] },
+                { "type": "Rectangle", "id": "old", "size": [10, 10] }
+            ]
+        }));
+        let to = artboard(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Frame", "id": "root", "size": [100, 100], "children": [
+                    { "type": "ReferenceId", "referenceId": "title" },
+                    { "type": "ReferenceId", "referenceId": "new" }
+                ] },
+                { "type": "Text", "id": "title", "text": "World", "attributes": [], "size": [50, 20] },
+                { "type": "Rectangle", "id": "new", "size": [10, 10] }
+            ]
+        }));
+
+        let diff = diff(&from, &to);
+
+        assert!(!diff.requires_reload());
+        assert_eq!(diff.events.len(), 4);
+        assert!(matches!(
+            &diff.events[0],
+            CoreInputEvent::UpdateEntityChildren(event) if event.children.len() == 2
+        ));
+        assert!(matches!(
+            &diff.events[1],
+            CoreInputEvent::UpdateTextNode(event) if event.text.as_deref() == Some("World")
+        ));
+        assert!(matches!(&diff.events[2], CoreInputEvent::CreateNode(_)));
+        assert!(matches!(&diff.events[3], CoreInputEvent::DeleteEntity(_)));
+    }
+
+    #[test]
+    fn test_diff_styles() {
+        let from = artboard(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Rectangle", "id": "rect", "size": [10, 10], "styles": [
+                { "type": "LayerBlur", "id": "blur", "blur": 4 },
+                { "type": "BackgroundBlur", "id": "background", "blur": 8 },
+                { "type": "LayerBlur", "id": "old", "blur": 2 }
+            ] }]
+        }));
+        let to = artboard(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Rectangle", "id": "rect", "size": [10, 10], "styles": [
+                { "type": "BackgroundBlur", "id": "background", "blur": 8 },
+                { "type": "LayerBlur", "id": "new", "blur": 2 },
+                { "type": "LayerBlur", "id": "blur", "blur": 4 }
+            ] }]
+        }));
+
+        let diff = diff(&from, &to);
+
+        assert!(!diff.requires_reload());
+        assert_eq!(diff.events.len(), 3);
+        assert!(matches!(
+            &diff.events[0],
+            CoreInputEvent::CreateStyle(event) if event.index == Some(1)
+        ));
+        assert!(matches!(&diff.events[1], CoreInputEvent::DeleteStyle(_)));
+        assert!(matches!(
+            &diff.events[2],
+            CoreInputEvent::ReorderStyles(event) if event.styles.len() == 3
+        ));
+    }
+
+    #[test]
+    fn test_diff_sub_pixel_layout() {
+        let from = artboard(serde_json::json!({ "size": [100, 100], "nodes": [] }));
+        let to = artboard(serde_json::json!({
+            "size": [100, 100],
+            "subPixelLayout": false,
+            "nodes": []
+        }));
+
+        let diff = diff(&from, &to);
+
+        assert!(diff.requires_reload());
+        assert!(diff.events.is_empty());
+        assert_eq!(diff.changes.len(), 1);
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let dtif = artboard(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Rectangle", "id": "rect", "size": [10, 10] }]
+        }));
+
+        assert!(diff(&dtif, &dtif).is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{validation::node_id_and_styles, DtifArtboard};
+use dyn_arb_bundles::{
+    events::CoreInputEvent,
+    properties::{ArbVersion, Viewport},
+    reference_id::{ReferenceId, ReferenceIdOrEntity},
+    AssetWithId, Node, Paint, Style, Variable, VariableBinding,
+};
+use dyn_utils::properties::size::Size;
+use std::collections::HashSet;
+
+#[cfg(feature = "lua_scripts")]
+use dyn_arb_bundles::LuaScriptWithId;
+
+/// DTIF document holding multiple artboards (e.g. the same content in different formats)
+/// that share paints, assets, variables and scripts.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct DtifDocument {
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub version: Option<ArbVersion>,
+    /// A list of artboards (pages).
+    pub artboards: Vec<DtifDocumentArtboard>,
+    /// A list of paints shared by all artboards.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub paints: Vec<Paint>,
+    /// A list of assets shared by all artboards.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub assets: Vec<AssetWithId>,
+    /// A list of variables (design tokens) shared by all artboards.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variables: Vec<Variable>,
+    /// The active variable mode (e.g. "light" or "dark").
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variable_mode: Option<String>,
+    /// A list of properties bound to variables.
+    /// Bindings only end up in the artboards containing their target.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variable_bindings: Vec<VariableBinding>,
+    /// A list of scripts shared by all artboards.
+    #[cfg(feature = "lua_scripts")]
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub scripts: Vec<LuaScriptWithId>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct DtifDocumentArtboard {
+    /// The name used to select the artboard (e.g. "instagram-story").
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub name: Option<String>,
+    /// The absolute size of the artboard.
+    pub size: Size,
+    /// The viewport of the artboard.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub viewport: Option<Viewport>,
+    /// Whether the layout keeps sub-pixel precision
+    /// instead of rounding positions and sizes to whole pixels.
+    #[cfg_attr(
+        feature = "specta_support",
+        serde(default = "dyn_utils::serde::default_as_true")
+    )]
+    pub sub_pixel_layout: bool,
+    /// A list of nodes.
+    pub nodes: Vec<Node>,
+    /// A list of input events.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub events: Vec<CoreInputEvent>,
+}
+
+impl DtifDocument {
+    /// Finds the index of the artboard with the given name
+    /// or, if no artboard has that name, interprets the selector as index.
+    pub fn find_artboard_index(&self, selector: &str) -> Option<usize> {
+        self.artboards
+            .iter()
+            .position(|artboard| artboard.name.as_deref() == Some(selector))
+            .or_else(|| {
+                selector
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| *index < self.artboards.len())
+            })
+    }
+
+    /// Builds a standalone artboard DTIF including the shared elements of the document,
+    /// so that it can be loaded into a world and rendered like any other artboard.
+    pub fn to_artboard(&self, index: usize) -> Option<DtifArtboard> {
+        let artboard = self.artboards.get(index)?;
+
+        return Some(DtifArtboard {
+            version: self.version,
+            size: artboard.size,
+            viewport: artboard.viewport,
+            sub_pixel_layout: artboard.sub_pixel_layout,
+            nodes: artboard.nodes.clone(),
+            paints: self.paints.clone(),
+            assets: self.assets.clone(),
+            variables: self.variables.clone(),
+            variable_mode: self.variable_mode.clone(),
+            variable_bindings: self
+                .get_variable_binding_indices(artboard)
+                .into_iter()
+                .map(|index| self.variable_bindings[index].clone())
+                .collect(),
+            events: artboard.events.clone(),
+            #[cfg(feature = "lua_scripts")]
+            scripts: self.scripts.clone(),
+        });
+    }
+
+    pub fn to_artboards(&self) -> Vec<DtifArtboard> {
+        (0..self.artboards.len())
+            .filter_map(|index| self.to_artboard(index))
+            .collect()
+    }
+
+    /// Finds the indices of the variable bindings that target an element of the artboard.
+    pub(crate) fn get_variable_binding_indices(
+        &self,
+        artboard: &DtifDocumentArtboard,
+    ) -> Vec<usize> {
+        let mut reference_ids: HashSet<&ReferenceId> = HashSet::new();
+        for node in artboard.nodes.iter() {
+            let (maybe_id, styles) = node_id_and_styles(node);
+            reference_ids.extend(maybe_id.iter());
+            reference_ids.extend(styles.iter().filter_map(|style| match style {
+                Style::Fill(style) => style.id.as_ref(),
+                Style::Stroke(style) => style.id.as_ref(),
+                Style::DropShadow(style) => style.id.as_ref(),
+                Style::InnerShadow(style) => style.id.as_ref(),
+                Style::LayerBlur(style) => style.id.as_ref(),
+                Style::BackgroundBlur(style) => style.id.as_ref(),
+            }));
+        }
+        reference_ids.extend(self.paints.iter().filter_map(|paint| match paint {
+            Paint::Solid(paint) => paint.id.as_ref(),
+            Paint::Image(paint) => paint.id.as_ref(),
+            Paint::Gradient(paint) => paint.id.as_ref(),
+        }));
+
+        return self
+            .variable_bindings
+            .iter()
+            .enumerate()
+            .filter(|(_, binding)| match &binding.target_id {
+                ReferenceIdOrEntity::ReferenceId { reference_id } => {
+                    reference_ids.contains(reference_id)
+                }
+                ReferenceIdOrEntity::Entity { .. } => true,
+            })
+            .map(|(index, _)| index)
+            .collect();
+    }
+}
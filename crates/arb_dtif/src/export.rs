@@ -0,0 +1,755 @@
+use crate::DtifArtboard;
+use bevy_ecs::{
+    entity::Entity,
+    world::{EntityRef, World},
+};
+use bevy_hierarchy::Children;
+use bevy_transform::components::Transform;
+use dyn_arb_asset::{
+    asset::{AssetContent, AssetContentType, ImageAssetContentType},
+    asset_id::{AssetId, ImageId},
+    resources::AssetsRes,
+};
+use dyn_arb_bundles::{
+    components::{
+        marker::{InstanceContent, Removed},
+        mixins::{
+            AbsoluteLayoutElementMixin, BlendModeMixin, ComponentMixin, CornerRadiiMixin,
+            ImageAssetMixin, InstanceMixin, LayoutElement, OpacityMixin, PaintChildMixin,
+            PathMixin, SizeMixin, StaticLayoutElementMixin, StaticLayoutParentMixin,
+            StyleChildrenMixin, VariableBindingsMixin, VisibilityMixin,
+        },
+        nodes::{
+            ArbNode, ArbNodeVariant, BooleanOperationArbNode, EllipseArbNode, FrameArbNode,
+            PolygonArbNode, StarArbNode, TextArbNode,
+        },
+        paints::{ArbPaint, ArbPaintVariant, GradientArbPaint, ImageArbPaint, SolidArbPaint},
+        styles::{
+            ArbStyle, ArbStyleVariant, BackgroundBlurArbStyle, DropShadowArbStyle,
+            InnerShadowArbStyle, LayerBlurArbStyle, StrokeArbStyle,
+        },
+    },
+    mapper::tiny_skia_path_to_string,
+    properties::InstanceOverride,
+    reference_id::{ReferenceId, ReferenceIdOrEntity, ReferenceIdOrImageId},
+    utils::transform_to_z_rotation_rad,
+    AssetWithId, BackgroundBlurStyle, BooleanOperationNode, ComponentNode, DropShadowStyle,
+    EllipseNode, FillStyle, FrameNode, GradientPaint, ImagePaint, InnerShadowStyle, InstanceNode,
+    LayerBlurStyle, Node, Paint, PolygonNode, RectangleNode, SolidPaint, StarNode, StrokeStyle,
+    Style, TextNode, Variable, VariableBinding, VectorNode,
+};
+use dyn_arb_core::resources::{
+    artboard::ArtboardRes, referencer::ReferencerRes, variables::VariablesRes,
+};
+use dyn_utils::units::{abs::Abs, angle::Angle};
+use std::collections::HashMap;
+
+impl DtifArtboard {
+    /// Rebuilds a flat DTIF document from the current state of the ECS world.
+    ///
+    /// Nodes, paints and assets that weren't created with a reference id
+    /// get a generated one, so that relations between them (children, paints of styles,
+    /// images of paints) can be expressed in the exported document.
+    pub fn from_world(world: &World) -> Self {
+        let mut cx = ExportContext::new(world);
+
+        let mut entities = world
+            .iter_entities()
+            .filter(|entity_ref| !entity_ref.contains::<Removed>())
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|entity_ref| entity_ref.id());
+
+        let paints = entities
+            .iter()
+            .filter_map(|entity_ref| cx.export_paint(entity_ref))
+            .collect::<Vec<_>>();
+        let nodes = entities
+            .iter()
+            .filter_map(|entity_ref| cx.export_node(entity_ref))
+            .collect::<Vec<_>>();
+        let assets = cx.export_assets();
+        let variable_bindings = entities
+            .iter()
+            .flat_map(|entity_ref| cx.export_variable_bindings(entity_ref))
+            .collect::<Vec<_>>();
+
+        let maybe_arb_res = world.get_resource::<ArtboardRes>();
+
+        return Self {
+            version: maybe_arb_res.map(|arb_res| arb_res.version),
+            size: maybe_arb_res
+                .map(|arb_res| arb_res.size)
+                .unwrap_or_default(),
+            viewport: maybe_arb_res.map(|arb_res| arb_res.viewport),
+            sub_pixel_layout: maybe_arb_res
+                .map(|arb_res| arb_res.sub_pixel_layout)
+                .unwrap_or(true),
+            nodes,
+            paints,
+            assets,
+            variables: export_variables(world),
+            variable_mode: world
+                .get_resource::<VariablesRes>()
+                .and_then(|variables_res| variables_res.get_mode().map(String::from)),
+            variable_bindings,
+            events: Vec::new(),
+            #[cfg(feature = "lua_scripts")]
+            scripts: export_scripts(world),
+        };
+    }
+}
+
+struct ExportContext<'w> {
+    world: &'w World,
+    entity_to_reference_id: HashMap<Entity, ReferenceId>,
+    image_id_to_reference_id: HashMap<ImageId, ReferenceId>,
+}
+
+impl<'w> ExportContext<'w> {
+    fn new(world: &'w World) -> Self {
+        let mut entity_to_reference_id = HashMap::new();
+        let mut image_id_to_reference_id = HashMap::new();
+
+        if let Some(referencer_res) = world.get_resource::<ReferencerRes>() {
+            for (reference_id, entity) in referencer_res.get_reference_id_to_entity_map() {
+                entity_to_reference_id.insert(*entity, reference_id.clone());
+            }
+            for (reference_id, asset_id) in referencer_res.get_reference_id_to_asset_id_map() {
+                if let AssetId::Image(image_id) = asset_id {
+                    image_id_to_reference_id.insert(*image_id, reference_id.clone());
+                }
+            }
+        }
+
+        return Self {
+            world,
+            entity_to_reference_id,
+            image_id_to_reference_id,
+        };
+    }
+
+    fn get_entity_reference_id(&mut self, entity: Entity) -> ReferenceId {
+        self.entity_to_reference_id
+            .entry(entity)
+            .or_insert_with(|| {
+                ReferenceId::new(format!("entity_{}v{}", entity.index(), entity.generation()))
+            })
+            .clone()
+    }
+
+    fn to_reference_id(&mut self, id: &ReferenceIdOrEntity) -> ReferenceIdOrEntity {
+        match id {
+            ReferenceIdOrEntity::Entity { entity } => {
+                ReferenceIdOrEntity::reference_id(self.get_entity_reference_id(*entity))
+            }
+            ReferenceIdOrEntity::ReferenceId { .. } => id.clone(),
+        }
+    }
+
+    fn get_image_reference_id(&mut self, image_id: ImageId) -> ReferenceId {
+        self.image_id_to_reference_id
+            .entry(image_id)
+            .or_insert_with(|| ReferenceId::new(format!("image_{}", image_id)))
+            .clone()
+    }
+
+    // =========================================================================
+    // Node
+    // =========================================================================
+
+    fn export_node(&mut self, entity_ref: &EntityRef) -> Option<Node> {
+        if entity_ref.contains::<InstanceContent>() {
+            return None;
+        }
+        let ArbNode { variant } = entity_ref.get::<ArbNode>()?;
+        let entity = entity_ref.id();
+
+        let id = Some(self.get_entity_reference_id(entity));
+        let (translation, rotation_deg) = entity_ref
+            .get::<Transform>()
+            .map(|transform| {
+                (
+                    transform.translation.truncate(),
+                    Angle::rad(transform_to_z_rotation_rad(transform)),
+                )
+            })
+            .unwrap_or_default();
+        let size = entity_ref
+            .get::<SizeMixin>()
+            .map(|SizeMixin(size)| *size)
+            .unwrap_or_default();
+        let visible = entity_ref
+            .get::<VisibilityMixin>()
+            .map(|VisibilityMixin(visible)| *visible)
+            .unwrap_or(true);
+        let blend_mode = entity_ref
+            .get::<BlendModeMixin>()
+            .map(|BlendModeMixin(blend_mode)| *blend_mode)
+            .unwrap_or_default();
+        let opacity = entity_ref
+            .get::<OpacityMixin>()
+            .map(|OpacityMixin(opacity)| *opacity)
+            .unwrap_or_default();
+        let layout_element = if let Some(StaticLayoutElementMixin(layout_element)) =
+            entity_ref.get::<StaticLayoutElementMixin>()
+        {
+            LayoutElement::Static(*layout_element)
+        } else if let Some(AbsoluteLayoutElementMixin(layout_element)) =
+            entity_ref.get::<AbsoluteLayoutElementMixin>()
+        {
+            LayoutElement::Absolute(*layout_element)
+        } else {
+            LayoutElement::default()
+        };
+        let styles = entity_ref
+            .get::<StyleChildrenMixin>()
+            .map(|StyleChildrenMixin(style_entities)| {
+                style_entities
+                    .iter()
+                    .filter_map(|style_entity| self.export_style(*style_entity))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let node = match variant {
+            ArbNodeVariant::Frame => {
+                let frame = entity_ref.get::<FrameArbNode>()?;
+                let children = self.export_children(&entity_ref);
+
+                let layout_parent = entity_ref
+                    .get::<StaticLayoutParentMixin>()
+                    .map(|StaticLayoutParentMixin(layout_parent)| layout_parent.clone());
+                let corner_radii = entity_ref
+                    .get::<CornerRadiiMixin>()
+                    .map(|CornerRadiiMixin(corner_radii)| *corner_radii)
+                    .unwrap_or_default();
+
+                if entity_ref.contains::<ComponentMixin>() {
+                    Node::Component(ComponentNode {
+                        id,
+                        clip_content: frame.clip_content,
+                        layout_parent,
+                        corner_radii,
+                        size,
+                        styles,
+                        children,
+                    })
+                } else if let Some(instance_mixin) = entity_ref.get::<InstanceMixin>() {
+                    // Content of the instance is derived from the component on import
+                    Node::Instance(InstanceNode {
+                        id,
+                        component_id: self.to_reference_id(&instance_mixin.component_id),
+                        overrides: instance_mixin
+                            .overrides
+                            .iter()
+                            .map(|instance_override| InstanceOverride {
+                                target_id: self.to_reference_id(&instance_override.target_id),
+                                paint_id: instance_override
+                                    .paint_id
+                                    .as_ref()
+                                    .map(|paint_id| self.to_reference_id(paint_id)),
+                                ..instance_override.clone()
+                            })
+                            .collect(),
+                        translation,
+                        rotation_deg,
+                        size,
+                        visible,
+                        blend_mode,
+                        opacity,
+                        layout_element,
+                    })
+                } else {
+                    Node::Frame(FrameNode {
+                        id,
+                        clip_content: frame.clip_content,
+                        layout_parent,
+                        corner_radii,
+                        translation,
+                        rotation_deg,
+                        size,
+                        visible,
+                        blend_mode,
+                        opacity,
+                        layout_element,
+                        styles,
+                        children,
+                    })
+                }
+            }
+            ArbNodeVariant::Rectangle => Node::Rectangle(RectangleNode {
+                id,
+                corner_radii: entity_ref
+                    .get::<CornerRadiiMixin>()
+                    .map(|CornerRadiiMixin(corner_radii)| *corner_radii)
+                    .unwrap_or_default(),
+                translation,
+                rotation_deg,
+                size,
+                visible,
+                blend_mode,
+                opacity,
+                layout_element,
+                styles,
+            }),
+            ArbNodeVariant::Ellipse => {
+                let ellipse = entity_ref.get::<EllipseArbNode>()?;
+                Node::Ellipse(EllipseNode {
+                    id,
+                    starting_angle: ellipse.arc_data.starting_angle,
+                    ending_angle: ellipse.arc_data.ending_angle,
+                    inner_radius_ratio: ellipse.arc_data.inner_radius_ratio,
+                    translation,
+                    rotation_deg,
+                    size,
+                    visible,
+                    blend_mode,
+                    opacity,
+                    layout_element,
+                    styles,
+                })
+            }
+            ArbNodeVariant::Star => {
+                let star = entity_ref.get::<StarArbNode>()?;
+                Node::Star(StarNode {
+                    id,
+                    inner_radius_ratio: star.inner_radius_ratio,
+                    point_count: star.point_count,
+                    translation,
+                    rotation_deg,
+                    size,
+                    visible,
+                    blend_mode,
+                    opacity,
+                    layout_element,
+                    styles,
+                })
+            }
+            ArbNodeVariant::Polygon => {
+                let polygon = entity_ref.get::<PolygonArbNode>()?;
+                Node::Polygon(PolygonNode {
+                    id,
+                    point_count: polygon.point_count,
+                    translation,
+                    rotation_deg,
+                    size,
+                    visible,
+                    blend_mode,
+                    opacity,
+                    layout_element,
+                    styles,
+                })
+            }
+            ArbNodeVariant::Text => {
+                let text = entity_ref.get::<TextArbNode>()?;
+                Node::Text(TextNode {
+                    id,
+                    text: text.text.clone(),
+                    attributes: text.attributes.iter().cloned().collect(),
+                    line_wrap: text.line_wrap,
+                    horizontal_text_alignment: text.horizontal_text_alignment,
+                    vertical_text_alignment: text.vertical_text_alignment,
+                    sizing_mode: text.sizing_mode,
+                    translation,
+                    rotation_deg,
+                    size,
+                    visible,
+                    blend_mode,
+                    opacity,
+                    layout_element,
+                    styles,
+                })
+            }
+            ArbNodeVariant::Vector => {
+                let path_mixin = entity_ref.get::<PathMixin>()?;
+                Node::Vector(VectorNode {
+                    id,
+                    path: tiny_skia_path_to_string(&path_mixin.path),
+                    winding_rule: path_mixin.winding_rule,
+                    translation,
+                    rotation_deg,
+                    size,
+                    visible,
+                    blend_mode,
+                    opacity,
+                    layout_element,
+                    styles,
+                })
+            }
+            ArbNodeVariant::BooleanOperation => {
+                let boolean_operation = entity_ref.get::<BooleanOperationArbNode>()?;
+                Node::BooleanOperation(BooleanOperationNode {
+                    id,
+                    operation: boolean_operation.operation,
+                    translation,
+                    rotation_deg,
+                    size,
+                    visible,
+                    blend_mode,
+                    opacity,
+                    layout_element,
+                    styles,
+                    children: self.export_children(&entity_ref),
+                })
+            }
+        };
+
+        return Some(node);
+    }
+
+    fn export_children(&mut self, entity_ref: &EntityRef) -> Vec<ReferenceIdOrEntity> {
+        let child_entities = entity_ref
+            .get::<Children>()
+            .map(|children| {
+                children
+                    .iter()
+                    .copied()
+                    .filter(|child| self.is_exportable_node(*child))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        return child_entities
+            .into_iter()
+            .map(|child| ReferenceIdOrEntity::reference_id(self.get_entity_reference_id(child)))
+            .collect();
+    }
+
+    fn is_exportable_node(&self, entity: Entity) -> bool {
+        self.world
+            .get_entity(entity)
+            .map(|entity_ref| {
+                entity_ref.contains::<ArbNode>()
+                    && !entity_ref.contains::<Removed>()
+                    && !entity_ref.contains::<InstanceContent>()
+            })
+            .unwrap_or(false)
+    }
+
+    // =========================================================================
+    // Style
+    // =========================================================================
+
+    fn export_style(&mut self, entity: Entity) -> Option<Style> {
+        let entity_ref = self.world.get_entity(entity)?;
+        if entity_ref.contains::<Removed>() {
+            return None;
+        }
+        let ArbStyle { variant } = entity_ref.get::<ArbStyle>()?;
+
+        let id = self.entity_to_reference_id.get(&entity).cloned();
+        let visible = entity_ref
+            .get::<VisibilityMixin>()
+            .map(|VisibilityMixin(visible)| *visible)
+            .unwrap_or(true);
+        let blend_mode = entity_ref
+            .get::<BlendModeMixin>()
+            .map(|BlendModeMixin(blend_mode)| *blend_mode)
+            .unwrap_or_default();
+        let opacity = entity_ref
+            .get::<OpacityMixin>()
+            .map(|OpacityMixin(opacity)| *opacity)
+            .unwrap_or_default();
+
+        let style = match variant {
+            ArbStyleVariant::Fill => Style::Fill(FillStyle {
+                id,
+                paint_id: self.get_style_paint_id(&entity_ref)?,
+                visible,
+                blend_mode,
+                opacity,
+            }),
+            ArbStyleVariant::Stroke => {
+                let stroke = entity_ref.get::<StrokeArbStyle>()?;
+                Style::Stroke(StrokeStyle {
+                    id,
+                    width: Abs::pt(stroke.stroke.width),
+                    paint_id: self.get_style_paint_id(&entity_ref)?,
+                    alignment: stroke.alignment,
+                    cap: stroke.stroke.line_cap.into(),
+                    join: stroke.stroke.line_join.into(),
+                    miter_limit: stroke.stroke.miter_limit,
+                    dash_array: stroke
+                        .dash_array
+                        .iter()
+                        .map(|length| Abs::pt(*length))
+                        .collect(),
+                    dash_offset: Abs::pt(stroke.dash_offset),
+                    visible,
+                    blend_mode,
+                    opacity,
+                })
+            }
+            ArbStyleVariant::DropShadow => {
+                let drop_shadow = entity_ref.get::<DropShadowArbStyle>()?;
+                Style::DropShadow(DropShadowStyle {
+                    id,
+                    color: drop_shadow.color,
+                    position: drop_shadow.position,
+                    spread: drop_shadow.spread,
+                    blur: drop_shadow.blur,
+                    visible,
+                    blend_mode,
+                    opacity,
+                })
+            }
+            ArbStyleVariant::InnerShadow => {
+                let inner_shadow = entity_ref.get::<InnerShadowArbStyle>()?;
+                Style::InnerShadow(InnerShadowStyle {
+                    id,
+                    color: inner_shadow.color,
+                    position: inner_shadow.position,
+                    spread: inner_shadow.spread,
+                    blur: inner_shadow.blur,
+                    visible,
+                    blend_mode,
+                    opacity,
+                })
+            }
+            ArbStyleVariant::LayerBlur => {
+                let layer_blur = entity_ref.get::<LayerBlurArbStyle>()?;
+                Style::LayerBlur(LayerBlurStyle {
+                    id,
+                    blur: layer_blur.blur,
+                    visible,
+                })
+            }
+            ArbStyleVariant::BackgroundBlur => {
+                let background_blur = entity_ref.get::<BackgroundBlurArbStyle>()?;
+                Style::BackgroundBlur(BackgroundBlurStyle {
+                    id,
+                    blur: background_blur.blur,
+                    visible,
+                })
+            }
+        };
+
+        return Some(style);
+    }
+
+    fn get_style_paint_id(&mut self, entity_ref: &EntityRef) -> Option<ReferenceIdOrEntity> {
+        match entity_ref.get::<PaintChildMixin>() {
+            Some(PaintChildMixin(paint_entity)) => Some(ReferenceIdOrEntity::reference_id(
+                self.get_entity_reference_id(*paint_entity),
+            )),
+            None => {
+                log::warn!(
+                    "Style {:?} was not exported because it has no paint attached!",
+                    entity_ref.id()
+                );
+                None
+            }
+        }
+    }
+
+    // =========================================================================
+    // Paint
+    // =========================================================================
+
+    fn export_paint(&mut self, entity_ref: &EntityRef) -> Option<Paint> {
+        let ArbPaint { variant } = entity_ref.get::<ArbPaint>()?;
+        let id = Some(self.get_entity_reference_id(entity_ref.id()));
+
+        let paint = match variant {
+            ArbPaintVariant::Solid => {
+                let solid = entity_ref.get::<SolidArbPaint>()?;
+                Paint::Solid(SolidPaint {
+                    id,
+                    color: solid.color,
+                })
+            }
+            ArbPaintVariant::Image => {
+                let image = entity_ref.get::<ImageArbPaint>()?;
+                let image_id = match entity_ref.get::<ImageAssetMixin>() {
+                    Some(ImageAssetMixin(Some(image_id))) => *image_id,
+                    _ => {
+                        log::warn!(
+                            "Image paint {:?} was not exported because it has no image attached!",
+                            entity_ref.id()
+                        );
+                        return None;
+                    }
+                };
+                Paint::Image(ImagePaint {
+                    id,
+                    image_id: ReferenceIdOrImageId::reference_id(
+                        self.get_image_reference_id(image_id),
+                    ),
+                    scale_mode: image.scale_mode,
+                    filters: (!image.filters.is_identity()).then_some(image.filters),
+                })
+            }
+            ArbPaintVariant::Gradient => {
+                let gradient = entity_ref.get::<GradientArbPaint>()?;
+                Paint::Gradient(GradientPaint {
+                    id,
+                    variant: gradient.variant,
+                    stops: gradient.stops.iter().copied().collect(),
+                })
+            }
+        };
+
+        return Some(paint);
+    }
+
+    // =========================================================================
+    // Asset
+    // =========================================================================
+
+    fn export_assets(&mut self) -> Vec<AssetWithId> {
+        let mut assets = Vec::new();
+        let assets_res = match self.world.get_resource::<AssetsRes>() {
+            Some(assets_res) => assets_res,
+            None => return assets,
+        };
+
+        for (image_id, image) in assets_res.iter_images() {
+            assets.push(AssetWithId {
+                id: Some(self.get_image_reference_id(image_id)),
+                content: AssetContent::Binary {
+                    content: image.content.clone(),
+                },
+                content_type: match image.content_type {
+                    ImageAssetContentType::Png => AssetContentType::Png,
+                    ImageAssetContentType::Jpeg => AssetContentType::Jpeg,
+                    ImageAssetContentType::Svg => AssetContentType::Svg {
+                        width: image.width,
+                        height: image.height,
+                    },
+                    ImageAssetContentType::Unsupported(_) => AssetContentType::Unknown,
+                },
+            });
+        }
+
+        // Only fonts that were explicitly provided as asset can be exported,
+        // fonts loaded from the system aren't part of the document
+        if let Some(referencer_res) = self.world.get_resource::<ReferencerRes>() {
+            let mut font_assets = referencer_res
+                .get_reference_id_to_asset_id_map()
+                .iter()
+                .filter_map(|(reference_id, asset_id)| match asset_id {
+                    AssetId::Font(font_id) => Some((font_id, reference_id)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            font_assets.sort_by_key(|(font_id, _)| *font_id);
+
+            for (font_id, reference_id) in font_assets {
+                let maybe_content = font_id.0.first().and_then(|face_id| {
+                    assets_res
+                        .get_fonts_book()
+                        .get_db()
+                        .with_face_data(*face_id, |data, _| data.to_vec())
+                });
+                if let Some(content) = maybe_content {
+                    assets.push(AssetWithId {
+                        id: Some(reference_id.clone()),
+                        content: AssetContent::Binary { content },
+                        content_type: AssetContentType::Ttf,
+                    });
+                }
+            }
+        }
+
+        return assets;
+    }
+
+    // =========================================================================
+    // Variable
+    // =========================================================================
+
+    fn export_variable_bindings(&mut self, entity_ref: &EntityRef) -> Vec<VariableBinding> {
+        if entity_ref.contains::<InstanceContent>() {
+            return Vec::new();
+        }
+
+        match entity_ref.get::<VariableBindingsMixin>() {
+            Some(VariableBindingsMixin(bindings)) => bindings
+                .iter()
+                .map(|(property, variable_id)| VariableBinding {
+                    target_id: ReferenceIdOrEntity::reference_id(
+                        self.get_entity_reference_id(entity_ref.id()),
+                    ),
+                    property: *property,
+                    variable_id: variable_id.clone(),
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn export_variables(world: &World) -> Vec<Variable> {
+    let mut variables = match world.get_resource::<VariablesRes>() {
+        Some(variables_res) => variables_res.iter_variables().cloned().collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
+    variables.sort_by_key(|variable| variable.id.to_string());
+    return variables;
+}
+
+#[cfg(feature = "lua_scripts")]
+fn export_scripts(world: &World) -> Vec<dyn_arb_bundles::LuaScriptWithId> {
+    match world.get_resource::<dyn_arb_core::resources::lua::LuaRes>() {
+        Some(lua_res) => lua_res
+            .get_scripts()
+            .iter()
+            .map(|(id, script)| dyn_arb_bundles::LuaScriptWithId {
+                id: id.clone(),
+                source: script.source.lines().map(String::from).collect(),
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::{load, reference_id};
+
+    #[test]
+    fn test_from_world_roundtrip() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10"/>"#;
+        let dtif: DtifArtboard = serde_json::from_value(serde_json::json!({
+            "version": "V000001",
+            "size": [100, 100],
+            "viewport": { "physicalPosition": [0, 0], "physicalSize": [100, 100] },
+            "nodes": [
+                { "type": "Frame", "id": "root", "size": [100, 100],
+                    "styles": [{ "type": "Fill", "id": "background", "paintId": reference_id("image") }],
+                    "children": [reference_id("rect"), reference_id("ellipse")] },
+                { "type": "Rectangle", "id": "rect", "translation": [10, 20], "size": [30, 40], "styles": [
+                    { "type": "Fill", "paintId": reference_id("solid"), "opacity": 0.5 },
+                    { "type": "Stroke", "width": 2, "paintId": reference_id("gradient"),
+                        "cap": "Round", "dashArray": [4, 2], "dashOffset": 1 },
+                    { "type": "DropShadow", "color": [0, 0, 0], "position": [2, 2], "blur": 4 },
+                    { "type": "LayerBlur", "blur": 3 }
+                ] },
+                { "type": "Ellipse", "id": "ellipse", "size": [10, 10] }
+            ],
+            "paints": [
+                { "type": "Solid", "id": "solid", "color": [255, 0, 0] },
+                { "type": "Image", "id": "image", "imageId": reference_id("logo") },
+                { "type": "Gradient", "id": "gradient", "variant": { "type": "Linear" }, "stops": [
+                    { "position": 0, "color": [0, 0, 255] },
+                    { "position": 1, "color": [0, 255, 0] }
+                ] }
+            ],
+            "assets": [{
+                "id": "logo",
+                "content": { "type": "Binary", "content": svg.as_bytes() },
+                "contentType": { "type": "Svg", "width": 10, "height": 10 }
+            }]
+        }))
+        .unwrap();
+
+        let app = load(&dtif);
+        let exported = DtifArtboard::from_world(&app.world);
+
+        assert_eq!(
+            serde_json::to_value(&exported).unwrap(),
+            serde_json::to_value(&dtif).unwrap()
+        );
+    }
+}
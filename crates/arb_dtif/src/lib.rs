@@ -1,14 +1,28 @@
+#[cfg(feature = "specta_support")]
+pub mod diff;
+pub mod document;
+mod export;
+#[cfg(feature = "specta_support")]
+pub mod migration;
+#[cfg(feature = "package")]
+pub mod package;
+#[cfg(all(test, feature = "specta_support"))]
+mod test_utils;
+pub mod validation;
+
 use bevy_ecs::world::World;
 use dyn_arb_bundles::{
     events::{
-        CoreInputEvent, CreateAssetInputEvent, CreateNodeInputEvent, CreatePaintInputEvent,
-        InputEvent,
+        BindVariableInputEvent, CoreInputEvent, CreateAssetInputEvent, CreateNodeInputEvent,
+        CreatePaintInputEvent, CreateVariableInputEvent, InputEvent, UpdateVariableModeInputEvent,
     },
     properties::{ArbVersion, Viewport},
-    AssetWithId, Node, Paint,
+    AssetWithId, Node, Paint, Variable, VariableBinding,
 };
 use dyn_utils::properties::size::Size;
 
+pub use validation::{validate, validate_document};
+
 // Note: Cannot be inlined because of Utoipa lack of renaming Schema references
 // https://github.com/juhaku/utoipa/issues/894#issuecomment-2164362189
 #[cfg(feature = "lua_scripts")]
@@ -32,6 +46,13 @@ pub struct DtifArtboard {
     /// The viewport of the artboard.
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub viewport: Option<Viewport>,
+    /// Whether the layout keeps sub-pixel precision
+    /// instead of rounding positions and sizes to whole pixels.
+    #[cfg_attr(
+        feature = "specta_support",
+        serde(default = "dyn_utils::serde::default_as_true")
+    )]
+    pub sub_pixel_layout: bool,
     /// A list of nodes.
     pub nodes: Vec<Node>,
     /// A list of paints.
@@ -40,6 +61,15 @@ pub struct DtifArtboard {
     /// A list of assets.
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub assets: Vec<AssetWithId>,
+    /// A list of variables (design tokens).
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variables: Vec<Variable>,
+    /// The active variable mode (e.g. "light" or "dark").
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variable_mode: Option<String>,
+    /// A list of properties bound to variables.
+    #[cfg_attr(feature = "specta_support", serde(default))]
+    pub variable_bindings: Vec<VariableBinding>,
     /// A list of input events.
     #[cfg_attr(feature = "specta_support", serde(default))]
     pub events: Vec<CoreInputEvent>,
@@ -55,6 +85,13 @@ impl DtifArtboard {
             world.send_event(CreateAssetInputEvent { asset });
         }
 
+        for variable in std::mem::take(&mut self.variables) {
+            world.send_event(CreateVariableInputEvent { variable });
+        }
+        if let Some(mode) = self.variable_mode.take() {
+            world.send_event(UpdateVariableModeInputEvent { mode: Some(mode) });
+        }
+
         for node in std::mem::take(&mut self.nodes) {
             world.send_event(CreateNodeInputEvent { node });
         }
@@ -63,6 +100,14 @@ impl DtifArtboard {
             world.send_event(CreatePaintInputEvent { paint });
         }
 
+        for binding in std::mem::take(&mut self.variable_bindings) {
+            world.send_event(BindVariableInputEvent {
+                target_id: binding.target_id,
+                property: binding.property,
+                variable_id: Some(binding.variable_id),
+            });
+        }
+
         for event in std::mem::take(&mut self.events) {
             event.send_into_world(world);
         }
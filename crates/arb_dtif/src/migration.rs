@@ -0,0 +1,215 @@
+use dyn_arb_bundles::properties::ArbVersion;
+use serde_json::Value;
+
+/// Upgrade step from one schema version to the directly following one.
+///
+/// Generic over the version so that chaining can be tested
+/// independently of the versions released so far.
+struct Migration<V = ArbVersion> {
+    from: V,
+    to: V,
+    migrate: fn(&mut Value) -> Result<(), MigrationError>,
+}
+
+/// Registered upgrade steps, ordered by `from` version.
+///
+/// To introduce a new schema version, add the variant to `ArbVersion`,
+/// bump `ArbVersion::LATEST` and append a step from the previous version here.
+const MIGRATIONS: &[Migration] = &[];
+
+/// Upgrades a raw DTIF document in place to `ArbVersion::LATEST`
+/// by chaining all migrations starting from the version specified in the document.
+///
+/// Documents without version are treated as the oldest version.
+/// Both artboard and document (multi-artboard) DTIFs are passed through here,
+/// so migrations have to handle both shapes.
+///
+/// Returns the version the document was migrated from.
+pub fn migrate(dtif: &mut Value) -> Result<ArbVersion, MigrationError> {
+    let document = dtif.as_object_mut().ok_or(MigrationError::NotAnObject)?;
+
+    let from_version = match document.get("version") {
+        None | Some(Value::Null) => ArbVersion::default(),
+        Some(value) => serde_json::from_value::<ArbVersion>(value.clone())
+            .map_err(|_| MigrationError::UnsupportedVersion(value.to_string()))?,
+    };
+
+    apply_migrations(dtif, from_version, ArbVersion::LATEST, MIGRATIONS)?;
+
+    if let Some(document) = dtif.as_object_mut() {
+        document.insert(
+            String::from("version"),
+            serde_json::to_value(ArbVersion::LATEST)
+                .map_err(|err| MigrationError::InvalidDocument(err.to_string()))?,
+        );
+    }
+
+    return Ok(from_version);
+}
+
+/// Chains the migrations starting at `from` until the `to` version is reached.
+fn apply_migrations<V: Copy + PartialOrd + std::fmt::Debug>(
+    dtif: &mut Value,
+    from: V,
+    to: V,
+    migrations: &[Migration<V>],
+) -> Result<(), MigrationError> {
+    let mut version = from;
+    while version < to {
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.from == version)
+            .ok_or_else(|| MigrationError::MissingMigration(format!("{:?}", version)))?;
+
+        (migration.migrate)(dtif).map_err(|err| MigrationError::Failed {
+            from: format!("{:?}", migration.from),
+            to: format!("{:?}", migration.to),
+            reason: err.to_string(),
+        })?;
+        version = migration.to;
+    }
+
+    return Ok(());
+}
+
+#[derive(Debug)]
+pub enum MigrationError {
+    NotAnObject,
+    UnsupportedVersion(String),
+    MissingMigration(String),
+    InvalidDocument(String),
+    Failed {
+        from: String,
+        to: String,
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotAnObject => write!(f, "DTIF must be a JSON object!"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "DTIF version {} is not supported!", version)
+            }
+            Self::MissingMigration(version) => {
+                write!(f, "No migration registered from version {}!", version)
+            }
+            Self::InvalidDocument(reason) => write!(f, "DTIF is invalid: {}", reason),
+            Self::Failed { from, to, reason } => write!(
+                f,
+                "Failed to migrate DTIF from version {} to {}: {}",
+                from, to, reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_without_version() {
+        let mut dtif = serde_json::json!({ "size": [100, 100], "nodes": [] });
+
+        let from_version = migrate(&mut dtif).unwrap();
+
+        assert_eq!(from_version, ArbVersion::V000001);
+        assert_eq!(dtif["version"], serde_json::json!("V000001"));
+    }
+
+    #[derive(Debug, PartialEq, PartialOrd, Copy, Clone)]
+    enum TestVersion {
+        V1,
+        V2,
+        V3,
+    }
+
+    const TEST_MIGRATIONS: &[Migration<TestVersion>] = &[
+        Migration {
+            from: TestVersion::V1,
+            to: TestVersion::V2,
+            migrate: |dtif| {
+                // Renamed "color" to "fill"
+                let color = dtif["color"].take();
+                dtif["fill"] = color;
+                return Ok(());
+            },
+        },
+        Migration {
+            from: TestVersion::V2,
+            to: TestVersion::V3,
+            migrate: |dtif| {
+                // Wrapped "fill" into a list of "fills"
+                let fill = dtif["fill"].take();
+                dtif["fills"] = Value::Array(vec![fill]);
+                return Ok(());
+            },
+        },
+    ];
+
+    #[test]
+    fn test_apply_migrations_chained() {
+        let mut dtif = serde_json::json!({ "color": "red" });
+
+        apply_migrations(&mut dtif, TestVersion::V1, TestVersion::V3, TEST_MIGRATIONS).unwrap();
+
+        assert_eq!(dtif["fills"], serde_json::json!(["red"]));
+    }
+
+    #[test]
+    fn test_apply_migrations_from_intermediate_version() {
+        let mut dtif = serde_json::json!({ "fill": "red" });
+
+        apply_migrations(&mut dtif, TestVersion::V2, TestVersion::V3, TEST_MIGRATIONS).unwrap();
+
+        assert_eq!(dtif["fills"], serde_json::json!(["red"]));
+    }
+
+    #[test]
+    fn test_apply_migrations_missing_step() {
+        let mut dtif = serde_json::json!({ "color": "red" });
+
+        let result = apply_migrations(
+            &mut dtif,
+            TestVersion::V1,
+            TestVersion::V3,
+            &TEST_MIGRATIONS[..1],
+        );
+
+        assert!(matches!(
+            result,
+            Err(MigrationError::MissingMigration(version)) if version == "V2"
+        ));
+    }
+
+    #[test]
+    fn test_apply_migrations_failed_step() {
+        let migrations: &[Migration<TestVersion>] = &[Migration {
+            from: TestVersion::V1,
+            to: TestVersion::V2,
+            migrate: |_| Err(MigrationError::InvalidDocument(String::from("no color"))),
+        }];
+        let mut dtif = serde_json::json!({});
+
+        let result = apply_migrations(&mut dtif, TestVersion::V1, TestVersion::V2, migrations);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Failed to migrate DTIF from version V1 to V2: DTIF is invalid: no color"
+        );
+    }
+
+    #[test]
+    fn test_migrate_unsupported_version() {
+        let mut dtif = serde_json::json!({ "version": "V999999", "size": [100, 100] });
+
+        assert!(matches!(
+            migrate(&mut dtif),
+            Err(MigrationError::UnsupportedVersion(_))
+        ));
+    }
+}
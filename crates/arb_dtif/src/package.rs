@@ -0,0 +1,441 @@
+use crate::{document::DtifDocument, DtifArtboard};
+use dyn_arb_asset::asset::{AssetContent, AssetContentType};
+use dyn_arb_bundles::AssetWithId;
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Write},
+};
+use zip::{result::ZipError, write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+/// Name of the file containing the DTIF (artboard or document) in a package.
+pub const DOCUMENT_FILE_NAME: &str = "document.json";
+
+/// Directory containing the raw asset files in a package.
+pub const ASSETS_DIRECTORY: &str = "assets/";
+
+/// Limits for the decompressed content of a package,
+/// as a small zip can decompress to an arbitrary amount of data.
+#[derive(Debug, Copy, Clone)]
+pub struct PackageLimits {
+    /// Maximum decompressed size of a single file in bytes.
+    pub max_file_size: u64,
+    /// Maximum decompressed size of all read files together in bytes.
+    pub max_total_size: u64,
+}
+
+impl Default for PackageLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: 32 * 1024 * 1024,
+            max_total_size: 128 * 1024 * 1024,
+        }
+    }
+}
+
+/// Packaged DTIF (zip) holding the DTIF as `document.json`
+/// and the content of its assets as raw files in `assets/`,
+/// instead of inlining them as (huge) JSON number arrays.
+///
+/// Packaged assets are referenced from the DTIF via their relative path
+/// (e.g. `{ "type": "Url", "url": "assets/logo.png" }`),
+/// so the contained `document.json` remains a valid DTIF on its own.
+#[derive(Debug, Clone, Default)]
+pub struct DtifPackage {
+    /// The raw (not yet migrated) DTIF artboard or document.
+    pub dtif: serde_json::Value,
+    /// The raw asset files keyed by their path in the package.
+    pub files: HashMap<String, Vec<u8>>,
+}
+
+impl DtifPackage {
+    pub fn from_artboard(mut dtif: DtifArtboard) -> Result<Self, PackageError> {
+        let files = pack_assets(&mut dtif.assets);
+        return Ok(Self {
+            dtif: serde_json::to_value(&dtif)
+                .map_err(|err| PackageError::InvalidDocument(err.to_string()))?,
+            files,
+        });
+    }
+
+    pub fn from_document(mut document: DtifDocument) -> Result<Self, PackageError> {
+        let files = pack_assets(&mut document.assets);
+        return Ok(Self {
+            dtif: serde_json::to_value(&document)
+                .map_err(|err| PackageError::InvalidDocument(err.to_string()))?,
+            files,
+        });
+    }
+
+    /// Checks whether the bytes start with the zip local file header signature.
+    pub fn is_package(bytes: &[u8]) -> bool {
+        bytes.starts_with(b"PK\x03\x04")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PackageError> {
+        Self::from_bytes_with_limits(bytes, PackageLimits::default())
+    }
+
+    pub fn from_bytes_with_limits(
+        bytes: &[u8],
+        limits: PackageLimits,
+    ) -> Result<Self, PackageError> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+        let mut remaining_size = limits.max_total_size;
+
+        let dtif = {
+            let file = archive
+                .by_name(DOCUMENT_FILE_NAME)
+                .map_err(|_| PackageError::MissingDocument)?;
+            let size = file.size();
+            let content = read_file(file, DOCUMENT_FILE_NAME, size, &limits, &mut remaining_size)?;
+            serde_json::from_slice(&content)
+                .map_err(|err| PackageError::InvalidDocument(err.to_string()))?
+        };
+
+        let mut files = HashMap::new();
+        for index in 0..archive.len() {
+            let file = archive.by_index(index)?;
+            // Skip entries that would escape the package directory if extracted
+            if !file.is_file()
+                || !file.name().starts_with(ASSETS_DIRECTORY)
+                || file.enclosed_name().is_none()
+            {
+                continue;
+            }
+            let (name, size) = (file.name().to_string(), file.size());
+            let content = read_file(file, &name, size, &limits, &mut remaining_size)?;
+            files.insert(name, content);
+        }
+
+        return Ok(Self { dtif, files });
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PackageError> {
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip_writer.start_file(DOCUMENT_FILE_NAME, options)?;
+        zip_writer.write_all(
+            &serde_json::to_vec(&self.dtif)
+                .map_err(|err| PackageError::InvalidDocument(err.to_string()))?,
+        )?;
+
+        // Sort files to keep the output deterministic
+        let mut paths: Vec<&String> = self.files.keys().collect();
+        paths.sort();
+        for path in paths {
+            // Already compressed formats (e.g. PNG, JPEG) don't benefit from deflate
+            zip_writer.start_file(
+                path.as_str(),
+                SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+            )?;
+            zip_writer.write_all(&self.files[path])?;
+        }
+
+        return Ok(zip_writer.finish()?.into_inner());
+    }
+
+    /// Replaces references to packaged asset files with their binary content.
+    pub fn resolve_assets(&self, assets: &mut [AssetWithId]) -> Result<(), PackageError> {
+        for asset in assets.iter_mut() {
+            let maybe_content = match &asset.content {
+                AssetContent::Url { url } if url.starts_with(ASSETS_DIRECTORY) => Some(
+                    self.files
+                        .get(url)
+                        .cloned()
+                        .ok_or_else(|| PackageError::MissingAsset(url.clone()))?,
+                ),
+                _ => None,
+            };
+            if let Some(content) = maybe_content {
+                asset.content = AssetContent::Binary { content };
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/// Reads the decompressed content of the file without exceeding the limits,
+/// regardless of the (untrusted) size declared in the zip header.
+fn read_file(
+    file: impl Read,
+    name: &str,
+    declared_size: u64,
+    limits: &PackageLimits,
+    remaining_size: &mut u64,
+) -> Result<Vec<u8>, PackageError> {
+    let limit = limits.max_file_size.min(*remaining_size);
+    if declared_size > limit {
+        return Err(PackageError::TooLarge(name.to_string()));
+    }
+
+    let mut content = Vec::new();
+    file.take(limit + 1).read_to_end(&mut content)?;
+    if content.len() as u64 > limit {
+        return Err(PackageError::TooLarge(name.to_string()));
+    }
+    *remaining_size -= content.len() as u64;
+
+    return Ok(content);
+}
+
+/// Moves the binary content of the assets into package files
+/// and references them by their path in the package instead.
+fn pack_assets(assets: &mut [AssetWithId]) -> HashMap<String, Vec<u8>> {
+    let mut files = HashMap::new();
+
+    for (index, asset) in assets.iter_mut().enumerate() {
+        if let AssetContent::Binary { content } = &mut asset.content {
+            let name = match &asset.id {
+                Some(id) => to_file_name(&id.to_string()),
+                None => index.to_string(),
+            };
+            let mut path = format!(
+                "{}{}.{}",
+                ASSETS_DIRECTORY,
+                name,
+                to_file_extension(&asset.content_type)
+            );
+            // Different ids can map to the same file name (e.g. 'a/b' and 'a_b')
+            if files.contains_key(&path) {
+                path = format!(
+                    "{}{}-{}.{}",
+                    ASSETS_DIRECTORY,
+                    name,
+                    index,
+                    to_file_extension(&asset.content_type)
+                );
+            }
+            files.insert(path.clone(), std::mem::take(content));
+            asset.content = AssetContent::Url { url: path };
+        }
+    }
+
+    return files;
+}
+
+/// Escapes the reference id for use as file name,
+/// as it can contain path separators (e.g. '../logo') or other reserved characters.
+fn to_file_name(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn to_file_extension(content_type: &AssetContentType) -> &'static str {
+    match content_type {
+        AssetContentType::Jpeg => "jpg",
+        AssetContentType::Png => "png",
+        AssetContentType::Svg { .. } => "svg",
+        AssetContentType::Ttf => "ttf",
+        AssetContentType::Unknown => "bin",
+    }
+}
+
+#[derive(Debug)]
+pub enum PackageError {
+    Zip(ZipError),
+    Io(std::io::Error),
+    MissingDocument,
+    InvalidDocument(String),
+    MissingAsset(String),
+    TooLarge(String),
+}
+
+impl std::fmt::Display for PackageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Zip(err) => write!(f, "DTIF package is not a valid zip archive: {}", err),
+            Self::Io(err) => write!(f, "Failed to read DTIF package: {}", err),
+            Self::MissingDocument => write!(
+                f,
+                "DTIF package doesn't contain a '{}'!",
+                DOCUMENT_FILE_NAME
+            ),
+            Self::InvalidDocument(reason) => write!(f, "DTIF is invalid: {}", reason),
+            Self::MissingAsset(path) => {
+                write!(f, "DTIF package doesn't contain the asset '{}'!", path)
+            }
+            Self::TooLarge(path) => {
+                write!(f, "File '{}' of the DTIF package is too large!", path)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PackageError {}
+
+impl From<ZipError> for PackageError {
+    fn from(err: ZipError) -> Self {
+        Self::Zip(err)
+    }
+}
+
+impl From<std::io::Error> for PackageError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyn_arb_bundles::reference_id::ReferenceId;
+
+    #[test]
+    fn test_package_roundtrip() {
+        let mut dtif: DtifArtboard = serde_json::from_value(serde_json::json!({
+            "size": [100, 100],
+            "nodes": []
+        }))
+        .unwrap();
+        dtif.assets.push(AssetWithId {
+            id: Some(ReferenceId::new(String::from("logo"))),
+            content: AssetContent::Binary {
+                content: vec![1, 2, 3],
+            },
+            content_type: AssetContentType::Png,
+        });
+
+        let bytes = DtifPackage::from_artboard(dtif)
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        assert!(DtifPackage::is_package(&bytes));
+
+        let package = DtifPackage::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            package.dtif["assets"][0]["content"],
+            serde_json::json!({ "type": "Url", "url": "assets/logo.png" })
+        );
+
+        let mut dtif: DtifArtboard = serde_json::from_value(package.dtif.clone()).unwrap();
+        package.resolve_assets(&mut dtif.assets).unwrap();
+        assert!(matches!(
+            &dtif.assets[0].content,
+            AssetContent::Binary { content } if content == &vec![1, 2, 3]
+        ));
+    }
+
+    fn png_asset(id: &str, content: Vec<u8>) -> AssetWithId {
+        AssetWithId {
+            id: Some(ReferenceId::new(String::from(id))),
+            content: AssetContent::Binary { content },
+            content_type: AssetContentType::Png,
+        }
+    }
+
+    fn empty_dtif() -> DtifArtboard {
+        serde_json::from_value(serde_json::json!({ "size": [100, 100], "nodes": [] })).unwrap()
+    }
+
+    #[test]
+    fn test_escape_asset_file_names() {
+        let mut dtif = empty_dtif();
+        dtif.assets.push(png_asset("../../etc/logo", vec![1]));
+        dtif.assets.push(png_asset("a/b", vec![2]));
+        dtif.assets.push(png_asset("a_b", vec![3]));
+
+        let package = DtifPackage::from_artboard(dtif).unwrap();
+        let mut paths: Vec<&str> = package.files.keys().map(|path| path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "assets/______etc_logo.png",
+                "assets/a_b-2.png",
+                "assets/a_b.png"
+            ]
+        );
+
+        let package = DtifPackage::from_bytes(&package.to_bytes().unwrap()).unwrap();
+        let mut dtif: DtifArtboard = serde_json::from_value(package.dtif.clone()).unwrap();
+        package.resolve_assets(&mut dtif.assets).unwrap();
+        let contents: Vec<&AssetContent> = dtif.assets.iter().map(|asset| &asset.content).collect();
+        assert!(matches!(
+            contents[..],
+            [
+                AssetContent::Binary { content: a },
+                AssetContent::Binary { content: b },
+                AssetContent::Binary { content: c }
+            ] if a == &vec![1] && b == &vec![2] && c == &vec![3]
+        ));
+    }
+
+    #[test]
+    fn test_skip_files_outside_of_package() {
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        zip_writer
+            .start_file(DOCUMENT_FILE_NAME, SimpleFileOptions::default())
+            .unwrap();
+        zip_writer.write_all(b"{}").unwrap();
+        zip_writer
+            .start_file("assets/../../logo.png", SimpleFileOptions::default())
+            .unwrap();
+        zip_writer.write_all(&[1, 2, 3]).unwrap();
+        let bytes = zip_writer.finish().unwrap().into_inner();
+
+        let package = DtifPackage::from_bytes(&bytes).unwrap();
+        assert!(package.files.is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut dtif = empty_dtif();
+        dtif.assets.push(png_asset("a", vec![0; 10_000]));
+        dtif.assets.push(png_asset("b", vec![0; 10_000]));
+        let package = DtifPackage::from_artboard(dtif).unwrap();
+        let document_size = serde_json::to_vec(&package.dtif).unwrap().len() as u64;
+        let bytes = package.to_bytes().unwrap();
+        let read = |max_file_size: u64, max_total_size: u64| {
+            DtifPackage::from_bytes_with_limits(
+                &bytes,
+                PackageLimits {
+                    max_file_size,
+                    max_total_size,
+                },
+            )
+        };
+
+        assert!(read(10_000, document_size + 20_000).is_ok());
+
+        // Single file exceeds the limit
+        assert!(matches!(
+            read(5_000, u64::MAX),
+            Err(PackageError::TooLarge(path)) if path == "assets/a.png"
+        ));
+
+        // Files exceed the limit together
+        assert!(matches!(
+            read(10_000, document_size + 15_000),
+            Err(PackageError::TooLarge(path)) if path == "assets/b.png"
+        ));
+    }
+
+    #[test]
+    fn test_limit_decompressed_size() {
+        // Highly compressible content that is much larger than the package
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip_writer.start_file(DOCUMENT_FILE_NAME, options).unwrap();
+        zip_writer.write_all(&vec![b' '; 1024 * 1024]).unwrap();
+        let bytes = zip_writer.finish().unwrap().into_inner();
+        assert!(bytes.len() < 16 * 1024);
+
+        let result = DtifPackage::from_bytes_with_limits(
+            &bytes,
+            PackageLimits {
+                max_file_size: 16 * 1024,
+                max_total_size: u64::MAX,
+            },
+        );
+        assert!(matches!(result, Err(PackageError::TooLarge(path)) if path == DOCUMENT_FILE_NAME));
+    }
+}
@@ -0,0 +1,22 @@
+use crate::DtifArtboard;
+use bevy_app::App;
+use dyn_arb_core::ArbCorePlugin;
+
+/// Reference to the element with the given id in DTIF JSON.
+pub fn reference_id(id: &str) -> serde_json::Value {
+    serde_json::json!({ "type": "ReferenceId", "referenceId": id })
+}
+
+/// Creates an app with the core plugin and loads the artboard into it.
+pub fn load(dtif: &DtifArtboard) -> App {
+    let mut app = App::new();
+    app.add_plugins(ArbCorePlugin {
+        version: dtif.version,
+        size: dtif.size,
+        viewport: dtif.viewport,
+        sub_pixel_layout: dtif.sub_pixel_layout,
+    });
+    dtif.clone().send_into_world(&mut app.world);
+    app.update();
+    return app;
+}
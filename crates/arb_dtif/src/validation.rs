@@ -0,0 +1,982 @@
+use crate::{document::DtifDocument, DtifArtboard};
+use dyn_arb_asset::asset::AssetContentType;
+use dyn_arb_bundles::{
+    mapper::string_to_tiny_skia_path,
+    reference_id::{ReferenceId, ReferenceIdOrEntity, ReferenceIdOrImageId},
+    Node, Paint, Style, Variable, VariableBinding,
+};
+use dyn_utils::units::abs::Abs;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type),
+    serde(rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub struct Diagnostic {
+    /// JSON path to the offending value (e.g. `$.nodes[2].styles[0].paintId`).
+    pub path: String,
+    pub severity: DiagnosticSeverity,
+    pub code: DiagnosticCode,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(path: String, code: DiagnosticCode, message: String) -> Self {
+        Self {
+            path,
+            severity: DiagnosticSeverity::Error,
+            code,
+            message,
+        }
+    }
+
+    fn warning(path: String, code: DiagnosticCode, message: String) -> Self {
+        Self {
+            path,
+            severity: DiagnosticSeverity::Warning,
+            code,
+            message,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == DiagnosticSeverity::Error
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} at '{}': {}",
+            self.severity.as_str(),
+            self.code.as_str(),
+            self.path,
+            self.message
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum DiagnosticSeverity {
+    /// The DTIF can't be loaded as intended.
+    Error,
+    /// The DTIF can be loaded but likely doesn't do what was intended.
+    Warning,
+}
+
+impl DiagnosticSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Error => "ERROR",
+            DiagnosticSeverity::Warning => "WARNING",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "specta_support",
+    derive(serde::Serialize, serde::Deserialize, specta::Type)
+)]
+#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
+pub enum DiagnosticCode {
+    /// The same reference id is used by more than one element.
+    DuplicateReferenceId,
+    /// A reference id doesn't resolve to any element of the DTIF.
+    UnresolvedReference,
+    /// A reference id resolves to an element of the wrong kind
+    /// (e.g. a fill whose paint id points to a node).
+    ReferenceKindMismatch,
+    /// A node references itself as child, directly or through its descendants.
+    SelfReference,
+    /// A node is referenced as child by more than one frame.
+    MultipleParents,
+    /// A text attribute interval has a start after its end.
+    InvalidTextAttributeInterval,
+    /// A text attribute interval lies outside of the text.
+    TextAttributeIntervalOutOfBounds,
+    /// A vector path couldn't be parsed.
+    InvalidVectorPath,
+    /// A variable is bound to a property that doesn't accept values of its type.
+    VariableTypeMismatch,
+    /// A stroke dash array can't be drawn as specified
+    /// (e.g. it contains negative values and is drawn as solid stroke instead).
+    InvalidDashArray,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::DuplicateReferenceId => "DUPLICATE_REFERENCE_ID",
+            DiagnosticCode::UnresolvedReference => "UNRESOLVED_REFERENCE",
+            DiagnosticCode::ReferenceKindMismatch => "REFERENCE_KIND_MISMATCH",
+            DiagnosticCode::SelfReference => "SELF_REFERENCE",
+            DiagnosticCode::MultipleParents => "MULTIPLE_PARENTS",
+            DiagnosticCode::InvalidTextAttributeInterval => "INVALID_TEXT_ATTRIBUTE_INTERVAL",
+            DiagnosticCode::TextAttributeIntervalOutOfBounds => {
+                "TEXT_ATTRIBUTE_INTERVAL_OUT_OF_BOUNDS"
+            }
+            DiagnosticCode::InvalidVectorPath => "INVALID_VECTOR_PATH",
+            DiagnosticCode::VariableTypeMismatch => "VARIABLE_TYPE_MISMATCH",
+            DiagnosticCode::InvalidDashArray => "INVALID_DASH_ARRAY",
+        }
+    }
+}
+
+/// Validates the DTIF without loading it into a world
+/// and reports every problem found rather than stopping at the first one.
+///
+/// Entity references can't be resolved statically and are therefore not validated.
+pub fn validate(dtif: &DtifArtboard) -> Vec<Diagnostic> {
+    let mut validator = Validator::new(String::from("$.nodes"));
+
+    validator.validate_artboard(dtif);
+    validator.validate_variable_bindings(dtif.variable_bindings.iter().enumerate(), dtif);
+
+    return validator.diagnostics;
+}
+
+/// Validates the artboards with the given indices of a DTIF document like [`validate`].
+///
+/// Nodes are reported at their path within the document (e.g. `$.artboards[1].nodes[0]`)
+/// and problems of shared elements (e.g. paints) only once, even if they affect multiple artboards.
+pub fn validate_document(document: &DtifDocument, indices: &[usize]) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    for index in indices {
+        let (artboard, dtif) = match (document.artboards.get(*index), document.to_artboard(*index))
+        {
+            (Some(artboard), Some(dtif)) => (artboard, dtif),
+            _ => continue,
+        };
+        let mut validator = Validator::new(format!("$.artboards[{}].nodes", index));
+
+        validator.validate_artboard(&dtif);
+        validator.validate_variable_bindings(
+            document
+                .get_variable_binding_indices(artboard)
+                .into_iter()
+                .map(|binding_index| (binding_index, &document.variable_bindings[binding_index])),
+            &dtif,
+        );
+
+        for diagnostic in validator.diagnostics {
+            let is_duplicate = diagnostics
+                .iter()
+                .any(|other| other.path == diagnostic.path && other.code == diagnostic.code);
+            if !is_duplicate {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    return diagnostics;
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum ElementKind {
+    Node,
+    Component,
+    Style,
+    Paint,
+    ImageAsset,
+    FontAsset,
+    Asset,
+    Variable,
+    #[cfg(feature = "lua_scripts")]
+    Script,
+}
+
+impl ElementKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ElementKind::Node => "node",
+            ElementKind::Component => "component",
+            ElementKind::Style => "style",
+            ElementKind::Paint => "paint",
+            ElementKind::ImageAsset => "image asset",
+            ElementKind::FontAsset => "font asset",
+            ElementKind::Asset => "asset",
+            ElementKind::Variable => "variable",
+            #[cfg(feature = "lua_scripts")]
+            ElementKind::Script => "script",
+        }
+    }
+}
+
+struct Validator {
+    /// JSON path to the nodes of the validated artboard.
+    nodes_path: String,
+    reference_ids: HashMap<ReferenceId, (ElementKind, String)>,
+    child_to_parent_path: HashMap<ReferenceId, String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator {
+    fn new(nodes_path: String) -> Self {
+        Self {
+            nodes_path,
+            reference_ids: HashMap::new(),
+            child_to_parent_path: HashMap::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Validates the nodes and paints of the artboard.
+    fn validate_artboard(&mut self, dtif: &DtifArtboard) {
+        self.collect_reference_ids(dtif);
+
+        for (index, node) in dtif.nodes.iter().enumerate() {
+            self.validate_node(node, &format!("{}[{}]", self.nodes_path, index));
+        }
+        self.validate_hierarchy(dtif);
+
+        for (index, paint) in dtif.paints.iter().enumerate() {
+            self.validate_paint(paint, &format!("$.paints[{}]", index));
+        }
+    }
+
+    // =========================================================================
+    // Reference Ids
+    // =========================================================================
+
+    fn collect_reference_ids(&mut self, dtif: &DtifArtboard) {
+        for (index, node) in dtif.nodes.iter().enumerate() {
+            let path = format!("{}[{}]", self.nodes_path, index);
+            let (maybe_id, styles) = node_id_and_styles(node);
+            let kind = match node {
+                Node::Component(_) => ElementKind::Component,
+                _ => ElementKind::Node,
+            };
+            self.register_reference_id(maybe_id.as_ref(), kind, &path);
+
+            for (style_index, style) in styles.iter().enumerate() {
+                let maybe_style_id = match style {
+                    Style::Fill(style) => style.id.as_ref(),
+                    Style::Stroke(style) => style.id.as_ref(),
+                    Style::DropShadow(style) => style.id.as_ref(),
+                    Style::InnerShadow(style) => style.id.as_ref(),
+                    Style::LayerBlur(style) => style.id.as_ref(),
+                    Style::BackgroundBlur(style) => style.id.as_ref(),
+                };
+                self.register_reference_id(
+                    maybe_style_id,
+                    ElementKind::Style,
+                    &format!("{}.styles[{}]", path, style_index),
+                );
+            }
+        }
+
+        for (index, paint) in dtif.paints.iter().enumerate() {
+            let maybe_id = match paint {
+                Paint::Solid(paint) => paint.id.as_ref(),
+                Paint::Image(paint) => paint.id.as_ref(),
+                Paint::Gradient(paint) => paint.id.as_ref(),
+            };
+            self.register_reference_id(
+                maybe_id,
+                ElementKind::Paint,
+                &format!("$.paints[{}]", index),
+            );
+        }
+
+        for (index, asset) in dtif.assets.iter().enumerate() {
+            let kind = match asset.content_type {
+                AssetContentType::Jpeg | AssetContentType::Png | AssetContentType::Svg { .. } => {
+                    ElementKind::ImageAsset
+                }
+                AssetContentType::Ttf => ElementKind::FontAsset,
+                AssetContentType::Unknown => ElementKind::Asset,
+            };
+            self.register_reference_id(asset.id.as_ref(), kind, &format!("$.assets[{}]", index));
+        }
+
+        for (index, variable) in dtif.variables.iter().enumerate() {
+            self.register_reference_id(
+                Some(&variable.id),
+                ElementKind::Variable,
+                &format!("$.variables[{}]", index),
+            );
+        }
+
+        #[cfg(feature = "lua_scripts")]
+        for (index, script) in dtif.scripts.iter().enumerate() {
+            self.register_reference_id(
+                Some(&script.id),
+                ElementKind::Script,
+                &format!("$.scripts[{}]", index),
+            );
+        }
+    }
+
+    fn register_reference_id(
+        &mut self,
+        maybe_id: Option<&ReferenceId>,
+        kind: ElementKind,
+        path: &str,
+    ) {
+        let id = match maybe_id {
+            Some(id) => id,
+            None => return,
+        };
+
+        if let Some((_, first_path)) = self.reference_ids.get(id) {
+            self.diagnostics.push(Diagnostic::error(
+                format!("{}.id", path),
+                DiagnosticCode::DuplicateReferenceId,
+                format!("Reference id '{}' is already used at '{}'.", id, first_path),
+            ));
+        } else {
+            self.reference_ids
+                .insert(id.clone(), (kind, path.to_string()));
+        }
+    }
+
+    /// Checks that the reference id resolves to an element of one of the expected kinds.
+    fn validate_reference_id(
+        &mut self,
+        id: &ReferenceId,
+        expected_kinds: &[ElementKind],
+        path: String,
+    ) {
+        match self.reference_ids.get(id) {
+            Some((kind, _)) if expected_kinds.contains(kind) => {}
+            Some((kind, target_path)) => {
+                let message = format!(
+                    "Reference id '{}' points to a {} at '{}' but a {} was expected.",
+                    id,
+                    kind.as_str(),
+                    target_path,
+                    expected_kinds
+                        .iter()
+                        .map(|kind| kind.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" or ")
+                );
+                self.diagnostics.push(Diagnostic::error(
+                    path,
+                    DiagnosticCode::ReferenceKindMismatch,
+                    message,
+                ));
+            }
+            None => self.diagnostics.push(Diagnostic::error(
+                path,
+                DiagnosticCode::UnresolvedReference,
+                format!("Reference id '{}' doesn't resolve to any element.", id),
+            )),
+        }
+    }
+
+    // =========================================================================
+    // Node
+    // =========================================================================
+
+    fn validate_node(&mut self, node: &Node, path: &str) {
+        match node {
+            Node::Frame(frame) => {
+                for (index, child) in frame.children.iter().enumerate() {
+                    if let ReferenceIdOrEntity::ReferenceId { reference_id } = child {
+                        let child_path = format!("{}.children[{}].referenceId", path, index);
+                        self.validate_child(reference_id, child_path);
+                    }
+                }
+            }
+            Node::BooleanOperation(boolean_operation) => {
+                for (index, child) in boolean_operation.children.iter().enumerate() {
+                    if let ReferenceIdOrEntity::ReferenceId { reference_id } = child {
+                        let child_path = format!("{}.children[{}].referenceId", path, index);
+                        self.validate_child(reference_id, child_path);
+                    }
+                }
+            }
+            Node::Component(component) => {
+                for (index, child) in component.children.iter().enumerate() {
+                    if let ReferenceIdOrEntity::ReferenceId { reference_id } = child {
+                        let child_path = format!("{}.children[{}].referenceId", path, index);
+                        self.validate_child(reference_id, child_path);
+                    }
+                }
+            }
+            Node::Instance(instance) => {
+                if let ReferenceIdOrEntity::ReferenceId { reference_id } = &instance.component_id {
+                    self.validate_reference_id(
+                        reference_id,
+                        &[ElementKind::Component],
+                        format!("{}.componentId.referenceId", path),
+                    );
+                }
+                for (index, instance_override) in instance.overrides.iter().enumerate() {
+                    let override_path = format!("{}.overrides[{}]", path, index);
+                    if let ReferenceIdOrEntity::ReferenceId { reference_id } =
+                        &instance_override.target_id
+                    {
+                        self.validate_reference_id(
+                            reference_id,
+                            &[ElementKind::Node, ElementKind::Style],
+                            format!("{}.targetId.referenceId", override_path),
+                        );
+                    }
+                    if let Some(ReferenceIdOrEntity::ReferenceId { reference_id }) =
+                        &instance_override.paint_id
+                    {
+                        self.validate_reference_id(
+                            reference_id,
+                            &[ElementKind::Paint],
+                            format!("{}.paintId.referenceId", override_path),
+                        );
+                    }
+                }
+            }
+            Node::Text(text) => {
+                let text_len = text.text.len();
+                for (index, interval) in text.attributes.iter().enumerate() {
+                    let interval_path = format!("{}.attributes[{}]", path, index);
+                    if interval.start > interval.end {
+                        self.diagnostics.push(Diagnostic::error(
+                            interval_path,
+                            DiagnosticCode::InvalidTextAttributeInterval,
+                            format!(
+                                "Attribute interval starts at {} which is after its end at {}.",
+                                interval.start, interval.end
+                            ),
+                        ));
+                    } else if interval.end > text_len {
+                        self.diagnostics.push(Diagnostic::error(
+                            format!("{}.end", interval_path),
+                            DiagnosticCode::TextAttributeIntervalOutOfBounds,
+                            format!(
+                                "Attribute interval ends at {} but the text is only {} bytes long.",
+                                interval.end, text_len
+                            ),
+                        ));
+                    } else if !text.text.is_char_boundary(interval.start)
+                        || !text.text.is_char_boundary(interval.end)
+                    {
+                        self.diagnostics.push(Diagnostic::error(
+                            interval_path,
+                            DiagnosticCode::InvalidTextAttributeInterval,
+                            format!(
+                                "Attribute interval from {} to {} doesn't lie on character boundaries.",
+                                interval.start, interval.end
+                            ),
+                        ));
+                    }
+                }
+            }
+            Node::Vector(vector) => {
+                if let Err(err) = string_to_tiny_skia_path(&vector.path) {
+                    self.diagnostics.push(Diagnostic::error(
+                        format!("{}.path", path),
+                        DiagnosticCode::InvalidVectorPath,
+                        format!("Failed to parse path: {}", err),
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        let (_, styles) = node_id_and_styles(node);
+        for (index, style) in styles.iter().enumerate() {
+            self.validate_style(style, &format!("{}.styles[{}]", path, index));
+        }
+    }
+
+    fn validate_child(&mut self, child_id: &ReferenceId, path: String) {
+        if let Some(first_path) = self.child_to_parent_path.get(child_id) {
+            self.diagnostics.push(Diagnostic::warning(
+                path.clone(),
+                DiagnosticCode::MultipleParents,
+                format!(
+                    "Node '{}' is already a child at '{}' and will be moved.",
+                    child_id, first_path
+                ),
+            ));
+        } else {
+            self.child_to_parent_path
+                .insert(child_id.clone(), path.clone());
+        }
+
+        self.validate_reference_id(child_id, &[ElementKind::Node, ElementKind::Component], path);
+    }
+
+    /// Reports cycles in the node hierarchy (e.g. a frame that is a child of its own child),
+    /// walking the hierarchy iteratively as untrusted documents can be arbitrarily deep.
+    fn validate_hierarchy(&mut self, dtif: &DtifArtboard) {
+        let mut node_ids = Vec::new();
+        let mut children: HashMap<&ReferenceId, Vec<(&ReferenceId, String)>> = HashMap::new();
+        for (index, node) in dtif.nodes.iter().enumerate() {
+            let (maybe_id, node_children) = match node {
+                Node::Frame(node) => (node.id.as_ref(), &node.children),
+                Node::Component(node) => (node.id.as_ref(), &node.children),
+                Node::BooleanOperation(node) => (node.id.as_ref(), &node.children),
+                _ => continue,
+            };
+            if let Some(id) = maybe_id {
+                node_ids.push(id);
+                let edges = children.entry(id).or_default();
+                for (child_index, child) in node_children.iter().enumerate() {
+                    if let ReferenceIdOrEntity::ReferenceId { reference_id } = child {
+                        edges.push((
+                            reference_id,
+                            format!(
+                                "{}[{}].children[{}].referenceId",
+                                self.nodes_path, index, child_index
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut finished: HashSet<&ReferenceId> = HashSet::new();
+        for root_id in node_ids {
+            if finished.contains(root_id) {
+                continue;
+            }
+
+            // Path from the root to the current node with the index of the next child to visit
+            let mut stack: Vec<(&ReferenceId, usize)> = vec![(root_id, 0)];
+            while let Some(&(id, child_index)) = stack.last() {
+                let maybe_edge = children.get(id).and_then(|edges| edges.get(child_index));
+                let (child_id, path) = match maybe_edge {
+                    Some(edge) => edge,
+                    None => {
+                        finished.insert(id);
+                        stack.pop();
+                        continue;
+                    }
+                };
+                if let Some(last) = stack.last_mut() {
+                    last.1 += 1;
+                }
+
+                if let Some(cycle_start) = stack.iter().position(|(id, _)| id == child_id) {
+                    let cycle = stack[cycle_start..]
+                        .iter()
+                        .map(|(id, _)| id.to_string())
+                        .chain(std::iter::once(child_id.to_string()))
+                        .collect::<Vec<_>>()
+                        .join("' -> '");
+                    self.diagnostics.push(Diagnostic::error(
+                        path.clone(),
+                        DiagnosticCode::SelfReference,
+                        format!("Node '{}' is its own descendant ('{}').", child_id, cycle),
+                    ));
+                } else if !finished.contains(child_id) {
+                    stack.push((*child_id, 0));
+                }
+            }
+        }
+    }
+
+    // =========================================================================
+    // Style
+    // =========================================================================
+
+    fn validate_style(&mut self, style: &Style, path: &str) {
+        let maybe_paint_id = match style {
+            Style::Fill(style) => Some(&style.paint_id),
+            Style::Stroke(style) => Some(&style.paint_id),
+            Style::DropShadow(_)
+            | Style::InnerShadow(_)
+            | Style::LayerBlur(_)
+            | Style::BackgroundBlur(_) => None,
+        };
+
+        if let Some(ReferenceIdOrEntity::ReferenceId { reference_id }) = maybe_paint_id {
+            self.validate_reference_id(
+                reference_id,
+                &[ElementKind::Paint],
+                format!("{}.paintId.referenceId", path),
+            );
+        }
+
+        if let Style::Stroke(style) = style {
+            self.validate_dash_array(&style.dash_array, format!("{}.dashArray", path));
+        }
+    }
+
+    fn validate_dash_array(&mut self, dash_array: &[Abs], path: String) {
+        if dash_array.is_empty() {
+            return;
+        }
+
+        if dash_array
+            .iter()
+            .any(|length| !length.to_pt().is_finite() || length.to_pt() < 0.0)
+        {
+            self.diagnostics.push(Diagnostic::error(
+                path,
+                DiagnosticCode::InvalidDashArray,
+                String::from(
+                    "Dash array contains negative values and will be drawn as solid stroke.",
+                ),
+            ));
+        } else if dash_array.iter().all(|length| length.to_pt() == 0.0) {
+            self.diagnostics.push(Diagnostic::error(
+                path,
+                DiagnosticCode::InvalidDashArray,
+                String::from("Dash array contains only zeros and will be drawn as solid stroke."),
+            ));
+        } else if dash_array.len() % 2 == 1 {
+            self.diagnostics.push(Diagnostic::warning(
+                path,
+                DiagnosticCode::InvalidDashArray,
+                String::from(
+                    "Dash array has an odd number of values and will be repeated to an even number (e.g. [4, 2, 1] to [4, 2, 1, 4, 2, 1]).",
+                ),
+            ));
+        }
+    }
+
+    // =========================================================================
+    // Paint
+    // =========================================================================
+
+    fn validate_paint(&mut self, paint: &Paint, path: &str) {
+        if let Paint::Image(image) = paint {
+            if let ReferenceIdOrImageId::ReferenceId { reference_id } = &image.image_id {
+                self.validate_reference_id(
+                    reference_id,
+                    &[ElementKind::ImageAsset, ElementKind::Asset],
+                    format!("{}.imageId.referenceId", path),
+                );
+            }
+        }
+    }
+
+    // =========================================================================
+    // Variable
+    // =========================================================================
+
+    /// Validates the variable bindings along with their index in the DTIF.
+    fn validate_variable_bindings<'a>(
+        &mut self,
+        bindings: impl Iterator<Item = (usize, &'a VariableBinding)>,
+        dtif: &DtifArtboard,
+    ) {
+        let variables = dtif
+            .variables
+            .iter()
+            .map(|variable| (&variable.id, variable))
+            .collect::<HashMap<_, _>>();
+        for (index, binding) in bindings {
+            self.validate_variable_binding(
+                binding,
+                &variables,
+                &format!("$.variableBindings[{}]", index),
+            );
+        }
+    }
+
+    fn validate_variable_binding(
+        &mut self,
+        binding: &VariableBinding,
+        variables: &HashMap<&ReferenceId, &Variable>,
+        path: &str,
+    ) {
+        if let ReferenceIdOrEntity::ReferenceId { reference_id } = &binding.target_id {
+            self.validate_reference_id(
+                reference_id,
+                &[
+                    ElementKind::Node,
+                    ElementKind::Component,
+                    ElementKind::Style,
+                    ElementKind::Paint,
+                ],
+                format!("{}.targetId.referenceId", path),
+            );
+        }
+
+        self.validate_reference_id(
+            &binding.variable_id,
+            &[ElementKind::Variable],
+            format!("{}.variableId", path),
+        );
+
+        if let Some(variable) = variables.get(&binding.variable_id) {
+            let all_values_accepted = std::iter::once(&variable.value)
+                .chain(variable.modes.values())
+                .all(|value| binding.property.accepts(value));
+            if !all_values_accepted {
+                self.diagnostics.push(Diagnostic::warning(
+                    format!("{}.property", path),
+                    DiagnosticCode::VariableTypeMismatch,
+                    format!(
+                        "Variable '{}' has a value that can't be bound to {:?} and will be ignored.",
+                        binding.variable_id, binding.property
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+pub(crate) fn node_id_and_styles(node: &Node) -> (&Option<ReferenceId>, &[Style]) {
+    match node {
+        Node::Frame(node) => (&node.id, &node.styles),
+        Node::Component(node) => (&node.id, &node.styles),
+        Node::Instance(node) => (&node.id, &[]),
+        Node::Rectangle(node) => (&node.id, &node.styles),
+        Node::Ellipse(node) => (&node.id, &node.styles),
+        Node::Star(node) => (&node.id, &node.styles),
+        Node::Polygon(node) => (&node.id, &node.styles),
+        Node::Text(node) => (&node.id, &node.styles),
+        Node::Vector(node) => (&node.id, &node.styles),
+        Node::BooleanOperation(node) => (&node.id, &node.styles),
+    }
+}
+
+#[cfg(all(test, feature = "specta_support"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::reference_id;
+
+    fn validate_json(value: serde_json::Value) -> Vec<Diagnostic> {
+        validate(&serde_json::from_value(value).unwrap())
+    }
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<DiagnosticCode> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect()
+    }
+
+    fn fill(paint_id: &str) -> serde_json::Value {
+        serde_json::json!({ "type": "Fill", "paintId": reference_id(paint_id) })
+    }
+
+    #[test]
+    fn test_valid() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Frame", "id": "root", "size": [100, 100],
+                    "children": [reference_id("rect")], "styles": [fill("paint")] },
+                { "type": "Rectangle", "id": "rect", "size": [10, 10] }
+            ],
+            "paints": [{ "type": "Solid", "id": "paint", "color": [0, 0, 0] }]
+        }));
+
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_reference_id() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Rectangle", "id": "a", "size": [10, 10] },
+                { "type": "Rectangle", "id": "a", "size": [10, 10] }
+            ]
+        }));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![DiagnosticCode::DuplicateReferenceId]
+        );
+        assert_eq!(diagnostics[0].path, "$.nodes[1].id");
+    }
+
+    #[test]
+    fn test_unresolved_reference() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Rectangle", "size": [10, 10], "styles": [fill("missing")] }]
+        }));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![DiagnosticCode::UnresolvedReference]
+        );
+        assert_eq!(
+            diagnostics[0].path,
+            "$.nodes[0].styles[0].paintId.referenceId"
+        );
+    }
+
+    #[test]
+    fn test_reference_kind_mismatch() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Rectangle", "id": "rect", "size": [10, 10], "styles": [fill("rect")] }]
+        }));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![DiagnosticCode::ReferenceKindMismatch]
+        );
+    }
+
+    #[test]
+    fn test_self_reference() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Frame", "id": "a", "size": [10, 10], "children": [reference_id("a")] }
+            ]
+        }));
+
+        assert_eq!(codes(&diagnostics), vec![DiagnosticCode::SelfReference]);
+        assert_eq!(diagnostics[0].path, "$.nodes[0].children[0].referenceId");
+    }
+
+    #[test]
+    fn test_indirect_self_reference() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Frame", "id": "a", "size": [10, 10], "children": [reference_id("b")] },
+                { "type": "Frame", "id": "b", "size": [10, 10], "children": [reference_id("c")] },
+                { "type": "Frame", "id": "c", "size": [10, 10], "children": [reference_id("a")] },
+                { "type": "Frame", "id": "d", "size": [10, 10], "children": [reference_id("b")] }
+            ]
+        }));
+
+        // Node 'b' having two parents is reported too, but the cycle only once
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                DiagnosticCode::MultipleParents,
+                DiagnosticCode::SelfReference
+            ]
+        );
+        assert_eq!(diagnostics[1].path, "$.nodes[2].children[0].referenceId");
+        assert_eq!(
+            diagnostics[1].message,
+            "Node 'a' is its own descendant ('a' -> 'b' -> 'c' -> 'a')."
+        );
+    }
+
+    #[test]
+    fn test_multiple_parents() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [
+                { "type": "Frame", "id": "a", "size": [10, 10], "children": [reference_id("c")] },
+                { "type": "Frame", "id": "b", "size": [10, 10], "children": [reference_id("c")] },
+                { "type": "Rectangle", "id": "c", "size": [10, 10] }
+            ]
+        }));
+
+        assert_eq!(codes(&diagnostics), vec![DiagnosticCode::MultipleParents]);
+        assert!(!diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn test_text_attribute_intervals() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Text", "text": "Hello", "size": [50, 20], "attributes": [
+                { "start": 3, "end": 1, "attributes": {} },
+                { "start": 0, "end": 10, "attributes": {} }
+            ] }]
+        }));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                DiagnosticCode::InvalidTextAttributeInterval,
+                DiagnosticCode::TextAttributeIntervalOutOfBounds
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_vector_path() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Vector", "path": "M 0 0 L", "windingRule": "Nonzero", "size": [10, 10] }]
+        }));
+
+        assert_eq!(codes(&diagnostics), vec![DiagnosticCode::InvalidVectorPath]);
+    }
+
+    #[test]
+    fn test_variable_type_mismatch() {
+        let diagnostics = validate_json(serde_json::json!({
+            "size": [100, 100],
+            "nodes": [{ "type": "Rectangle", "id": "rect", "size": [10, 10] }],
+            "variables": [{ "id": "title", "value": { "type": "String", "value": "Hello" } }],
+            "variableBindings": [
+                { "targetId": reference_id("rect"), "property": "Width", "variableId": "title" }
+            ]
+        }));
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![DiagnosticCode::VariableTypeMismatch]
+        );
+        assert!(!diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn test_document_paths() {
+        let document: DtifDocument = serde_json::from_value(serde_json::json!({
+            "artboards": [
+                { "size": [100, 100], "nodes": [
+                    { "type": "Rectangle", "id": "a", "size": [10, 10], "styles": [fill("paint")] }
+                ] },
+                { "size": [100, 100], "nodes": [
+                    { "type": "Rectangle", "id": "b", "size": [10, 10], "styles": [fill("paint")] },
+                    { "type": "Rectangle", "id": "c", "size": [10, 10], "styles": [fill("missing")] }
+                ] }
+            ],
+            "paints": [{ "type": "Image", "id": "paint", "imageId": reference_id("missing") }]
+        }))
+        .unwrap();
+
+        let diagnostics = validate_document(&document, &[0, 1]);
+
+        // The problem of the shared paint is reported only once
+        let paths: Vec<&str> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "$.paints[0].imageId.referenceId",
+                "$.artboards[1].nodes[1].styles[0].paintId.referenceId"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_dash_array() {
+        let dtif_with_dash_array = |dash_array: serde_json::Value| {
+            serde_json::json!({
+                "size": [100, 100],
+                "nodes": [{ "type": "Rectangle", "size": [10, 10], "styles": [{
+                    "type": "Stroke", "width": 1, "paintId": reference_id("paint"),
+                    "dashArray": dash_array
+                }] }],
+                "paints": [{ "type": "Solid", "id": "paint", "color": [0, 0, 0] }]
+            })
+        };
+
+        assert!(validate_json(dtif_with_dash_array(serde_json::json!([4, 2]))).is_empty());
+
+        for dash_array in [serde_json::json!([4, -2]), serde_json::json!([0, 0])] {
+            let diagnostics = validate_json(dtif_with_dash_array(dash_array));
+            assert_eq!(codes(&diagnostics), vec![DiagnosticCode::InvalidDashArray]);
+            assert_eq!(diagnostics[0].path, "$.nodes[0].styles[0].dashArray");
+            assert!(diagnostics[0].is_error());
+        }
+
+        let diagnostics = validate_json(dtif_with_dash_array(serde_json::json!([4, 2, 1])));
+        assert_eq!(codes(&diagnostics), vec![DiagnosticCode::InvalidDashArray]);
+        assert!(!diagnostics[0].is_error());
+    }
+}
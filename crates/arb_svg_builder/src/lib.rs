@@ -8,11 +8,14 @@ use bevy_ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet};
 use resources::svg_context::SvgContextRes;
 use systems::{
     apply::{
+        apply_background_blur_backdrop_changes, apply_background_blur_changes,
         apply_blend_mode_mixin_changes, apply_clip_content_changes, apply_drop_shadow_changes,
-        apply_gradient_paint_changes, apply_image_asset_mixin_changes, apply_image_paint_changes,
-        apply_node_children_changes, apply_node_styles_changes, apply_opacity_mixin_changes,
-        apply_path_mixin_changes, apply_size_mixin_changes, apply_solid_paint_changes,
-        apply_stroke_path_mixin_changes, apply_transform_changes, apply_visibility_mixin_changes,
+        apply_gradient_paint_changes, apply_image_asset_mixin_changes, apply_image_filter_changes,
+        apply_image_paint_changes, apply_inner_shadow_changes, apply_layer_blur_changes,
+        apply_layer_blur_node_filter_changes, apply_node_children_changes,
+        apply_node_styles_changes, apply_opacity_mixin_changes, apply_path_mixin_changes,
+        apply_size_mixin_changes, apply_solid_paint_changes, apply_stroke_path_mixin_changes,
+        apply_transform_changes, apply_visibility_mixin_changes,
     },
     prepare::{insert_node_svg_bundle, insert_style_svg_bundle, propagate_size_mixin_to_style},
 };
@@ -79,7 +82,15 @@ impl Plugin for ArbSvgBuilderPlugin {
                 apply_gradient_paint_changes.in_set(ArbSvgBuilderSystemSet::Apply),
                 apply_image_paint_changes.in_set(ArbSvgBuilderSystemSet::Apply),
                 apply_image_asset_mixin_changes.in_set(ArbSvgBuilderSystemSet::Apply),
-                apply_drop_shadow_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                apply_image_filter_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                (
+                    apply_drop_shadow_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                    apply_inner_shadow_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                    apply_layer_blur_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                    apply_layer_blur_node_filter_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                    apply_background_blur_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                    apply_background_blur_backdrop_changes.in_set(ArbSvgBuilderSystemSet::Apply),
+                ),
             ),
         );
 
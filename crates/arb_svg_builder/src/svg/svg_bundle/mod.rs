@@ -4,9 +4,11 @@ pub mod style;
 use self::{
     node::{frame::FrameNodeSvgBundle, shape::ShapeNodeSvgBundle},
     style::{
+        background_blur_effect::BackgroundBlurEffectStyleSvgBundle,
         drop_shadow_effect::DropShadowEffectStyleSvgBundle,
         gradient_fill::GradientFillStyleSvgBundle, image_fill::ImageFillStyleSvgBundle,
-        solid_fill::SolidFillStyleSvgBundle,
+        inner_shadow_effect::InnerShadowEffectStyleSvgBundle,
+        layer_blur_effect::LayerBlurEffectStyleSvgBundle, solid_fill::SolidFillStyleSvgBundle,
     },
 };
 use super::svg_element::{SvgElement, SvgElementId};
@@ -79,6 +81,9 @@ pub enum SvgBundleVariant {
     GradientFill(GradientFillStyleSvgBundle),
     ImageFill(ImageFillStyleSvgBundle),
     DropShadowEffect(DropShadowEffectStyleSvgBundle),
+    InnerShadowEffect(InnerShadowEffectStyleSvgBundle),
+    LayerBlurEffect(LayerBlurEffectStyleSvgBundle),
+    BackgroundBlurEffect(BackgroundBlurEffectStyleSvgBundle),
 }
 
 impl SvgBundleVariant {
@@ -90,6 +95,9 @@ impl SvgBundleVariant {
             SvgBundleVariant::GradientFill(bundle) => bundle,
             SvgBundleVariant::ImageFill(bundle) => bundle,
             SvgBundleVariant::DropShadowEffect(bundle) => bundle,
+            SvgBundleVariant::InnerShadowEffect(bundle) => bundle,
+            SvgBundleVariant::LayerBlurEffect(bundle) => bundle,
+            SvgBundleVariant::BackgroundBlurEffect(bundle) => bundle,
         }
     }
 
@@ -101,6 +109,9 @@ impl SvgBundleVariant {
             SvgBundleVariant::GradientFill(bundle) => bundle,
             SvgBundleVariant::ImageFill(bundle) => bundle,
             SvgBundleVariant::DropShadowEffect(bundle) => bundle,
+            SvgBundleVariant::InnerShadowEffect(bundle) => bundle,
+            SvgBundleVariant::LayerBlurEffect(bundle) => bundle,
+            SvgBundleVariant::BackgroundBlurEffect(bundle) => bundle,
         }
     }
 
@@ -142,6 +153,9 @@ impl SvgBundleVariant {
             SvgBundleVariant::GradientFill(bundle) => &bundle.root_g,
             SvgBundleVariant::ImageFill(bundle) => &bundle.root_g,
             SvgBundleVariant::DropShadowEffect(bundle) => &bundle.root_g,
+            SvgBundleVariant::InnerShadowEffect(bundle) => &bundle.root_g,
+            SvgBundleVariant::LayerBlurEffect(bundle) => &bundle.root_g,
+            SvgBundleVariant::BackgroundBlurEffect(bundle) => &bundle.root_g,
         }
     }
 
@@ -153,6 +167,9 @@ impl SvgBundleVariant {
             SvgBundleVariant::GradientFill(bundle) => &mut bundle.root_g,
             SvgBundleVariant::ImageFill(bundle) => &mut bundle.root_g,
             SvgBundleVariant::DropShadowEffect(bundle) => &mut bundle.root_g,
+            SvgBundleVariant::InnerShadowEffect(bundle) => &mut bundle.root_g,
+            SvgBundleVariant::LayerBlurEffect(bundle) => &mut bundle.root_g,
+            SvgBundleVariant::BackgroundBlurEffect(bundle) => &mut bundle.root_g,
         }
     }
 
@@ -225,6 +242,15 @@ impl SvgBundleVariant {
             SvgBundleVariant::DropShadowEffect(bundle) => {
                 bundle.get_root_element().to_string(bundle, None)
             }
+            SvgBundleVariant::InnerShadowEffect(bundle) => {
+                bundle.get_root_element().to_string(bundle, None)
+            }
+            SvgBundleVariant::LayerBlurEffect(bundle) => {
+                bundle.get_root_element().to_string(bundle, None)
+            }
+            SvgBundleVariant::BackgroundBlurEffect(bundle) => {
+                bundle.get_root_element().to_string(bundle, None)
+            }
         }
     }
 }
@@ -0,0 +1,143 @@
+// Resources:
+// https://developer.mozilla.org/en-US/docs/Web/CSS/backdrop-filter
+// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/use
+
+use crate::{
+    resources::svg_context::SvgContextRes,
+    svg::{
+        svg_bundle::SvgBundle,
+        svg_element::{
+            attributes::{SvgAttribute, SvgAttributeFilter, SvgAttributeIn, SvgUnits},
+            SvgElement, SvgTag,
+        },
+    },
+};
+use bevy_ecs::entity::Entity;
+use smallvec::SmallVec;
+
+/// SVG filters can't access what is rendered behind an element
+/// (unlike the CSS `backdrop-filter`), so the backdrop is rebuilt
+/// from copies of the parent's styles and the siblings behind the node,
+/// which are then blurred and clipped to the shape of the node.
+#[derive(Debug, Clone)]
+pub struct BackgroundBlurEffectStyleSvgBundle {
+    pub entity: Entity,
+
+    pub root_g: SvgElement,
+    /**/ pub defs: SvgElement,
+    /**//**/ pub clip_path: SvgElement,
+    /**//**//**/ pub clip_path_path: SvgElement,
+    /**//**/ pub filter: SvgElement,
+    /**//**//**/ pub fe_gaussian_blur: SvgElement,
+    /**/ pub backdrop_g: SvgElement,
+    /**//**/ pub backdrop_content_g: SvgElement,
+    /**//**//**/ pub backdrop_uses: SmallVec<[SvgElement; 2]>,
+}
+
+impl SvgBundle for BackgroundBlurEffectStyleSvgBundle {
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_root_element(&self) -> &SvgElement {
+        &self.root_g
+    }
+
+    fn get_root_element_mut(&mut self) -> &mut SvgElement {
+        &mut self.root_g
+    }
+
+    fn elements_iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a SvgElement> + 'a> {
+        Box::new(
+            std::iter::once(&self.root_g)
+                .chain(std::iter::once(&self.defs))
+                .chain(std::iter::once(&self.clip_path))
+                .chain(std::iter::once(&self.clip_path_path))
+                .chain(std::iter::once(&self.filter))
+                .chain(std::iter::once(&self.fe_gaussian_blur))
+                .chain(std::iter::once(&self.backdrop_g))
+                .chain(std::iter::once(&self.backdrop_content_g))
+                .chain(self.backdrop_uses.iter()),
+        )
+    }
+
+    fn elements_iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut SvgElement> + 'a> {
+        Box::new(
+            std::iter::once(&mut self.root_g)
+                .chain(std::iter::once(&mut self.defs))
+                .chain(std::iter::once(&mut self.clip_path))
+                .chain(std::iter::once(&mut self.clip_path_path))
+                .chain(std::iter::once(&mut self.filter))
+                .chain(std::iter::once(&mut self.fe_gaussian_blur))
+                .chain(std::iter::once(&mut self.backdrop_g))
+                .chain(std::iter::once(&mut self.backdrop_content_g))
+                .chain(self.backdrop_uses.iter_mut()),
+        )
+    }
+}
+
+impl BackgroundBlurEffectStyleSvgBundle {
+    pub fn new(entity: Entity, cx: &mut SvgContextRes) -> Self {
+        log::info!("[BackgroundBlurEffectStyleSvgBundle::new] {:?}", entity);
+
+        let mut root_g_element = cx.create_bundle_root_element(SvgTag::Group, entity);
+
+        let mut defs_element = cx.create_element(SvgTag::Defs);
+        root_g_element.append_child_in_bundle_context(&mut defs_element);
+
+        let mut clip_path_element = cx.create_element(SvgTag::ClipPath);
+        defs_element.append_child_in_bundle_context(&mut clip_path_element);
+
+        let mut clip_path_path_element = cx.create_element(SvgTag::Path);
+        clip_path_element.append_child_in_bundle_context(&mut clip_path_path_element);
+
+        let mut filter_element = cx.create_element(SvgTag::Filter);
+        filter_element.set_attributes(vec![
+            SvgAttribute::ColorInterpolationFilters {
+                color_interpolation_filters: String::from("sRGB"),
+            },
+            SvgAttribute::FilterUnits {
+                filter_units: SvgUnits::UserSpaceOnUse,
+            },
+        ]);
+        defs_element.append_child_in_bundle_context(&mut filter_element);
+
+        let mut fe_gaussian_blur_element = cx.create_element(SvgTag::FeGaussianBlur);
+        fe_gaussian_blur_element.set_attribute(SvgAttribute::In {
+            value: SvgAttributeIn::SourceGraphic,
+        });
+        filter_element.append_child_in_bundle_context(&mut fe_gaussian_blur_element);
+
+        // The filter is applied before the clip path,
+        // so that the backdrop around the shape is blurred into its edges
+        let mut backdrop_g_element = cx.create_element(SvgTag::Group);
+        backdrop_g_element.set_attributes(vec![
+            SvgAttribute::ClipPath {
+                clip_path: clip_path_element.get_id(),
+            },
+            SvgAttribute::Filter {
+                filter: SvgAttributeFilter::Reference {
+                    id: filter_element.get_id(),
+                },
+            },
+        ]);
+        root_g_element.append_child_in_bundle_context(&mut backdrop_g_element);
+
+        // Transforms the backdrop from the space of the parent into the space of the node
+        let mut backdrop_content_g_element = cx.create_element(SvgTag::Group);
+        backdrop_g_element.append_child_in_bundle_context(&mut backdrop_content_g_element);
+
+        Self {
+            entity,
+            root_g: root_g_element,
+            defs: defs_element,
+            clip_path: clip_path_element,
+            clip_path_path: clip_path_path_element,
+            filter: filter_element,
+            fe_gaussian_blur: fe_gaussian_blur_element,
+            backdrop_g: backdrop_g_element,
+            backdrop_content_g: backdrop_content_g_element,
+            backdrop_uses: SmallVec::new(),
+        }
+    }
+}
@@ -13,6 +13,8 @@ use bevy_ecs::entity::Entity;
 use dyn_arb_bundles::components::paints::GradientVariant;
 use smallvec::SmallVec;
 
+/// Angular and diamond gradients have no native SVG counterpart,
+/// so they are rendered as a pattern containing a mesh of wedge paths.
 #[derive(Debug, Clone)]
 pub struct GradientFillStyleSvgBundle {
     pub entity: Entity,
@@ -22,6 +24,7 @@ pub struct GradientFillStyleSvgBundle {
     /**/ pub defs: SvgElement,
     /**//**/ pub gradient: SvgElement,
     /**//**/ pub gradient_stops: SmallVec<[SvgElement; 2]>,
+    /**//**/ pub mesh_elements: Vec<SvgElement>,
     /**/ pub shape_path: SvgElement,
 }
 
@@ -44,6 +47,7 @@ impl SvgBundle for GradientFillStyleSvgBundle {
                 .chain(std::iter::once(&self.defs))
                 .chain(std::iter::once(&self.gradient))
                 .chain(self.gradient_stops.iter())
+                .chain(self.mesh_elements.iter())
                 .chain(std::iter::once(&self.shape_path)),
         )
     }
@@ -54,6 +58,7 @@ impl SvgBundle for GradientFillStyleSvgBundle {
                 .chain(std::iter::once(&mut self.defs))
                 .chain(std::iter::once(&mut self.gradient))
                 .chain(self.gradient_stops.iter_mut())
+                .chain(self.mesh_elements.iter_mut())
                 .chain(std::iter::once(&mut self.shape_path)),
         )
     }
@@ -68,13 +73,7 @@ impl GradientFillStyleSvgBundle {
         let mut defs_element = cx.create_element(SvgTag::Defs);
         root_g_element.append_child_in_bundle_context(&mut defs_element);
 
-        let mut gradient_element = cx.create_element(match gradient_variant {
-            GradientVariant::Linear { .. } => SvgTag::LinearGradient,
-            GradientVariant::Radial { .. } => SvgTag::RadialGradient,
-        });
-        gradient_element.set_attribute(SvgAttribute::GradientUnits {
-            gradient_units: SvgUnits::UserSpaceOnUse,
-        });
+        let mut gradient_element = Self::create_gradient_element(&gradient_variant, cx);
         defs_element.append_child_in_bundle_context(&mut gradient_element);
 
         let mut shape_path_element = cx.create_element(SvgTag::Path);
@@ -108,19 +107,75 @@ impl GradientFillStyleSvgBundle {
 
         Self {
             entity,
-            variant: match gradient_variant {
-                GradientVariant::Linear { .. } => GradientFillStyleVariant::Linear,
-                GradientVariant::Radial { .. } => GradientFillStyleVariant::Radial,
-            },
+            variant: GradientFillStyleVariant::from(&gradient_variant),
 
             root_g: root_g_element,
             defs: defs_element,
             gradient: gradient_element,
             gradient_stops: SmallVec::new(),
+            mesh_elements: Vec::new(),
             shape_path: shape_path_element,
         }
     }
 
+    /// Updates the variant of the bundle and replaces the gradient element
+    /// if the new variant is rendered with another element (e.g. a pattern),
+    /// as the tag of an element can't change.
+    pub fn set_variant(&mut self, gradient_variant: &GradientVariant, cx: &mut SvgContextRes) {
+        let variant = GradientFillStyleVariant::from(gradient_variant);
+        if variant.get_tag() == self.variant.get_tag() {
+            self.variant = variant;
+            return;
+        }
+
+        // Remove old gradient element with its stops or mesh
+        #[cfg(feature = "output_svg_element_changes")]
+        self.gradient_stops
+            .iter_mut()
+            .chain(self.mesh_elements.iter_mut())
+            .for_each(|element| element.destroy());
+        self.gradient_stops.clear();
+        self.mesh_elements.clear();
+        self.defs.remove_child_element(&mut self.gradient);
+
+        // Add new gradient element
+        let mut gradient_element = Self::create_gradient_element(gradient_variant, cx);
+        self.defs
+            .append_child_in_bundle_context(&mut gradient_element);
+        self.shape_path.set_style(SvgStyle::Fill {
+            fill: SvgStyleColor::Reference {
+                id: gradient_element.get_id(),
+            },
+        });
+
+        #[cfg(feature = "tracing")]
+        gradient_element.set_attribute(SvgAttribute::Class {
+            class: Self::create_element_name(gradient_element.get_id(), "gradient"),
+        });
+
+        self.gradient = gradient_element;
+        self.variant = variant;
+    }
+
+    fn create_gradient_element(
+        gradient_variant: &GradientVariant,
+        cx: &mut SvgContextRes,
+    ) -> SvgElement {
+        let mut gradient_element =
+            cx.create_element(GradientFillStyleVariant::from(gradient_variant).get_tag());
+        match gradient_variant {
+            GradientVariant::Linear { .. } | GradientVariant::Radial { .. } => gradient_element
+                .set_attribute(SvgAttribute::GradientUnits {
+                    gradient_units: SvgUnits::UserSpaceOnUse,
+                }),
+            GradientVariant::Angular { .. } | GradientVariant::Diamond { .. } => gradient_element
+                .set_attribute(SvgAttribute::PatternUnits {
+                    pattern_units: SvgUnits::UserSpaceOnUse,
+                }),
+        }
+        return gradient_element;
+    }
+
     #[cfg(feature = "tracing")]
     #[inline]
     fn create_element_name(id: crate::svg::svg_element::SvgElementId, category: &str) -> String {
@@ -132,4 +187,29 @@ impl GradientFillStyleSvgBundle {
 pub enum GradientFillStyleVariant {
     Linear,
     Radial,
+    Angular,
+    Diamond,
+}
+
+impl GradientFillStyleVariant {
+    fn get_tag(&self) -> SvgTag {
+        match self {
+            GradientFillStyleVariant::Linear => SvgTag::LinearGradient,
+            GradientFillStyleVariant::Radial => SvgTag::RadialGradient,
+            GradientFillStyleVariant::Angular | GradientFillStyleVariant::Diamond => {
+                SvgTag::Pattern
+            }
+        }
+    }
+}
+
+impl From<&GradientVariant> for GradientFillStyleVariant {
+    fn from(gradient_variant: &GradientVariant) -> Self {
+        match gradient_variant {
+            GradientVariant::Linear { .. } => GradientFillStyleVariant::Linear,
+            GradientVariant::Radial { .. } => GradientFillStyleVariant::Radial,
+            GradientVariant::Angular { .. } => GradientFillStyleVariant::Angular,
+            GradientVariant::Diamond { .. } => GradientFillStyleVariant::Diamond,
+        }
+    }
 }
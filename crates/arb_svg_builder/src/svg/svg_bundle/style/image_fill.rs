@@ -3,7 +3,7 @@ use crate::{
     svg::{
         svg_bundle::SvgBundle,
         svg_element::{
-            attributes::{SvgAttribute, SvgUnits},
+            attributes::{SvgAttribute, SvgAttributeType, SvgUnits},
             styles::{SvgStyle, SvgStyleColor},
             SvgElement, SvgTag,
         },
@@ -21,6 +21,12 @@ pub struct ImageFillStyleSvgBundle {
     /**/ pub defs: SvgElement,
     /**//**/ pub pattern: SvgElement,
     /**//**//**/ pub image: SvgElement,
+    /**//**/ pub filter: SvgElement,
+    /**//**//**/ pub fe_component_transfer: SvgElement,
+    /**//**//**//**/ pub fe_func_r: SvgElement,
+    /**//**//**//**/ pub fe_func_g: SvgElement,
+    /**//**//**//**/ pub fe_func_b: SvgElement,
+    /**//**//**/ pub fe_color_matrix: SvgElement,
     /**/ pub shape_path: SvgElement,
 }
 
@@ -43,6 +49,12 @@ impl SvgBundle for ImageFillStyleSvgBundle {
                 .chain(std::iter::once(&self.defs))
                 .chain(std::iter::once(&self.pattern))
                 .chain(std::iter::once(&self.image))
+                .chain(std::iter::once(&self.filter))
+                .chain(std::iter::once(&self.fe_component_transfer))
+                .chain(std::iter::once(&self.fe_func_r))
+                .chain(std::iter::once(&self.fe_func_g))
+                .chain(std::iter::once(&self.fe_func_b))
+                .chain(std::iter::once(&self.fe_color_matrix))
                 .chain(std::iter::once(&self.shape_path)),
         )
     }
@@ -53,6 +65,12 @@ impl SvgBundle for ImageFillStyleSvgBundle {
                 .chain(std::iter::once(&mut self.defs))
                 .chain(std::iter::once(&mut self.pattern))
                 .chain(std::iter::once(&mut self.image))
+                .chain(std::iter::once(&mut self.filter))
+                .chain(std::iter::once(&mut self.fe_component_transfer))
+                .chain(std::iter::once(&mut self.fe_func_r))
+                .chain(std::iter::once(&mut self.fe_func_g))
+                .chain(std::iter::once(&mut self.fe_func_b))
+                .chain(std::iter::once(&mut self.fe_color_matrix))
                 .chain(std::iter::once(&mut self.shape_path)),
         )
     }
@@ -73,17 +91,38 @@ impl ImageFillStyleSvgBundle {
         });
         defs_element.append_child_in_bundle_context(&mut pattern_element);
 
+        // Scaled by the 'preserveAspectRatio' attribute depending on the scale mode
         let mut image_element = cx.create_element(SvgTag::Image);
-        match scale_mode {
-            ImageScaleMode::Fill => {
-                image_element.set_attribute(SvgAttribute::PreserveAspectRatio {
-                    preserve_aspect_ratio: String::from("xMidYMid slice"),
-                });
-            }
-            _ => {}
-        }
         pattern_element.append_child_in_bundle_context(&mut image_element);
 
+        // Image adjustments (see 'ImageFilters')
+        let mut filter_element = cx.create_element(SvgTag::Filter);
+        filter_element.set_attribute(SvgAttribute::ColorInterpolationFilters {
+            color_interpolation_filters: String::from("sRGB"),
+        });
+        defs_element.append_child_in_bundle_context(&mut filter_element);
+
+        let mut fe_component_transfer_element = cx.create_element(SvgTag::FeComponentTransfer);
+        filter_element.append_child_in_bundle_context(&mut fe_component_transfer_element);
+
+        let mut create_fe_func_element = |tag: SvgTag| {
+            let mut fe_func_element = cx.create_element(tag);
+            fe_func_element.set_attribute(SvgAttribute::Type {
+                value: SvgAttributeType::Linear,
+            });
+            fe_component_transfer_element.append_child_in_bundle_context(&mut fe_func_element);
+            fe_func_element
+        };
+        let fe_func_r_element = create_fe_func_element(SvgTag::FeFuncR);
+        let fe_func_g_element = create_fe_func_element(SvgTag::FeFuncG);
+        let fe_func_b_element = create_fe_func_element(SvgTag::FeFuncB);
+
+        let mut fe_color_matrix_element = cx.create_element(SvgTag::FeColorMatrix);
+        fe_color_matrix_element.set_attribute(SvgAttribute::Type {
+            value: SvgAttributeType::Matrix,
+        });
+        filter_element.append_child_in_bundle_context(&mut fe_color_matrix_element);
+
         let mut shape_path_element = cx.create_element(SvgTag::Path);
         shape_path_element.set_style(SvgStyle::Fill {
             fill: SvgStyleColor::Reference {
@@ -109,6 +148,9 @@ impl ImageFillStyleSvgBundle {
             image_element.set_attribute(SvgAttribute::Class {
                 class: Self::create_element_name(image_element.get_id(), "image"),
             });
+            filter_element.set_attribute(SvgAttribute::Class {
+                class: Self::create_element_name(filter_element.get_id(), "filter"),
+            });
             shape_path_element.set_attribute(SvgAttribute::Class {
                 class: Self::create_element_name(shape_path_element.get_id(), "shape-path"),
             });
@@ -127,6 +169,12 @@ impl ImageFillStyleSvgBundle {
             defs: defs_element,
             pattern: pattern_element,
             image: image_element,
+            filter: filter_element,
+            fe_component_transfer: fe_component_transfer_element,
+            fe_func_r: fe_func_r_element,
+            fe_func_g: fe_func_g_element,
+            fe_func_b: fe_func_b_element,
+            fe_color_matrix: fe_color_matrix_element,
             shape_path: shape_path_element,
         }
     }
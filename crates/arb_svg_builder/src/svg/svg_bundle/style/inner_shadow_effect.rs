@@ -0,0 +1,175 @@
+// Resources:
+// https://developer.mozilla.org/en-US/docs/Web/SVG/Element/feComposite
+// https://css-tricks.com/adding-shadows-to-svg-icons-with-css-and-svg-filters/
+
+use crate::{
+    resources::svg_context::SvgContextRes,
+    svg::{
+        svg_bundle::SvgBundle,
+        svg_element::{
+            attributes::{
+                ColorMatrix, SvgAttribute, SvgAttributeFilter, SvgAttributeIn,
+                SvgAttributeOperator, SvgAttributeType, SvgAttributeValues,
+            },
+            SvgElement, SvgTag,
+        },
+    },
+};
+use bevy_ecs::entity::Entity;
+
+/// Renders only the shadow cast inside the shape,
+/// so that it can be layered on top of the fills like any other style.
+#[derive(Debug, Clone)]
+pub struct InnerShadowEffectStyleSvgBundle {
+    pub entity: Entity,
+
+    pub root_g: SvgElement,
+    /**/ pub defs: SvgElement,
+    /**//**/ pub filter: SvgElement,
+    /**//**//**/ pub hard_alpha_fe_color_matrix: SvgElement,
+    /**//**//**/ pub source_alpha_fe_morphology: SvgElement,
+    /**//**//**/ pub fe_offset: SvgElement,
+    /**//**//**/ pub fe_gaussian_blur: SvgElement,
+    /**//**//**/ pub fe_composite: SvgElement,
+    /**//**//**/ pub fe_color_matrix: SvgElement,
+    pub shape_path: SvgElement,
+}
+
+impl SvgBundle for InnerShadowEffectStyleSvgBundle {
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_root_element(&self) -> &SvgElement {
+        &self.root_g
+    }
+
+    fn get_root_element_mut(&mut self) -> &mut SvgElement {
+        &mut self.root_g
+    }
+
+    fn elements_iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a SvgElement> + 'a> {
+        Box::new(
+            std::iter::once(&self.root_g)
+                .chain(std::iter::once(&self.defs))
+                .chain(std::iter::once(&self.filter))
+                .chain(std::iter::once(&self.hard_alpha_fe_color_matrix))
+                .chain(std::iter::once(&self.source_alpha_fe_morphology))
+                .chain(std::iter::once(&self.fe_offset))
+                .chain(std::iter::once(&self.fe_gaussian_blur))
+                .chain(std::iter::once(&self.fe_composite))
+                .chain(std::iter::once(&self.fe_color_matrix))
+                .chain(std::iter::once(&self.shape_path)),
+        )
+    }
+
+    fn elements_iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut SvgElement> + 'a> {
+        Box::new(
+            std::iter::once(&mut self.root_g)
+                .chain(std::iter::once(&mut self.defs))
+                .chain(std::iter::once(&mut self.filter))
+                .chain(std::iter::once(&mut self.hard_alpha_fe_color_matrix))
+                .chain(std::iter::once(&mut self.source_alpha_fe_morphology))
+                .chain(std::iter::once(&mut self.fe_offset))
+                .chain(std::iter::once(&mut self.fe_gaussian_blur))
+                .chain(std::iter::once(&mut self.fe_composite))
+                .chain(std::iter::once(&mut self.fe_color_matrix))
+                .chain(std::iter::once(&mut self.shape_path)),
+        )
+    }
+}
+
+impl InnerShadowEffectStyleSvgBundle {
+    pub fn new(entity: Entity, cx: &mut SvgContextRes) -> Self {
+        log::info!("[InnerShadowEffectStyleSvgBundle::new] {:?}", entity);
+
+        let mut root_g_element = cx.create_bundle_root_element(SvgTag::Group, entity);
+
+        let mut defs_element = cx.create_element(SvgTag::Defs);
+        root_g_element.append_child_in_bundle_context(&mut defs_element);
+
+        let mut filter_element = cx.create_element(SvgTag::Filter);
+        filter_element.set_attribute(SvgAttribute::ColorInterpolationFilters {
+            color_interpolation_filters: String::from("sRGB"),
+        });
+        defs_element.append_child_in_bundle_context(&mut filter_element);
+
+        let mut hard_alpha_fe_color_matrix_element = cx.create_element(SvgTag::FeColorMatrix);
+        hard_alpha_fe_color_matrix_element.set_attributes(vec![
+            SvgAttribute::In {
+                value: SvgAttributeIn::SourceAlpha,
+            },
+            SvgAttribute::Type {
+                value: SvgAttributeType::Matrix,
+            },
+            SvgAttribute::Values {
+                values: SvgAttributeValues::ColorMatrix(ColorMatrix::from_rgba(0, 0, 0, 127.0)),
+            },
+            SvgAttribute::Result {
+                result: String::from("hardAlpha"),
+            },
+        ]);
+        filter_element.append_child_in_bundle_context(&mut hard_alpha_fe_color_matrix_element);
+
+        // Shrinks the shape by the spread before it's offset,
+        // which grows the shadow towards the inside
+        let mut source_alpha_fe_morphology_element = cx.create_element(SvgTag::FeMorphology);
+        source_alpha_fe_morphology_element.set_attributes(vec![
+            SvgAttribute::Operator {
+                operator: SvgAttributeOperator::Erode,
+            },
+            SvgAttribute::In {
+                value: SvgAttributeIn::Other(String::from("hardAlpha")),
+            },
+        ]);
+        filter_element.append_child_in_bundle_context(&mut source_alpha_fe_morphology_element);
+
+        let mut fe_offset_element = cx.create_element(SvgTag::FeOffset);
+        filter_element.append_child_in_bundle_context(&mut fe_offset_element);
+
+        let mut fe_gaussian_blur_element = cx.create_element(SvgTag::FeGaussianBlur);
+        filter_element.append_child_in_bundle_context(&mut fe_gaussian_blur_element);
+
+        // Keeps the parts of the shape not covered by the offset shape (hardAlpha - shape)
+        let mut fe_composite_element = cx.create_element(SvgTag::FeComposite);
+        fe_composite_element.set_attributes(vec![
+            SvgAttribute::In2 {
+                value: SvgAttributeIn::Other(String::from("hardAlpha")),
+            },
+            SvgAttribute::Operator {
+                operator: SvgAttributeOperator::Arithmetic,
+            },
+            SvgAttribute::K2 { k2: -1.0 },
+            SvgAttribute::K3 { k3: 1.0 },
+        ]);
+        filter_element.append_child_in_bundle_context(&mut fe_composite_element);
+
+        let mut fe_color_matrix_element = cx.create_element(SvgTag::FeColorMatrix);
+        fe_color_matrix_element.set_attribute(SvgAttribute::Type {
+            value: SvgAttributeType::Matrix,
+        });
+        filter_element.append_child_in_bundle_context(&mut fe_color_matrix_element);
+
+        let mut shape_path_element = cx.create_element(SvgTag::Path);
+        shape_path_element.set_attribute(SvgAttribute::Filter {
+            filter: SvgAttributeFilter::Reference {
+                id: filter_element.get_id(),
+            },
+        });
+        root_g_element.append_child_in_bundle_context(&mut shape_path_element);
+
+        Self {
+            entity,
+            root_g: root_g_element,
+            defs: defs_element,
+            filter: filter_element,
+            hard_alpha_fe_color_matrix: hard_alpha_fe_color_matrix_element,
+            source_alpha_fe_morphology: source_alpha_fe_morphology_element,
+            fe_offset: fe_offset_element,
+            fe_gaussian_blur: fe_gaussian_blur_element,
+            fe_composite: fe_composite_element,
+            fe_color_matrix: fe_color_matrix_element,
+            shape_path: shape_path_element,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    resources::svg_context::SvgContextRes,
+    svg::{
+        svg_bundle::SvgBundle,
+        svg_element::{
+            attributes::{SvgAttribute, SvgAttributeIn, SvgUnits},
+            SvgElement, SvgTag,
+        },
+    },
+};
+use bevy_ecs::entity::Entity;
+
+/// Only holds the blur filter definition.
+/// The filter is referenced by the root element of the node the style belongs to,
+/// as the blur applies to the whole node including its other styles and children.
+#[derive(Debug, Clone)]
+pub struct LayerBlurEffectStyleSvgBundle {
+    pub entity: Entity,
+
+    pub root_g: SvgElement,
+    /**/ pub defs: SvgElement,
+    /**//**/ pub filter: SvgElement,
+    /**//**//**/ pub fe_gaussian_blur: SvgElement,
+}
+
+impl SvgBundle for LayerBlurEffectStyleSvgBundle {
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_root_element(&self) -> &SvgElement {
+        &self.root_g
+    }
+
+    fn get_root_element_mut(&mut self) -> &mut SvgElement {
+        &mut self.root_g
+    }
+
+    fn elements_iter<'a>(&'a self) -> Box<dyn Iterator<Item = &'a SvgElement> + 'a> {
+        Box::new(
+            std::iter::once(&self.root_g)
+                .chain(std::iter::once(&self.defs))
+                .chain(std::iter::once(&self.filter))
+                .chain(std::iter::once(&self.fe_gaussian_blur)),
+        )
+    }
+
+    fn elements_iter_mut<'a>(&'a mut self) -> Box<dyn Iterator<Item = &'a mut SvgElement> + 'a> {
+        Box::new(
+            std::iter::once(&mut self.root_g)
+                .chain(std::iter::once(&mut self.defs))
+                .chain(std::iter::once(&mut self.filter))
+                .chain(std::iter::once(&mut self.fe_gaussian_blur)),
+        )
+    }
+}
+
+impl LayerBlurEffectStyleSvgBundle {
+    pub fn new(entity: Entity, cx: &mut SvgContextRes) -> Self {
+        log::info!("[LayerBlurEffectStyleSvgBundle::new] {:?}", entity);
+
+        let mut root_g_element = cx.create_bundle_root_element(SvgTag::Group, entity);
+
+        let mut defs_element = cx.create_element(SvgTag::Defs);
+        root_g_element.append_child_in_bundle_context(&mut defs_element);
+
+        let mut filter_element = cx.create_element(SvgTag::Filter);
+        filter_element.set_attributes(vec![
+            SvgAttribute::ColorInterpolationFilters {
+                color_interpolation_filters: String::from("sRGB"),
+            },
+            SvgAttribute::FilterUnits {
+                filter_units: SvgUnits::UserSpaceOnUse,
+            },
+        ]);
+        defs_element.append_child_in_bundle_context(&mut filter_element);
+
+        let mut fe_gaussian_blur_element = cx.create_element(SvgTag::FeGaussianBlur);
+        fe_gaussian_blur_element.set_attribute(SvgAttribute::In {
+            value: SvgAttributeIn::SourceGraphic,
+        });
+        filter_element.append_child_in_bundle_context(&mut fe_gaussian_blur_element);
+
+        Self {
+            entity,
+            root_g: root_g_element,
+            defs: defs_element,
+            filter: filter_element,
+            fe_gaussian_blur: fe_gaussian_blur_element,
+        }
+    }
+}
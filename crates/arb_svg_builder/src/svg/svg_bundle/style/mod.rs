@@ -1,4 +1,7 @@
+pub mod background_blur_effect;
 pub mod drop_shadow_effect;
 pub mod gradient_fill;
 pub mod image_fill;
+pub mod inner_shadow_effect;
+pub mod layer_blur_effect;
 pub mod solid_fill;
@@ -86,6 +86,10 @@ pub enum SvgAttribute {
         clip_path: SvgElementId,
     },
     #[cfg_attr(feature = "specta_support", serde(rename_all = "camelCase"))]
+    ClipRule {
+        clip_rule: WindingRule,
+    },
+    #[cfg_attr(feature = "specta_support", serde(rename_all = "camelCase"))]
     PatternUnits {
         pattern_units: SvgUnits,
     },
@@ -136,12 +140,18 @@ pub enum SvgAttribute {
     K2 {
         k2: f32,
     },
+    K3 {
+        k3: f32,
+    },
     Offset {
         offset: f32,
     },
     Slope {
         slope: f32,
     },
+    Intercept {
+        intercept: f32,
+    },
     #[cfg_attr(feature = "specta_support", serde(rename_all = "camelCase"))]
     StdDeviation {
         std_deviation: f32,
@@ -197,6 +207,7 @@ impl SvgAttribute {
             Self::Filter { .. } => "filter",
             Self::D { .. } => "d",
             Self::ClipPath { .. } => "clip-path",
+            Self::ClipRule { .. } => "clip-rule",
             Self::PatternUnits { .. } => "patternUnits",
             Self::GradientUnits { .. } => "gradientUnits",
             Self::FilterUnits { .. } => "filterUnits",
@@ -211,8 +222,10 @@ impl SvgAttribute {
 
             Self::K1 { .. } => "k1",
             Self::K2 { .. } => "k2",
+            Self::K3 { .. } => "k3",
             Self::Offset { .. } => "offset",
             Self::Slope { .. } => "slope",
+            Self::Intercept { .. } => "intercept",
             Self::StdDeviation { .. } => "stdDeviation",
             Self::TableValues { .. } => "tableValues",
             Self::In { .. } => "in",
@@ -234,6 +247,7 @@ impl SvgAttribute {
                     content_type,
                 } => format!("data:{};base64,{}", content_type.mime_type(), content),
                 SvgHrefAttribute::Url { url } => url.clone(),
+                SvgHrefAttribute::Reference { id } => format!("#{id}"),
             },
             Self::Width { width: value, unit }
             | Self::Height {
@@ -280,7 +294,12 @@ impl SvgAttribute {
                 SvgAttributeColor::Reference { id } => format!("url(#{id})"),
                 SvgAttributeColor::None => String::from("none"),
             },
-            Self::FillRule { fill_rule } => match fill_rule {
+            Self::FillRule {
+                fill_rule: winding_rule,
+            }
+            | Self::ClipRule {
+                clip_rule: winding_rule,
+            } => match winding_rule {
                 WindingRule::Nonzero => String::from("nonzero"),
                 WindingRule::Evenodd => String::from("evenodd"),
             },
@@ -321,8 +340,10 @@ impl SvgAttribute {
 
             Self::K1 { k1 } => k1.to_string(),
             Self::K2 { k2 } => k2.to_string(),
+            Self::K3 { k3 } => k3.to_string(),
             Self::Offset { offset } => offset.to_string(),
             Self::Slope { slope } => slope.to_string(),
+            Self::Intercept { intercept } => intercept.to_string(),
             Self::StdDeviation {
                 std_deviation: deviation,
             } => deviation.to_string(),
@@ -336,6 +357,7 @@ impl SvgAttribute {
             },
             Self::Type { value } => match value {
                 SvgAttributeType::Matrix => String::from("matrix"),
+                SvgAttributeType::Linear => String::from("linear"),
                 SvgAttributeType::Other(other) => other.clone(),
             },
             Self::Result { result } => result.clone(),
@@ -344,6 +366,8 @@ impl SvgAttribute {
             },
             Self::Operator { operator } => match operator {
                 SvgAttributeOperator::Dilate => String::from("dilate"),
+                SvgAttributeOperator::Erode => String::from("erode"),
+                SvgAttributeOperator::Arithmetic => String::from("arithmetic"),
                 SvgAttributeOperator::Other(other) => other.clone(),
             },
         }
@@ -388,6 +412,10 @@ pub enum SvgHrefAttribute {
     Url {
         url: String,
     },
+    /// Reference to another element of the SVG (e.g. for `use` elements).
+    Reference {
+        id: SvgElementId,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -500,6 +528,8 @@ pub enum SvgAttributeIn {
 )]
 pub enum SvgAttributeOperator {
     Dilate,
+    Erode,
+    Arithmetic,
     Other(String),
 }
 
@@ -510,6 +540,7 @@ pub enum SvgAttributeOperator {
 )]
 pub enum SvgAttributeType {
     Matrix,
+    Linear,
     Other(String),
 }
 
@@ -557,6 +588,36 @@ impl ColorMatrix {
         }
     }
 
+    /// Creates a matrix that scales the saturation,
+    /// where `0.0` is grayscale and `1.0` leaves the colors unchanged.
+    pub fn from_saturation(saturation: f32) -> Self {
+        // https://www.w3.org/TR/filter-effects-1/#feColorMatrixElement
+        let (red, green, blue) = (0.2126, 0.7152, 0.0722);
+        let desaturation = 1.0 - saturation;
+        Self {
+            x_axis: (
+                red * desaturation + saturation,
+                red * desaturation,
+                red * desaturation,
+                0.0,
+            ),
+            y_axis: (
+                green * desaturation,
+                green * desaturation + saturation,
+                green * desaturation,
+                0.0,
+            ),
+            z_axis: (
+                blue * desaturation,
+                blue * desaturation,
+                blue * desaturation + saturation,
+                0.0,
+            ),
+            w_axis: (0.0, 0.0, 0.0, 1.0),
+            v_axis: (0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
     pub fn to_string(&self) -> String {
         format!(
             "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}",
@@ -583,3 +644,95 @@ impl ColorMatrix {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies the color matrix as serialized into the 'values' attribute to the RGBA color.
+    fn apply(color_matrix: &ColorMatrix, rgba: [f32; 4]) -> [f32; 4] {
+        let values: Vec<f32> = color_matrix
+            .to_string()
+            .split(' ')
+            .map(|value| value.parse().unwrap())
+            .collect();
+        assert_eq!(values.len(), 20);
+
+        let mut result = [0.0; 4];
+        for (channel, row) in values.chunks(5).enumerate() {
+            result[channel] = row[..4]
+                .iter()
+                .zip(rgba)
+                .map(|(factor, value)| factor * value)
+                .sum::<f32>()
+                + row[4];
+        }
+        return result;
+    }
+
+    fn assert_rgba_eq(actual: [f32; 4], expected: [f32; 4]) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < 1e-4),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_color_matrix_from_rgba() {
+        let color_matrix = ColorMatrix::from_rgba(255, 0, 51, 0.5);
+        assert_eq!(
+            color_matrix.to_string(),
+            "0 0 0 0 1 0 0 0 0 0 0 0 0 0 0.2 0 0 0 0.5 0"
+        );
+
+        // Colors are replaced while the alpha is scaled
+        assert_rgba_eq(
+            apply(&color_matrix, [0.2, 0.4, 0.6, 1.0]),
+            [1.0, 0.0, 0.2, 0.5],
+        );
+        assert_rgba_eq(
+            apply(&color_matrix, [0.2, 0.4, 0.6, 0.5]),
+            [1.0, 0.0, 0.2, 0.25],
+        );
+    }
+
+    #[test]
+    fn test_color_matrix_from_saturation() {
+        // A saturation of 1 leaves the colors unchanged
+        assert_eq!(
+            ColorMatrix::from_saturation(1.0).to_string(),
+            "1 0 0 0 0 0 1 0 0 0 0 0 1 0 0 0 0 0 1 0"
+        );
+
+        // A saturation of 0 maps the colors to their luminance
+        let grayscale = ColorMatrix::from_saturation(0.0);
+        assert_rgba_eq(
+            apply(&grayscale, [1.0, 0.0, 0.0, 1.0]),
+            [0.2126, 0.2126, 0.2126, 1.0],
+        );
+        assert_rgba_eq(
+            apply(&grayscale, [0.0, 1.0, 0.0, 0.5]),
+            [0.7152, 0.7152, 0.7152, 0.5],
+        );
+        assert_rgba_eq(
+            apply(&grayscale, [1.0, 1.0, 1.0, 1.0]),
+            [1.0, 1.0, 1.0, 1.0],
+        );
+
+        // Higher saturations intensify the colors but keep grays unchanged
+        let saturated = ColorMatrix::from_saturation(2.0);
+        assert_rgba_eq(
+            apply(&saturated, [0.5, 0.5, 0.5, 1.0]),
+            [0.5, 0.5, 0.5, 1.0],
+        );
+        assert_rgba_eq(
+            apply(&saturated, [0.6, 0.4, 0.4, 1.0]),
+            [0.7575, 0.3575, 0.3575, 1.0],
+        );
+    }
+}
@@ -120,20 +120,38 @@ fn path_to_string(path: &Path) -> Result<String, Error> {
 
 impl From<&Color> for SvgAttributeColor {
     fn from(color: &Color) -> Self {
-        SvgAttributeColor::RGB {
-            red: color.get_red(),
-            green: color.get_green(),
-            blue: color.get_blue(),
+        if color.is_opaque() {
+            SvgAttributeColor::RGB {
+                red: color.get_red(),
+                green: color.get_green(),
+                blue: color.get_blue(),
+            }
+        } else {
+            SvgAttributeColor::RGBA {
+                red: color.get_red(),
+                green: color.get_green(),
+                blue: color.get_blue(),
+                alpha: color.get_alpha(),
+            }
         }
     }
 }
 
 impl From<&Color> for SvgStyleColor {
     fn from(color: &Color) -> Self {
-        SvgStyleColor::RGB {
-            red: color.get_red(),
-            green: color.get_green(),
-            blue: color.get_blue(),
+        if color.is_opaque() {
+            SvgStyleColor::RGB {
+                red: color.get_red(),
+                green: color.get_green(),
+                blue: color.get_blue(),
+            }
+        } else {
+            SvgStyleColor::RGBA {
+                red: color.get_red(),
+                green: color.get_green(),
+                blue: color.get_blue(),
+                alpha: color.get_alpha(),
+            }
         }
     }
 }
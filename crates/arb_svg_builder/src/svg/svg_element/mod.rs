@@ -404,7 +404,7 @@ impl Display for SvgElementId {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SvgTag {
     // Basic Shapes
     Circle,
@@ -421,6 +421,7 @@ pub enum SvgTag {
     // Container Elements
     Group,
     Defs,
+    Use,
 
     // Definitions and Reusable Configurations
     ClipPath,
@@ -435,6 +436,9 @@ pub enum SvgTag {
     FeOffset,
     FeGaussianBlur,
     FeComponentTransfer,
+    FeFuncR,
+    FeFuncG,
+    FeFuncB,
     FeFuncA,
     FeComposite,
     FeColorMatrix,
@@ -461,6 +465,7 @@ impl SvgTag {
 
             SvgTag::Group => "g",
             SvgTag::Defs => "defs",
+            SvgTag::Use => "use",
 
             SvgTag::ClipPath => "clipPath",
             SvgTag::Pattern => "pattern",
@@ -473,6 +478,9 @@ impl SvgTag {
             SvgTag::FeOffset => "feOffset",
             SvgTag::FeGaussianBlur => "feGaussianBlur",
             SvgTag::FeComponentTransfer => "feComponentTransfer",
+            SvgTag::FeFuncR => "feFuncR",
+            SvgTag::FeFuncG => "feFuncG",
+            SvgTag::FeFuncB => "feFuncB",
             SvgTag::FeFuncA => "feFuncA",
             SvgTag::FeComposite => "feComposite",
             SvgTag::FeColorMatrix => "feColorMatrix",
@@ -1,45 +1,61 @@
 use crate::{
     resources::svg_context::SvgContextRes,
     svg::{
-        svg_bundle::{style::image_fill::ImageFillStyleVariant, SvgBundleVariant},
+        svg_bundle::{
+            style::{
+                gradient_fill::GradientFillStyleSvgBundle,
+                image_fill::{ImageFillStyleSvgBundle, ImageFillStyleVariant},
+            },
+            SvgBundleVariant,
+        },
         svg_element::{
             attributes::{
-                ColorMatrix, SvgAttribute, SvgAttributeValues, SvgHrefAttribute,
-                SvgHrefContentType, SvgMeasurementUnit, SvgTransformAttribute,
+                ColorMatrix, SvgAttribute, SvgAttributeFilter, SvgAttributeValues,
+                SvgHrefAttribute, SvgHrefContentType, SvgMeasurementUnit, SvgTransformAttribute,
+                SvgUnits,
             },
-            styles::{SvgDisplayStyle, SvgStyle},
-            SvgElementId, SvgTag,
+            styles::{SvgDisplayStyle, SvgStyle, SvgStyleColor},
+            SvgElement, SvgElementId, SvgTag,
         },
     },
 };
 use base64::prelude::*;
 use bevy_ecs::{
     entity::Entity,
-    query::{Changed, Or, With, Without},
+    query::{Added, Changed, Or, With, Without},
     removal_detection::RemovedComponents,
     system::{ParamSet, Query, Res, ResMut},
 };
-use bevy_hierarchy::Children;
+use bevy_hierarchy::{Children, Parent};
 use bevy_transform::components::Transform;
 use dyn_arb_asset::{asset::ImageAssetContentType, resources::AssetsRes};
 use dyn_arb_bundles::components::{
     mixins::{
-        BlendModeMixin, ImageAssetMixin, OpacityMixin, PaintParentMixin, PathMixin, SizeMixin,
-        StrokePathMixin, StyleChildrenMixin, VisibilityMixin,
+        BlendModeMixin, ImageAssetMixin, OpacityMixin, PaintChildMixin, PaintParentMixin,
+        PathMixin, SizeMixin, StrokePathMixin, StyleChildrenMixin, StyleParentMixin,
+        VisibilityMixin,
     },
     nodes::{ArbNode, FrameArbNode},
     paints::{
-        ArbPaint, GradientArbPaint, GradientVariant, ImageArbPaint, ImageScaleMode, SolidArbPaint,
+        ArbPaint, GradientArbPaint, GradientColorStop, GradientVariant, ImageArbPaint,
+        ImageScaleMode, SolidArbPaint,
     },
-    styles::{ArbStyle, DropShadowArbStyle, FillArbStyle, StrokeArbStyle},
+    styles::{
+        ArbStyle, BackgroundBlurArbStyle, DropShadowArbStyle, FillArbStyle, InnerShadowArbStyle,
+        LayerBlurArbStyle, StrokeArbStyle,
+    },
+};
+use dyn_utils::{
+    error::NoneErr,
+    properties::{color::Color, size::Size},
 };
-use dyn_utils::{error::NoneErr, properties::size::Size};
 use glam::{Mat3, Vec2};
 use smallvec::SmallVec;
 use std::{
     collections::{HashMap, HashSet},
     error::Error,
 };
+use tiny_skia_path::{PathBuilder, Rect};
 
 #[derive(Debug, Clone)]
 pub struct SvgBundleChildrenChange {
@@ -437,35 +453,6 @@ pub fn apply_size_mixin_changes(
                 },
             ]);
         }
-
-        match bundle_variant.as_mut() {
-            SvgBundleVariant::ImageFill(bundle) => match bundle.variant {
-                ImageFillStyleVariant::Fill | ImageFillStyleVariant::Fit => {
-                    bundle.pattern.set_attributes(vec![
-                        SvgAttribute::Width {
-                            width: size.width(),
-                            unit: SvgMeasurementUnit::Pixel,
-                        },
-                        SvgAttribute::Height {
-                            height: size.height(),
-                            unit: SvgMeasurementUnit::Pixel,
-                        },
-                    ]);
-                    bundle.image.set_attributes(vec![
-                        SvgAttribute::Width {
-                            width: size.width(),
-                            unit: SvgMeasurementUnit::Pixel,
-                        },
-                        SvgAttribute::Height {
-                            height: size.height(),
-                            unit: SvgMeasurementUnit::Pixel,
-                        },
-                    ]);
-                }
-                _ => {}
-            },
-            _ => {}
-        }
     }
 }
 
@@ -530,12 +517,21 @@ pub fn apply_clip_content_changes(
 pub fn apply_path_mixin_changes(
     mut query: Query<
         (&PathMixin, &mut SvgBundleVariant),
-        (With<ArbNode>, Without<ArbStyle>, Changed<PathMixin>),
+        (
+            With<ArbNode>,
+            Without<ArbStyle>,
+            Or<(Changed<PathMixin>, Changed<StyleChildrenMixin>)>,
+        ),
     >,
     mut style_bundle_query: Query<
         &mut SvgBundleVariant,
         (
-            Or<(With<FillArbStyle>, With<DropShadowArbStyle>)>,
+            Or<(
+                With<FillArbStyle>,
+                With<DropShadowArbStyle>,
+                With<InnerShadowArbStyle>,
+                With<BackgroundBlurArbStyle>,
+            )>,
             Without<ArbNode>,
         ),
     >,
@@ -591,6 +587,22 @@ pub fn apply_path_mixin_changes(
                                 },
                             ])
                         }
+                        SvgBundleVariant::InnerShadowEffect(bundle) => {
+                            bundle.shape_path.set_attributes(vec![
+                                SvgAttribute::D { d: path.into() },
+                                SvgAttribute::FillRule {
+                                    fill_rule: *winding_rule,
+                                },
+                            ])
+                        }
+                        SvgBundleVariant::BackgroundBlurEffect(bundle) => {
+                            bundle.clip_path_path.set_attributes(vec![
+                                SvgAttribute::D { d: path.into() },
+                                SvgAttribute::ClipRule {
+                                    clip_rule: *winding_rule,
+                                },
+                            ])
+                        }
                         _ => {}
                     }
                 }
@@ -634,7 +646,10 @@ pub fn apply_stroke_path_mixin_changes(
 pub fn apply_solid_paint_changes(
     paint_query: Query<
         (&SolidArbPaint, &PaintParentMixin),
-        (With<ArbPaint>, Changed<SolidArbPaint>),
+        (
+            With<ArbPaint>,
+            Or<(Changed<SolidArbPaint>, Changed<PaintParentMixin>)>,
+        ),
     >,
     mut style_query: Query<&mut SvgBundleVariant>,
 ) {
@@ -658,19 +673,33 @@ pub fn apply_solid_paint_changes(
 // -> Either new system to handle those or integrate into this system
 pub fn apply_gradient_paint_changes(
     mut svg_context_res: ResMut<SvgContextRes>,
-    paint_query: Query<
-        (&GradientArbPaint, &PaintParentMixin),
-        (With<ArbPaint>, Changed<GradientArbPaint>),
+    changed_paint_query: Query<
+        &PaintParentMixin,
+        (
+            With<GradientArbPaint>,
+            Or<(Changed<GradientArbPaint>, Changed<PaintParentMixin>)>,
+        ),
     >,
-    mut style_query: Query<(&mut SvgBundleVariant, &SizeMixin)>,
+    // Gradients are positioned relative to the size of the shape
+    changed_style_query: Query<Entity, (With<PaintChildMixin>, Changed<SizeMixin>)>,
+    paint_query: Query<&GradientArbPaint, With<ArbPaint>>,
+    mut style_query: Query<(&mut SvgBundleVariant, &SizeMixin, &PaintChildMixin)>,
 ) {
-    for (gradient_paint, PaintParentMixin(paint_parent_entities)) in paint_query.iter() {
-        for paint_parent_entity in paint_parent_entities {
-            if let Ok((mut bundle_variant, SizeMixin(size))) =
-                style_query.get_mut(*paint_parent_entity)
-            {
+    let style_entities: HashSet<Entity> = changed_paint_query
+        .iter()
+        .flat_map(|PaintParentMixin(paint_parent_entities)| paint_parent_entities.iter().copied())
+        .chain(changed_style_query.iter())
+        .collect();
+
+    for style_entity in style_entities {
+        if let Ok((mut bundle_variant, SizeMixin(size), PaintChildMixin(paint_entity))) =
+            style_query.get_mut(style_entity)
+        {
+            if let Ok(gradient_paint) = paint_query.get(*paint_entity) {
                 match bundle_variant.as_mut() {
                     SvgBundleVariant::GradientFill(bundle) => {
+                        bundle.set_variant(&gradient_paint.variant, &mut svg_context_res);
+
                         match gradient_paint.variant {
                             GradientVariant::Linear { transform } => {
                                 let (start, end) =
@@ -692,19 +721,10 @@ pub fn apply_gradient_paint_changes(
 
                                 // Add new gradient stop elements
                                 for gradient_stop in &gradient_paint.stops {
-                                    let mut gradient_stop_element =
-                                        svg_context_res.create_element(SvgTag::Stop);
-                                    gradient_stop_element.set_attributes(vec![
-                                        SvgAttribute::Offset {
-                                            offset: gradient_stop.position.get(),
-                                        },
-                                        SvgAttribute::StopColor {
-                                            stop_color: (&gradient_stop.color).into(),
-                                        },
-                                        SvgAttribute::StopOpacity {
-                                            stop_opacity: gradient_stop.opacity.get(),
-                                        },
-                                    ]);
+                                    let mut gradient_stop_element = create_gradient_stop_element(
+                                        &mut svg_context_res,
+                                        gradient_stop,
+                                    );
                                     bundle
                                         .gradient
                                         .append_child_in_bundle_context(&mut gradient_stop_element);
@@ -714,6 +734,15 @@ pub fn apply_gradient_paint_changes(
                             GradientVariant::Radial { transform } => {
                                 // TODO
                             }
+                            GradientVariant::Angular { .. } | GradientVariant::Diamond { .. } => {
+                                apply_gradient_mesh(
+                                    &mut svg_context_res,
+                                    bundle,
+                                    &gradient_paint.variant,
+                                    &gradient_paint.stops,
+                                    size,
+                                );
+                            }
                         }
                     }
                     _ => {}
@@ -723,6 +752,255 @@ pub fn apply_gradient_paint_changes(
     }
 }
 
+fn create_gradient_stop_element(
+    svg_context_res: &mut SvgContextRes,
+    gradient_stop: &GradientColorStop,
+) -> SvgElement {
+    let mut gradient_stop_element = svg_context_res.create_element(SvgTag::Stop);
+    gradient_stop_element.set_attributes(vec![
+        SvgAttribute::Offset {
+            offset: gradient_stop.position.get(),
+        },
+        // Color alpha is folded into the stop opacity
+        SvgAttribute::StopColor {
+            stop_color: (&gradient_stop.color.with_alpha(1.0)).into(),
+        },
+        SvgAttribute::StopOpacity {
+            stop_opacity: gradient_stop.opacity.get() * gradient_stop.color.get_alpha(),
+        },
+    ]);
+    return gradient_stop_element;
+}
+
+/// Number of solid wedges an angular gradient is approximated with.
+const ANGULAR_GRADIENT_WEDGE_COUNT: usize = 180;
+
+/// Renders angular and diamond gradients as a mesh of wedges within the pattern.
+///
+/// The wedges are built in the gradient space (centered at 0.5, 0.5)
+/// and mapped onto the shape by the pattern transform,
+/// so that any gradient transform (including skew) is applied exactly.
+fn apply_gradient_mesh(
+    svg_context_res: &mut SvgContextRes,
+    bundle: &mut GradientFillStyleSvgBundle,
+    gradient_variant: &GradientVariant,
+    stops: &[GradientColorStop],
+    size: &Size,
+) {
+    // Remove old mesh elements
+    bundle.gradient.clear_children();
+    #[cfg(feature = "output_svg_element_changes")]
+    bundle
+        .mesh_elements
+        .iter_mut()
+        .for_each(|mesh_element| mesh_element.destroy());
+    bundle.mesh_elements.clear();
+
+    let transform = match gradient_variant {
+        GradientVariant::Angular { transform } | GradientVariant::Diamond { transform } => {
+            transform
+        }
+        _ => return,
+    };
+    let user_from_gradient = Mat3::from_scale(size.to_vec2()) * transform.inverse();
+    let gradient_from_user = user_from_gradient.inverse();
+    let center = Vec2::splat(0.5);
+
+    // Cover the shape and a margin around it (e.g. for outside strokes)
+    let margin = size.width().max(size.height());
+    let radius = [
+        Vec2::new(-margin, -margin),
+        Vec2::new(size.width() + margin, -margin),
+        Vec2::new(-margin, size.height() + margin),
+        Vec2::new(size.width() + margin, size.height() + margin),
+    ]
+    .iter()
+    .map(|corner| {
+        gradient_from_user
+            .transform_point2(*corner)
+            .distance(center)
+    })
+    .fold(0.0, f32::max);
+    if !radius.is_finite() || radius <= 0.0 {
+        return;
+    }
+
+    // Content of the pattern is positioned relative to the origin of the pattern tile
+    let origin = center - radius;
+    let tile_center = center - origin;
+    bundle.gradient.set_attributes(vec![
+        SvgAttribute::X {
+            x: origin.x,
+            unit: SvgMeasurementUnit::Pixel,
+        },
+        SvgAttribute::Y {
+            y: origin.y,
+            unit: SvgMeasurementUnit::Pixel,
+        },
+        SvgAttribute::Width {
+            width: radius * 2.0,
+            unit: SvgMeasurementUnit::Pixel,
+        },
+        SvgAttribute::Height {
+            height: radius * 2.0,
+            unit: SvgMeasurementUnit::Pixel,
+        },
+        SvgAttribute::PatternTransform {
+            pattern_transform: (&user_from_gradient).into(),
+        },
+    ]);
+
+    match gradient_variant {
+        GradientVariant::Angular { .. } => {
+            let step = std::f32::consts::TAU / ANGULAR_GRADIENT_WEDGE_COUNT as f32;
+            // Reach the corners of the pattern tile with the outer edge of each wedge
+            let wedge_radius = radius * std::f32::consts::SQRT_2 / (step / 2.0).cos();
+
+            for index in 0..ANGULAR_GRADIENT_WEDGE_COUNT {
+                let (color, opacity) = sample_gradient_stops(
+                    stops,
+                    (index as f32 + 0.5) / ANGULAR_GRADIENT_WEDGE_COUNT as f32,
+                );
+
+                // Let opaque wedges overlap their successor
+                // to hide anti-aliasing seams between the wedges
+                let overlap = if opacity >= 1.0 { step / 2.0 } else { 0.0 };
+                let start_angle = index as f32 * step;
+                let end_angle = start_angle + step + overlap;
+
+                let mut path_builder = PathBuilder::new();
+                path_builder.move_to(tile_center.x, tile_center.y);
+                for angle in [start_angle, end_angle] {
+                    let point = tile_center + Vec2::from_angle(angle) * wedge_radius;
+                    path_builder.line_to(point.x, point.y);
+                }
+                path_builder.close();
+
+                if let Some(path) = path_builder.finish() {
+                    let mut wedge_element = svg_context_res.create_element(SvgTag::Path);
+                    wedge_element.set_attribute(SvgAttribute::D { d: (&path).into() });
+                    wedge_element.set_style(SvgStyle::Fill {
+                        fill: SvgStyleColor::RGBA {
+                            red: color.get_red(),
+                            green: color.get_green(),
+                            blue: color.get_blue(),
+                            alpha: opacity,
+                        },
+                    });
+                    bundle
+                        .gradient
+                        .append_child_in_bundle_context(&mut wedge_element);
+                    bundle.mesh_elements.push(wedge_element);
+                }
+            }
+        }
+        GradientVariant::Diamond { .. } => {
+            // Within each quadrant the diamond gradient is a linear gradient
+            // along the diagonal, reaching the end at a distance of 0.5 on the axes
+            for direction in [
+                Vec2::new(1.0, 1.0),
+                Vec2::new(-1.0, 1.0),
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(1.0, -1.0),
+            ] {
+                let end = tile_center + direction * 0.25;
+                let mut linear_gradient_element =
+                    svg_context_res.create_element(SvgTag::LinearGradient);
+                linear_gradient_element.set_attributes(vec![
+                    SvgAttribute::GradientUnits {
+                        gradient_units: SvgUnits::UserSpaceOnUse,
+                    },
+                    SvgAttribute::X1 { x1: tile_center.x },
+                    SvgAttribute::Y1 { y1: tile_center.y },
+                    SvgAttribute::X2 { x2: end.x },
+                    SvgAttribute::Y2 { y2: end.y },
+                ]);
+                for gradient_stop in stops {
+                    let mut gradient_stop_element =
+                        create_gradient_stop_element(svg_context_res, gradient_stop);
+                    linear_gradient_element
+                        .append_child_in_bundle_context(&mut gradient_stop_element);
+                    bundle.mesh_elements.push(gradient_stop_element);
+                }
+
+                let mut path_builder = PathBuilder::new();
+                path_builder.move_to(tile_center.x, tile_center.y);
+                for corner in [
+                    Vec2::new(direction.x, 0.0),
+                    direction,
+                    Vec2::new(0.0, direction.y),
+                ] {
+                    let point = tile_center + corner * radius;
+                    path_builder.line_to(point.x, point.y);
+                }
+                path_builder.close();
+
+                if let Some(path) = path_builder.finish() {
+                    let mut wedge_element = svg_context_res.create_element(SvgTag::Path);
+                    wedge_element.set_attribute(SvgAttribute::D { d: (&path).into() });
+                    wedge_element.set_style(SvgStyle::Fill {
+                        fill: SvgStyleColor::Reference {
+                            id: linear_gradient_element.get_id(),
+                        },
+                    });
+                    bundle
+                        .gradient
+                        .append_child_in_bundle_context(&mut linear_gradient_element);
+                    bundle
+                        .gradient
+                        .append_child_in_bundle_context(&mut wedge_element);
+                    bundle.mesh_elements.push(linear_gradient_element);
+                    bundle.mesh_elements.push(wedge_element);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Helper function to interpolate the color and opacity of the gradient at the given position.
+///
+/// The stops don't need to be sorted by their position.
+/// Positions before the first or after the last stop take the color of that stop.
+fn sample_gradient_stops(stops: &[GradientColorStop], position: f32) -> (Color, f32) {
+    let compare = |a: &&GradientColorStop, b: &&GradientColorStop| {
+        a.position.get().total_cmp(&b.position.get())
+    };
+    let from = stops
+        .iter()
+        .filter(|stop| stop.position.get() <= position)
+        .max_by(compare)
+        .or_else(|| stops.iter().min_by(compare));
+    let to = stops
+        .iter()
+        .filter(|stop| stop.position.get() >= position)
+        .min_by(compare)
+        .or_else(|| stops.iter().max_by(compare));
+
+    match (from, to) {
+        (Some(from), Some(to)) => {
+            let range = to.position.get() - from.position.get();
+            let t = if range > 0.0 {
+                (position - from.position.get()) / range
+            } else {
+                0.0
+            };
+            let lerp =
+                |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+            let opacity = |stop: &GradientColorStop| stop.opacity.get() * stop.color.get_alpha();
+            return (
+                Color::new_rgb(
+                    lerp(from.color.get_red(), to.color.get_red()),
+                    lerp(from.color.get_green(), to.color.get_green()),
+                    lerp(from.color.get_blue(), to.color.get_blue()),
+                ),
+                opacity(from) + (opacity(to) - opacity(from)) * t,
+            );
+        }
+        _ => return (Color::black(), 0.0),
+    }
+}
+
 /// Helper function to extract the x and y positions of the start and end of the linear gradient
 /// (scale is not important here).
 ///
@@ -743,150 +1021,153 @@ fn extract_start_end_point_from_mat3(shape_size: &Size, transform: &Mat3) -> (Ve
 // -> Either new system to handle those or integrate into this system
 pub fn apply_image_paint_changes(
     assets_res: Res<AssetsRes>,
-    paint_query: Query<
-        (&ImageArbPaint, &ImageAssetMixin, &PaintParentMixin),
-        (With<ArbPaint>, Changed<ImageArbPaint>),
+    paint_query: Query<(&ImageArbPaint, &ImageAssetMixin), With<ArbPaint>>,
+    changed_paint_query: Query<
+        &PaintParentMixin,
+        (
+            With<ArbPaint>,
+            With<ImageArbPaint>,
+            Or<(
+                Changed<ImageArbPaint>,
+                Changed<ImageAssetMixin>,
+                Changed<PaintParentMixin>,
+            )>,
+        ),
+    >,
+    changed_style_query: Query<
+        Entity,
+        (
+            With<ArbStyle>,
+            Or<(Changed<SizeMixin>, Changed<PaintChildMixin>)>,
+        ),
     >,
-    mut style_query: Query<(&mut SvgBundleVariant, &SizeMixin)>,
+    mut style_query: Query<(&PaintChildMixin, &SizeMixin, &mut SvgBundleVariant), With<ArbStyle>>,
 ) {
-    for (image_paint, ImageAssetMixin(maybe_image_id), PaintParentMixin(paint_parent_entities)) in
-        paint_query.iter()
-    {
-        if let Some(image) = maybe_image_id.and_then(|id| assets_res.get_image(id)) {
-            for paint_parent_entity in paint_parent_entities {
-                if let Ok((mut bundle_variant, SizeMixin(size))) =
-                    style_query.get_mut(*paint_parent_entity)
-                {
-                    match bundle_variant.as_mut() {
-                        SvgBundleVariant::ImageFill(bundle) => match image_paint.scale_mode {
-                            ImageScaleMode::Tile {
-                                rotation,
-                                scaling_factor,
-                            } => {
-                                let tile_width = f32::from(image.width) * scaling_factor;
-                                let tile_height = f32::from(image.height) * scaling_factor;
-
-                                bundle.pattern.set_attributes(vec![
-                                    SvgAttribute::PatternTransform {
-                                        pattern_transform: SvgTransformAttribute::Rotate {
-                                            rotation: rotation,
-                                        },
-                                    },
-                                    SvgAttribute::Width {
-                                        width: tile_width,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                    SvgAttribute::Height {
-                                        height: tile_height,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                ]);
-                                bundle.image.set_attributes(vec![
-                                    SvgAttribute::Width {
-                                        width: tile_width,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                    SvgAttribute::Height {
-                                        height: tile_height,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                ]);
-                            }
-                            ImageScaleMode::Crop { transform } => {
-                                let (image_width, image_height, image_transform) =
-                                    calculate_cropped_image_transform(
-                                        size,
-                                        (f32::from(image.width), f32::from(image.height)),
-                                        &transform,
-                                    );
+    // The image layout depends on the paint, the image asset and the size of the node
+    let mut changed_style_entities: HashSet<Entity> = changed_style_query.iter().collect();
+    changed_style_entities.extend(
+        changed_paint_query
+            .iter()
+            .flat_map(|PaintParentMixin(paint_parent_entities)| {
+                paint_parent_entities.iter().copied()
+            }),
+    );
 
-                                bundle.pattern.set_attributes(vec![
-                                    SvgAttribute::Width {
-                                        width: image_width,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                    SvgAttribute::Height {
-                                        height: image_height,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                ]);
-                                bundle.image.set_attributes(vec![
-                                    SvgAttribute::Transform {
-                                        transform: (&image_transform).into(),
-                                    },
-                                    SvgAttribute::Width {
-                                        width: image_width,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                    SvgAttribute::Height {
-                                        height: image_height,
-                                        unit: SvgMeasurementUnit::Pixel,
-                                    },
-                                ]);
-                            }
-                            _ => {}
-                        },
-                        _ => {}
-                    }
+    for entity in changed_style_entities {
+        let (PaintChildMixin(paint_entity), SizeMixin(size), mut bundle_variant) =
+            match style_query.get_mut(entity) {
+                Ok(style) => style,
+                Err(_) => continue,
+            };
+        if !matches!(bundle_variant.as_ref(), SvgBundleVariant::ImageFill(_)) {
+            continue;
+        }
+
+        let (image_paint, ImageAssetMixin(maybe_image_id)) = match paint_query.get(*paint_entity) {
+            Ok(paint) => paint,
+            Err(_) => continue,
+        };
+        let image = match maybe_image_id.and_then(|id| assets_res.get_image(id)) {
+            Some(image) => image,
+            None => continue,
+        };
+        let size = *size;
+
+        match bundle_variant.as_mut() {
+            SvgBundleVariant::ImageFill(bundle) => match image_paint.scale_mode {
+                // Scaling is handled by the 'preserveAspectRatio' attribute of the image
+                ImageScaleMode::Fill => {
+                    bundle.variant = ImageFillStyleVariant::Fill;
+                    set_image_preserve_aspect_ratio(bundle, "xMidYMid slice");
+                    set_image_pattern_size(bundle, size.width(), size.height());
+                    bundle.pattern.remove_attribute("patternTransform");
+                    bundle.image.remove_attribute("transform");
                 }
-            }
+                ImageScaleMode::Fit => {
+                    bundle.variant = ImageFillStyleVariant::Fit;
+                    set_image_preserve_aspect_ratio(bundle, "xMidYMid meet");
+                    set_image_pattern_size(bundle, size.width(), size.height());
+                    bundle.pattern.remove_attribute("patternTransform");
+                    bundle.image.remove_attribute("transform");
+                }
+                // Cropped and tiled images are sized and positioned explicitly
+                ImageScaleMode::Crop { transform } => {
+                    bundle.variant = ImageFillStyleVariant::Crop;
+                    set_image_preserve_aspect_ratio(bundle, "none");
+                    set_image_pattern_size(bundle, size.width(), size.height());
+                    bundle.pattern.remove_attribute("patternTransform");
+                    bundle.image.set_attribute(SvgAttribute::Transform {
+                        transform: (&calculate_cropped_image_transform(&size, &transform)).into(),
+                    });
+                }
+                ImageScaleMode::Tile {
+                    rotation,
+                    scaling_factor,
+                } => {
+                    bundle.variant = ImageFillStyleVariant::Tile;
+                    set_image_preserve_aspect_ratio(bundle, "none");
+                    set_image_pattern_size(
+                        bundle,
+                        f32::from(image.width) * scaling_factor,
+                        f32::from(image.height) * scaling_factor,
+                    );
+                    bundle
+                        .pattern
+                        .set_attribute(SvgAttribute::PatternTransform {
+                            pattern_transform: SvgTransformAttribute::Rotate { rotation },
+                        });
+                    bundle.image.remove_attribute("transform");
+                }
+            },
+            _ => {}
         }
     }
 }
 
-// TODO: Improve
-fn calculate_cropped_image_transform(
-    parent_size: &Size,
-    image_size: (f32, f32),
-    transform: &Mat3,
-) -> (f32, f32, Mat3) {
-    let (parent_width, parent_height) = parent_size.to_tuple();
-    let (image_width, image_height) = image_size;
-
-    // Calculate aspect ratios for container and image
-    let container_ratio = parent_width / parent_height;
-    let image_ratio = image_width / image_height;
-
-    // Determine new image dimensions based on aspect ratio arbarison
-    let (adjusted_image_width, adjusted_image_height) = if image_ratio > container_ratio {
-        (parent_height * image_ratio, parent_height)
-    } else {
-        (parent_width, parent_width / image_ratio)
-    };
-
-    // Calculate scale adjustment ratios
-    let x_ratio = parent_width / adjusted_image_width;
-    let y_ratio = parent_height / adjusted_image_height;
-
-    // Extract scale components from the matrix and adjust them
-    let scale_x = transform.x_axis.x;
-    let scale_y = transform.y_axis.y;
-    let adjusted_scale_x = (1.0 / scale_x) * x_ratio;
-    let adjusted_scale_y = (1.0 / scale_y) * y_ratio;
+/// Helper function to set how the image is scaled into its element.
+fn set_image_preserve_aspect_ratio(bundle: &mut ImageFillStyleSvgBundle, value: &str) {
+    bundle
+        .image
+        .set_attribute(SvgAttribute::PreserveAspectRatio {
+            preserve_aspect_ratio: String::from(value),
+        });
+}
 
-    // Calculate adjusted translation.
-    let tx = -adjusted_image_width * transform.z_axis.x * adjusted_scale_x;
-    let ty = -adjusted_image_height * transform.z_axis.y * adjusted_scale_y;
+/// Helper function to size the pattern tile and the image it contains.
+fn set_image_pattern_size(bundle: &mut ImageFillStyleSvgBundle, width: f32, height: f32) {
+    let size_attributes = vec![
+        SvgAttribute::Width {
+            width,
+            unit: SvgMeasurementUnit::Pixel,
+        },
+        SvgAttribute::Height {
+            height,
+            unit: SvgMeasurementUnit::Pixel,
+        },
+    ];
+    bundle.pattern.set_attributes(size_attributes.clone());
+    bundle.image.set_attributes(size_attributes);
+}
 
-    // Construct the adjusted transformation matrix
-    let adjusted_transform = Mat3::from_scale_angle_translation(
-        Vec2::new(adjusted_scale_x, adjusted_scale_y),
-        0.0,
-        Vec2::new(tx, ty),
-    );
+/// Helper function to calculate the transform of an image element spanning the node,
+/// given the crop transform that maps the normalized node space into the normalized image space.
+fn calculate_cropped_image_transform(size: &Size, transform: &Mat3) -> Mat3 {
+    let node_from_unit = Mat3::from_scale(size.to_vec2());
+    if node_from_unit.determinant() == 0.0 || transform.determinant() == 0.0 {
+        return Mat3::IDENTITY;
+    }
 
-    return (
-        adjusted_image_width,
-        adjusted_image_height,
-        adjusted_transform,
-    );
+    return node_from_unit * transform.inverse() * node_from_unit.inverse();
 }
 
 pub fn apply_image_asset_mixin_changes(
     assets_res: Res<AssetsRes>,
     paint_query: Query<
         (&ImageAssetMixin, &PaintParentMixin),
-        (With<ArbPaint>, Changed<ImageAssetMixin>),
+        (
+            With<ArbPaint>,
+            Or<(Changed<ImageAssetMixin>, Changed<PaintParentMixin>)>,
+        ),
     >,
     mut style_query: Query<&mut SvgBundleVariant>,
 ) {
@@ -921,6 +1202,77 @@ pub fn apply_image_asset_mixin_changes(
     }
 }
 
+/// Strength of the per channel gain applied by the temperature and tint image filters.
+const IMAGE_WHITE_BALANCE_STRENGTH: f32 = 0.2;
+
+pub fn apply_image_filter_changes(
+    paint_query: Query<
+        (&ImageArbPaint, &PaintParentMixin),
+        (
+            With<ArbPaint>,
+            Or<(Changed<ImageArbPaint>, Changed<PaintParentMixin>)>,
+        ),
+    >,
+    mut style_query: Query<&mut SvgBundleVariant>,
+) {
+    for (ImageArbPaint { filters, .. }, PaintParentMixin(paint_parent_entities)) in
+        paint_query.iter()
+    {
+        for paint_parent_entity in paint_parent_entities {
+            if let Ok(mut bundle_variant) = style_query.get_mut(*paint_parent_entity) {
+                match bundle_variant.as_mut() {
+                    SvgBundleVariant::ImageFill(bundle) => {
+                        if filters.is_identity() {
+                            bundle.image.set_attribute(SvgAttribute::Filter {
+                                filter: SvgAttributeFilter::None,
+                            });
+                            continue;
+                        }
+
+                        // Exposure and white balance scale each channel,
+                        // contrast scales each channel around the mid tone
+                        let exposure = 2f32.powf(filters.exposure);
+                        let contrast = 1.0 + filters.contrast;
+                        let intercept = 0.5 * (1.0 - contrast);
+                        let white_balance = [
+                            1.0 + filters.temperature * IMAGE_WHITE_BALANCE_STRENGTH,
+                            1.0 - filters.tint * IMAGE_WHITE_BALANCE_STRENGTH,
+                            1.0 - filters.temperature * IMAGE_WHITE_BALANCE_STRENGTH,
+                        ];
+                        for (fe_func, gain) in [
+                            &mut bundle.fe_func_r,
+                            &mut bundle.fe_func_g,
+                            &mut bundle.fe_func_b,
+                        ]
+                        .into_iter()
+                        .zip(white_balance)
+                        {
+                            fe_func.set_attributes(vec![
+                                SvgAttribute::Slope {
+                                    slope: exposure * gain * contrast,
+                                },
+                                SvgAttribute::Intercept { intercept },
+                            ]);
+                        }
+
+                        bundle.fe_color_matrix.set_attribute(SvgAttribute::Values {
+                            values: SvgAttributeValues::ColorMatrix(ColorMatrix::from_saturation(
+                                1.0 + filters.saturation,
+                            )),
+                        });
+                        bundle.image.set_attribute(SvgAttribute::Filter {
+                            filter: SvgAttributeFilter::Reference {
+                                id: bundle.filter.get_id(),
+                            },
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
 pub fn apply_drop_shadow_changes(
     mut query: Query<
         (&DropShadowArbStyle, &mut SvgBundleVariant, &SizeMixin),
@@ -979,7 +1331,7 @@ pub fn apply_drop_shadow_changes(
                         color.get_red(),
                         color.get_green(),
                         color.get_blue(),
-                        1.0, // Opacity is applied on wrapping group tag
+                        color.get_alpha(), // Style opacity is applied on wrapping group tag
                     )),
                 });
                 bundle.fe_offset.set_attributes(vec![
@@ -1001,3 +1353,1409 @@ pub fn apply_drop_shadow_changes(
         }
     }
 }
+
+pub fn apply_inner_shadow_changes(
+    mut query: Query<
+        (&InnerShadowArbStyle, &mut SvgBundleVariant),
+        (With<InnerShadowArbStyle>, Changed<InnerShadowArbStyle>),
+    >,
+) {
+    for (
+        InnerShadowArbStyle {
+            color,
+            position,
+            spread,
+            blur,
+        },
+        mut bundle_variant,
+    ) in query.iter_mut()
+    {
+        match bundle_variant.as_mut() {
+            SvgBundleVariant::InnerShadowEffect(bundle) => {
+                bundle.fe_color_matrix.set_attribute(SvgAttribute::Values {
+                    values: SvgAttributeValues::ColorMatrix(ColorMatrix::from_rgba(
+                        color.get_red(),
+                        color.get_green(),
+                        color.get_blue(),
+                        color.get_alpha(), // Style opacity is applied on wrapping group tag
+                    )),
+                });
+                bundle.fe_offset.set_attributes(vec![
+                    SvgAttribute::DX { dx: position.x },
+                    SvgAttribute::DY { dy: position.y },
+                ]);
+                bundle
+                    .source_alpha_fe_morphology
+                    .set_attribute(SvgAttribute::Radius {
+                        radius: spread.to_pt(),
+                    });
+                bundle
+                    .fe_gaussian_blur
+                    .set_attributes(vec![SvgAttribute::StdDeviation {
+                        std_deviation: blur.to_pt() / 2.0,
+                    }]);
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn apply_layer_blur_changes(
+    changed_geometry_query: Query<
+        (),
+        Or<(
+            Changed<SizeMixin>,
+            Changed<PathMixin>,
+            Changed<Transform>,
+            Changed<Children>,
+            Changed<VisibilityMixin>,
+            Changed<StyleChildrenMixin>,
+            Changed<FrameArbNode>,
+            Changed<StrokePathMixin>,
+            Changed<DropShadowArbStyle>,
+            Changed<LayerBlurArbStyle>,
+        )>,
+    >,
+    mut query: Query<(&LayerBlurArbStyle, &StyleParentMixin, &mut SvgBundleVariant)>,
+    node_query: Query<
+        (
+            &SizeMixin,
+            Option<&PathMixin>,
+            &Transform,
+            &VisibilityMixin,
+            Option<&StyleChildrenMixin>,
+            Option<&Children>,
+            Option<&FrameArbNode>,
+        ),
+        With<ArbNode>,
+    >,
+    style_query: Query<
+        (
+            &VisibilityMixin,
+            Option<&StrokePathMixin>,
+            Option<&DropShadowArbStyle>,
+            Option<&LayerBlurArbStyle>,
+        ),
+        With<ArbStyle>,
+    >,
+) {
+    // The rendered bounds depend on all descendants of the node (e.g. a moved grandchild),
+    // thus the filter regions are recomputed on any geometry change
+    if changed_geometry_query.is_empty() {
+        return;
+    }
+
+    for (LayerBlurArbStyle { blur }, StyleParentMixin(node_entity), mut bundle_variant) in
+        query.iter_mut()
+    {
+        match bundle_variant.as_mut() {
+            SvgBundleVariant::LayerBlurEffect(bundle) => {
+                // Extend the filter region so that the blurred edges aren't cut off.
+                // The region is specified in user space (the local space of the node)
+                // as the bounding box is empty for nodes without width or height (e.g. lines)
+                // and excludes effects like drop shadows
+                let bounds = match compute_unblurred_bounds(*node_entity, &node_query, &style_query)
+                {
+                    Some(bounds) => bounds,
+                    None => continue,
+                };
+                let margin = blur.to_pt() * 1.5;
+
+                bundle.filter.set_attributes(vec![
+                    SvgAttribute::X {
+                        x: bounds.left() - margin,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                    SvgAttribute::Y {
+                        y: bounds.top() - margin,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                    SvgAttribute::Width {
+                        width: bounds.width() + margin * 2.0,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                    SvgAttribute::Height {
+                        height: bounds.height() + margin * 2.0,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                ]);
+                bundle
+                    .fe_gaussian_blur
+                    .set_attribute(SvgAttribute::StdDeviation {
+                        std_deviation: blur.to_pt() / 2.0,
+                    });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Computes the bounds of everything the node renders in its local space
+/// (its shape, strokes, drop shadows and unclipped children) before its layer blur is applied.
+fn compute_unblurred_bounds(
+    entity: Entity,
+    node_query: &Query<
+        (
+            &SizeMixin,
+            Option<&PathMixin>,
+            &Transform,
+            &VisibilityMixin,
+            Option<&StyleChildrenMixin>,
+            Option<&Children>,
+            Option<&FrameArbNode>,
+        ),
+        With<ArbNode>,
+    >,
+    style_query: &Query<
+        (
+            &VisibilityMixin,
+            Option<&StrokePathMixin>,
+            Option<&DropShadowArbStyle>,
+            Option<&LayerBlurArbStyle>,
+        ),
+        With<ArbStyle>,
+    >,
+) -> Option<Rect> {
+    let (
+        SizeMixin(size),
+        maybe_path_mixin,
+        _,
+        _,
+        maybe_style_children_mixin,
+        maybe_children,
+        maybe_frame,
+    ) = node_query.get(entity).ok()?;
+
+    let shape_bounds = match maybe_path_mixin {
+        Some(PathMixin { path, .. }) => path.bounds(),
+        None => Rect::from_xywh(0.0, 0.0, size.width(), size.height())?,
+    };
+    let mut bounds = shape_bounds;
+
+    if let Some(StyleChildrenMixin(style_entities)) = maybe_style_children_mixin {
+        for style_entity in style_entities.iter() {
+            match style_query.get(*style_entity) {
+                Ok((VisibilityMixin(true), Some(StrokePathMixin { path, .. }), _, _)) => {
+                    bounds = union_rects(&bounds, &path.bounds());
+                }
+                Ok((
+                    VisibilityMixin(true),
+                    _,
+                    Some(DropShadowArbStyle {
+                        position,
+                        spread,
+                        blur,
+                        ..
+                    }),
+                    _,
+                )) => {
+                    let margin = spread.to_pt() + blur.to_pt() * 1.5;
+                    if let Some(shadow_bounds) = Rect::from_ltrb(
+                        shape_bounds.left() + position.x - margin,
+                        shape_bounds.top() + position.y - margin,
+                        shape_bounds.right() + position.x + margin,
+                        shape_bounds.bottom() + position.y + margin,
+                    ) {
+                        bounds = union_rects(&bounds, &shadow_bounds);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Children of frames that clip their content can't exceed the shape
+    let clips_content = maybe_frame.map_or(false, |frame| frame.clip_content);
+    if let (Some(children), false) = (maybe_children, clips_content) {
+        for child in children.iter() {
+            if let Some(child_bounds) = compute_rendered_bounds(*child, node_query, style_query) {
+                bounds = union_rects(&bounds, &child_bounds);
+            }
+        }
+    }
+
+    return Some(bounds);
+}
+
+/// Computes the bounds of everything the node renders in the local space of its parent.
+fn compute_rendered_bounds(
+    entity: Entity,
+    node_query: &Query<
+        (
+            &SizeMixin,
+            Option<&PathMixin>,
+            &Transform,
+            &VisibilityMixin,
+            Option<&StyleChildrenMixin>,
+            Option<&Children>,
+            Option<&FrameArbNode>,
+        ),
+        With<ArbNode>,
+    >,
+    style_query: &Query<
+        (
+            &VisibilityMixin,
+            Option<&StrokePathMixin>,
+            Option<&DropShadowArbStyle>,
+            Option<&LayerBlurArbStyle>,
+        ),
+        With<ArbStyle>,
+    >,
+) -> Option<Rect> {
+    let (_, _, transform, VisibilityMixin(visible), maybe_style_children_mixin, _, _) =
+        node_query.get(entity).ok()?;
+    if !visible {
+        return None;
+    }
+
+    let mut bounds = compute_unblurred_bounds(entity, node_query, style_query)?;
+
+    // Only the first visible layer blur is applied to the node
+    let maybe_blur = maybe_style_children_mixin.and_then(|StyleChildrenMixin(style_entities)| {
+        style_entities
+            .iter()
+            .find_map(|style_entity| match style_query.get(*style_entity) {
+                Ok((VisibilityMixin(true), _, _, Some(LayerBlurArbStyle { blur }))) => Some(*blur),
+                _ => None,
+            })
+    });
+    if let Some(blur) = maybe_blur {
+        bounds = bounds.outset(blur.to_pt() * 1.5, blur.to_pt() * 1.5)?;
+    }
+
+    let mat3 = to_affine_mat3(transform);
+    return bounds.transform(tiny_skia_path::Transform::from_row(
+        mat3.x_axis.x,
+        mat3.x_axis.y,
+        mat3.y_axis.x,
+        mat3.y_axis.y,
+        mat3.z_axis.x,
+        mat3.z_axis.y,
+    ));
+}
+
+fn union_rects(a: &Rect, b: &Rect) -> Rect {
+    Rect::from_ltrb(
+        a.left().min(b.left()),
+        a.top().min(b.top()),
+        a.right().max(b.right()),
+        a.bottom().max(b.bottom()),
+    )
+    .unwrap_or(*a)
+}
+
+/// Extracts the 2D affine part of the transform.
+fn to_affine_mat3(transform: &Transform) -> Mat3 {
+    let mat4 = transform.compute_matrix();
+    return Mat3::from_cols_array(&[
+        mat4.x_axis.x,
+        mat4.x_axis.y,
+        0.0,
+        mat4.y_axis.x,
+        mat4.y_axis.y,
+        0.0,
+        mat4.w_axis.x,
+        mat4.w_axis.y,
+        1.0,
+    ]);
+}
+
+/// Applies the filter of the first visible layer blur style to the node it belongs to.
+pub fn apply_layer_blur_node_filter_changes(
+    changed_node_query: Query<Entity, (With<ArbNode>, Changed<StyleChildrenMixin>)>,
+    changed_layer_blur_query: Query<
+        &StyleParentMixin,
+        (
+            With<LayerBlurArbStyle>,
+            Or<(Changed<LayerBlurArbStyle>, Changed<VisibilityMixin>)>,
+        ),
+    >,
+    mut node_query: Query<
+        (&StyleChildrenMixin, &mut SvgBundleVariant),
+        (With<ArbNode>, Without<ArbStyle>),
+    >,
+    layer_blur_query: Query<
+        (&SvgBundleVariant, &VisibilityMixin),
+        (With<LayerBlurArbStyle>, Without<ArbNode>),
+    >,
+) {
+    let node_entities: HashSet<Entity> = changed_node_query
+        .iter()
+        .chain(
+            changed_layer_blur_query
+                .iter()
+                .map(|StyleParentMixin(parent)| *parent),
+        )
+        .collect();
+
+    for node_entity in node_entities {
+        if let Ok((StyleChildrenMixin(style_entities), mut node_bundle_variant)) =
+            node_query.get_mut(node_entity)
+        {
+            let maybe_filter_id = style_entities.iter().find_map(|style_entity| {
+                match layer_blur_query.get(*style_entity) {
+                    Ok((SvgBundleVariant::LayerBlurEffect(bundle), VisibilityMixin(true))) => {
+                        Some(bundle.filter.get_id())
+                    }
+                    _ => None,
+                }
+            });
+
+            let root_element = node_bundle_variant.get_root_element_mut();
+            if let Some(filter_id) = maybe_filter_id {
+                root_element.set_attribute(SvgAttribute::Filter {
+                    filter: SvgAttributeFilter::Reference { id: filter_id },
+                });
+            } else {
+                root_element.remove_attribute("filter");
+            }
+        }
+    }
+}
+
+pub fn apply_background_blur_changes(
+    mut query: Query<
+        (&BackgroundBlurArbStyle, &mut SvgBundleVariant, &SizeMixin),
+        (
+            With<BackgroundBlurArbStyle>,
+            Or<(Changed<BackgroundBlurArbStyle>, Changed<SizeMixin>)>,
+        ),
+    >,
+) {
+    for (BackgroundBlurArbStyle { blur }, mut bundle_variant, SizeMixin(size)) in query.iter_mut() {
+        match bundle_variant.as_mut() {
+            SvgBundleVariant::BackgroundBlurEffect(bundle) => {
+                // Include the backdrop around the shape (in the local space of the node),
+                // as it's blurred into the edges of the shape
+                let margin = blur.to_pt() * 1.5;
+
+                bundle.filter.set_attributes(vec![
+                    SvgAttribute::X {
+                        x: -margin,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                    SvgAttribute::Y {
+                        y: -margin,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                    SvgAttribute::Width {
+                        width: size.width() + margin * 2.0,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                    SvgAttribute::Height {
+                        height: size.height() + margin * 2.0,
+                        unit: SvgMeasurementUnit::Pixel,
+                    },
+                ]);
+                bundle
+                    .fe_gaussian_blur
+                    .set_attribute(SvgAttribute::StdDeviation {
+                        std_deviation: blur.to_pt() / 2.0,
+                    });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Rebuilds the backdrop of background blur styles
+/// from the styles of the parent and the siblings behind their node.
+pub fn apply_background_blur_backdrop_changes(
+    mut svg_context_res: ResMut<SvgContextRes>,
+    changed_style_query: Query<
+        &StyleParentMixin,
+        (
+            With<BackgroundBlurArbStyle>,
+            Or<(Added<BackgroundBlurArbStyle>, Changed<StyleParentMixin>)>,
+        ),
+    >,
+    changed_node_query: Query<
+        Entity,
+        (
+            With<ArbNode>,
+            Or<(
+                Changed<Transform>,
+                Changed<Parent>,
+                Changed<StyleChildrenMixin>,
+            )>,
+        ),
+    >,
+    changed_children_query: Query<&Children, (With<ArbNode>, Changed<Children>)>,
+    node_query: Query<(&Transform, Option<&Parent>, &StyleChildrenMixin), With<ArbNode>>,
+    children_query: Query<&Children, With<ArbNode>>,
+    node_bundle_query: Query<&SvgBundleVariant, (With<ArbNode>, Without<ArbStyle>)>,
+    mut style_bundle_query: Query<
+        &mut SvgBundleVariant,
+        (With<BackgroundBlurArbStyle>, Without<ArbNode>),
+    >,
+) {
+    let node_entities: HashSet<Entity> = changed_style_query
+        .iter()
+        .map(|StyleParentMixin(parent)| *parent)
+        .chain(changed_node_query.iter())
+        .chain(
+            changed_children_query
+                .iter()
+                .flat_map(|children| children.iter().copied()),
+        )
+        .collect();
+
+    for node_entity in node_entities {
+        let (transform, maybe_parent, StyleChildrenMixin(style_entities)) =
+            match node_query.get(node_entity) {
+                Ok(node) => node,
+                Err(_) => continue,
+            };
+        if !style_entities
+            .iter()
+            .any(|style_entity| style_bundle_query.contains(*style_entity))
+        {
+            continue;
+        }
+
+        // Collect the elements behind the node in the order they are rendered
+        let mut backdrop_element_ids: SmallVec<[SvgElementId; 2]> = SmallVec::new();
+        if let Some(parent) = maybe_parent {
+            if let Some(styles_wrapper_element) = node_bundle_query
+                .get(parent.get())
+                .ok()
+                .and_then(|bundle_variant| bundle_variant.get_styles_wrapper_element())
+            {
+                backdrop_element_ids.push(styles_wrapper_element.get_id());
+            }
+
+            // Children are ordered from the top-most to the bottom-most node
+            if let Ok(siblings) = children_query.get(parent.get()) {
+                if let Some(index) = siblings.iter().position(|entity| *entity == node_entity) {
+                    for sibling in siblings[index + 1..].iter().rev() {
+                        if let Ok(sibling_bundle_variant) = node_bundle_query.get(*sibling) {
+                            backdrop_element_ids
+                                .push(sibling_bundle_variant.get_root_element().get_id());
+                        }
+                    }
+                }
+            }
+        }
+
+        let parent_to_node = to_affine_mat3(transform).inverse();
+        for style_entity in style_entities.iter() {
+            if let Ok(mut style_bundle_variant) = style_bundle_query.get_mut(*style_entity) {
+                match style_bundle_variant.as_mut() {
+                    SvgBundleVariant::BackgroundBlurEffect(bundle) => {
+                        bundle
+                            .backdrop_content_g
+                            .set_attribute(SvgAttribute::Transform {
+                                transform: (&parent_to_node).into(),
+                            });
+
+                        // Remove old backdrop elements
+                        bundle.backdrop_content_g.clear_children();
+                        #[cfg(feature = "output_svg_element_changes")]
+                        bundle
+                            .backdrop_uses
+                            .iter_mut()
+                            .for_each(|backdrop_use| backdrop_use.destroy());
+                        bundle.backdrop_uses.clear();
+
+                        // Add new backdrop elements
+                        for id in backdrop_element_ids.iter() {
+                            let mut use_element = svg_context_res.create_element(SvgTag::Use);
+                            use_element.set_attribute(SvgAttribute::Href {
+                                href: SvgHrefAttribute::Reference { id: *id },
+                            });
+                            bundle
+                                .backdrop_content_g
+                                .append_child_in_bundle_context(&mut use_element);
+                            bundle.backdrop_uses.push(use_element);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::svg_bundle::{
+        node::frame::FrameNodeSvgBundle,
+        style::{
+            background_blur_effect::BackgroundBlurEffectStyleSvgBundle,
+            layer_blur_effect::LayerBlurEffectStyleSvgBundle,
+        },
+    };
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_hierarchy::BuildWorldChildren;
+    use dyn_arb_asset::{
+        asset::{Asset, AssetContent, AssetContentType},
+        asset_id::ImageId,
+    };
+    use dyn_arb_bundles::components::{
+        nodes::ArbNodeVariant,
+        paints::{ArbPaintVariant, ImageFilters},
+        styles::ArbStyleVariant,
+    };
+    use dyn_utils::{
+        properties::opacity::Opacity,
+        units::{abs::Abs, ratio::Ratio},
+    };
+
+    fn stop(position: f32, color: Color) -> GradientColorStop {
+        GradientColorStop {
+            position: Ratio::new(position),
+            color,
+            opacity: Opacity::default(),
+        }
+    }
+
+    fn red_to_blue() -> Vec<GradientColorStop> {
+        vec![
+            stop(0.0, Color::new_rgb(255, 0, 0)),
+            stop(1.0, Color::new_rgb(0, 0, 255)),
+        ]
+    }
+
+    fn build_gradient_mesh(
+        gradient_variant: GradientVariant,
+        stops: &[GradientColorStop],
+    ) -> GradientFillStyleSvgBundle {
+        let mut svg_context_res = SvgContextRes::default();
+        let mut bundle = GradientFillStyleSvgBundle::new(
+            Entity::from_raw(0),
+            gradient_variant,
+            &mut svg_context_res,
+        );
+        apply_gradient_mesh(
+            &mut svg_context_res,
+            &mut bundle,
+            &gradient_variant,
+            stops,
+            &Size::new(Abs::pt(100.0), Abs::pt(100.0)),
+        );
+        return bundle;
+    }
+
+    #[test]
+    fn test_sample_gradient_stops() {
+        let stops = red_to_blue();
+        assert_eq!(
+            sample_gradient_stops(&stops, 0.5),
+            (Color::new_rgb(128, 0, 128), 1.0)
+        );
+        assert_eq!(
+            sample_gradient_stops(&stops, 0.25),
+            (Color::new_rgb(191, 0, 64), 1.0)
+        );
+
+        // Positions outside of the stops take the color of the closest stop
+        assert_eq!(
+            sample_gradient_stops(&stops, -0.5),
+            (Color::new_rgb(255, 0, 0), 1.0)
+        );
+        assert_eq!(
+            sample_gradient_stops(&stops, 1.5),
+            (Color::new_rgb(0, 0, 255), 1.0)
+        );
+        let inner_stops = vec![
+            stop(0.25, Color::new_rgb(255, 0, 0)),
+            stop(0.75, Color::new_rgb(0, 0, 255)),
+        ];
+        assert_eq!(
+            sample_gradient_stops(&inner_stops, 0.1),
+            (Color::new_rgb(255, 0, 0), 1.0)
+        );
+        assert_eq!(
+            sample_gradient_stops(&inner_stops, 0.9),
+            (Color::new_rgb(0, 0, 255), 1.0)
+        );
+
+        // Opacity and color alpha are interpolated as well
+        let translucent_stops = vec![
+            GradientColorStop {
+                opacity: Opacity::new(0.5),
+                ..stop(0.0, Color::new_rgba(255, 0, 0, 0.5))
+            },
+            stop(1.0, Color::new_rgb(255, 0, 0)),
+        ];
+        assert_eq!(
+            sample_gradient_stops(&translucent_stops, 0.5),
+            (Color::new_rgb(255, 0, 0), 0.625)
+        );
+    }
+
+    #[test]
+    fn test_sample_gradient_stops_edge_cases() {
+        assert_eq!(sample_gradient_stops(&[], 0.5), (Color::black(), 0.0));
+
+        let single_stop = vec![stop(0.3, Color::new_rgb(0, 255, 0))];
+        for position in [-1.0, 0.0, 0.3, 0.5, 2.0] {
+            assert_eq!(
+                sample_gradient_stops(&single_stop, position),
+                (Color::new_rgb(0, 255, 0), 1.0)
+            );
+        }
+
+        let unsorted_stops = vec![
+            stop(1.0, Color::new_rgb(0, 0, 255)),
+            stop(0.0, Color::new_rgb(255, 0, 0)),
+            stop(0.5, Color::new_rgb(0, 255, 0)),
+        ];
+        assert_eq!(
+            sample_gradient_stops(&unsorted_stops, 0.25),
+            (Color::new_rgb(128, 128, 0), 1.0)
+        );
+        assert_eq!(
+            sample_gradient_stops(&unsorted_stops, 0.75),
+            (Color::new_rgb(0, 128, 128), 1.0)
+        );
+        assert_eq!(
+            sample_gradient_stops(&unsorted_stops, 0.0),
+            (Color::new_rgb(255, 0, 0), 1.0)
+        );
+    }
+
+    #[test]
+    fn test_apply_angular_gradient_mesh() {
+        let bundle = build_gradient_mesh(
+            GradientVariant::Angular {
+                transform: Mat3::IDENTITY,
+            },
+            &red_to_blue(),
+        );
+
+        // The pattern covers the shape and a margin of its size around it
+        let radius = (2.0 * 1.5f32.powi(2)).sqrt();
+        match bundle.gradient.get_attribute("width") {
+            Some(SvgAttribute::Width { width, .. }) => assert!((width - radius * 2.0).abs() < 1e-4),
+            attribute => panic!("Unexpected width {:?}", attribute),
+        }
+
+        assert_eq!(bundle.mesh_elements.len(), ANGULAR_GRADIENT_WEDGE_COUNT);
+        let fill = |index: usize| {
+            bundle.mesh_elements[index]
+                .get_style("fill")
+                .unwrap()
+                .to_svg_string()
+        };
+        assert_eq!(fill(0), "rgb(254, 0, 1, 1)");
+        assert_eq!(
+            fill(ANGULAR_GRADIENT_WEDGE_COUNT / 2),
+            "rgb(127, 0, 128, 1)"
+        );
+        assert_eq!(fill(ANGULAR_GRADIENT_WEDGE_COUNT - 1), "rgb(1, 0, 254, 1)");
+    }
+
+    #[test]
+    fn test_apply_diamond_gradient_mesh() {
+        let stops = red_to_blue();
+        let bundle = build_gradient_mesh(
+            GradientVariant::Diamond {
+                transform: Mat3::IDENTITY,
+            },
+            &stops,
+        );
+
+        // Each quadrant consists of a linear gradient with its stops and a wedge
+        assert_eq!(bundle.mesh_elements.len(), 4 * (stops.len() + 2));
+    }
+
+    #[test]
+    fn test_apply_gradient_mesh_edge_cases() {
+        // A single stop fills all wedges with its color
+        let bundle = build_gradient_mesh(
+            GradientVariant::Angular {
+                transform: Mat3::IDENTITY,
+            },
+            &[stop(0.5, Color::new_rgb(0, 255, 0))],
+        );
+        assert_eq!(bundle.mesh_elements.len(), ANGULAR_GRADIENT_WEDGE_COUNT);
+        assert!(bundle.mesh_elements.iter().all(|element| {
+            element.get_style("fill").unwrap().to_svg_string() == "rgb(0, 255, 0, 1)"
+        }));
+
+        // A degenerate transform can't be inverted and renders no mesh
+        let bundle = build_gradient_mesh(
+            GradientVariant::Angular {
+                transform: Mat3::ZERO,
+            },
+            &red_to_blue(),
+        );
+        assert!(bundle.mesh_elements.is_empty());
+        assert!(bundle.gradient.get_attribute("width").is_none());
+    }
+
+    fn get_gradient_fill(world: &World, entity: Entity) -> &GradientFillStyleSvgBundle {
+        match world.get::<SvgBundleVariant>(entity).unwrap() {
+            SvgBundleVariant::GradientFill(bundle) => bundle,
+            _ => panic!("Expected gradient fill bundle"),
+        }
+    }
+
+    #[test]
+    fn test_apply_gradient_paint_changes() {
+        let mut world = World::new();
+        world.insert_resource(SvgContextRes::default());
+        let linear = GradientVariant::Linear {
+            transform: Mat3::IDENTITY,
+        };
+        let paint = world
+            .spawn((
+                ArbPaint {
+                    variant: ArbPaintVariant::Gradient,
+                },
+                GradientArbPaint {
+                    variant: linear,
+                    stops: SmallVec::from_vec(red_to_blue()),
+                },
+            ))
+            .id();
+        let style = world.spawn_empty().id();
+        let bundle = GradientFillStyleSvgBundle::new(
+            style,
+            linear,
+            &mut world.resource_mut::<SvgContextRes>(),
+        );
+        world.entity_mut(style).insert((
+            ArbStyle {
+                variant: ArbStyleVariant::Fill,
+            },
+            PaintChildMixin(paint),
+            SizeMixin(Size::new(Abs::pt(100.0), Abs::pt(100.0))),
+            SvgBundleVariant::GradientFill(bundle),
+        ));
+        world
+            .entity_mut(paint)
+            .insert(PaintParentMixin(SmallVec::from_slice(&[style])));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_gradient_paint_changes);
+        let set_variant = |world: &mut World, schedule: &mut Schedule, variant| {
+            world.get_mut::<GradientArbPaint>(paint).unwrap().variant = variant;
+            schedule.run(world);
+        };
+        let get_tag_and_fill = |world: &World| {
+            let bundle = get_gradient_fill(world, style);
+            (
+                bundle.gradient.to_string(bundle, None)[..15].to_string(),
+                bundle.shape_path.get_style("fill").unwrap().to_svg_string(),
+                format!("url(#{})", bundle.gradient.get_id()),
+            )
+        };
+
+        set_variant(&mut world, &mut schedule, linear);
+        let (tag, fill, gradient_url) = get_tag_and_fill(&world);
+        assert!(tag.starts_with("<linearGradient"));
+        assert_eq!(fill, gradient_url);
+        assert_eq!(get_gradient_fill(&world, style).gradient_stops.len(), 2);
+
+        // Changing the variant family replaces the gradient element with a pattern
+        set_variant(
+            &mut world,
+            &mut schedule,
+            GradientVariant::Angular {
+                transform: Mat3::IDENTITY,
+            },
+        );
+        let (tag, fill, gradient_url) = get_tag_and_fill(&world);
+        assert!(tag.starts_with("<pattern"));
+        assert_eq!(fill, gradient_url);
+        let bundle = get_gradient_fill(&world, style);
+        assert!(bundle.gradient_stops.is_empty());
+        assert_eq!(bundle.mesh_elements.len(), ANGULAR_GRADIENT_WEDGE_COUNT);
+        let pattern_id = bundle.gradient.get_id();
+
+        // Variants of the same family keep the element
+        set_variant(
+            &mut world,
+            &mut schedule,
+            GradientVariant::Diamond {
+                transform: Mat3::IDENTITY,
+            },
+        );
+        assert_eq!(
+            get_gradient_fill(&world, style).gradient.get_id(),
+            pattern_id
+        );
+
+        // Resized styles update the mesh
+        let get_width = |world: &World| {
+            get_gradient_fill(world, style)
+                .gradient
+                .get_attribute("width")
+                .unwrap()
+                .to_svg_string()
+        };
+        let width = get_width(&world);
+        world.get_mut::<SizeMixin>(style).unwrap().0 = Size::new(Abs::pt(200.0), Abs::pt(100.0));
+        schedule.run(&mut world);
+        assert_ne!(get_width(&world), width);
+
+        // And back to a native gradient
+        set_variant(&mut world, &mut schedule, linear);
+        let (tag, fill, gradient_url) = get_tag_and_fill(&world);
+        assert!(tag.starts_with("<linearGradient"));
+        assert_eq!(fill, gradient_url);
+        let bundle = get_gradient_fill(&world, style);
+        assert!(bundle.mesh_elements.is_empty());
+        assert_eq!(bundle.gradient_stops.len(), 2);
+    }
+
+    #[test]
+    fn test_calculate_cropped_image_transform() {
+        let size = Size::new(Abs::pt(200.0), Abs::pt(100.0));
+        assert_eq!(
+            calculate_cropped_image_transform(&size, &Mat3::IDENTITY),
+            Mat3::IDENTITY
+        );
+
+        // Crop the center quarter of the image, so the image is scaled up
+        // and the cropped area spans the node
+        let transform =
+            Mat3::from_scale_angle_translation(Vec2::splat(0.5), 0.0, Vec2::splat(0.25));
+        let image_transform = calculate_cropped_image_transform(&size, &transform);
+        assert!(image_transform
+            .transform_point2(Vec2::new(50.0, 25.0))
+            .abs_diff_eq(Vec2::ZERO, 1e-4));
+        assert!(image_transform
+            .transform_point2(Vec2::new(150.0, 75.0))
+            .abs_diff_eq(Vec2::new(200.0, 100.0), 1e-4));
+
+        // Degenerate sizes and transforms leave the image untransformed
+        assert_eq!(
+            calculate_cropped_image_transform(&Size::new(Abs::zero(), Abs::pt(100.0)), &transform),
+            Mat3::IDENTITY
+        );
+        assert_eq!(
+            calculate_cropped_image_transform(&size, &Mat3::ZERO),
+            Mat3::IDENTITY
+        );
+    }
+
+    fn insert_image(world: &mut World, width: u16, height: u16) -> ImageId {
+        let asset = Asset {
+            content: AssetContent::Binary {
+                content: Vec::new(),
+            },
+            content_type: AssetContentType::Svg { width, height },
+        };
+        return world
+            .resource_mut::<AssetsRes>()
+            .insert_as_image(asset)
+            .unwrap();
+    }
+
+    fn get_size(element: &SvgElement) -> (String, String) {
+        let get = |key| {
+            element
+                .get_attribute(key)
+                .map(|attribute| attribute.to_svg_string())
+                .unwrap_or_default()
+        };
+        return (get("width"), get("height"));
+    }
+
+    fn get_image_fill(world: &World, entity: Entity) -> &ImageFillStyleSvgBundle {
+        match world.get::<SvgBundleVariant>(entity).unwrap() {
+            SvgBundleVariant::ImageFill(bundle) => bundle,
+            _ => panic!("Expected image fill bundle"),
+        }
+    }
+
+    /// Spawns an image paint with the given image and a fill style of the given size using it.
+    fn spawn_image_fill(
+        world: &mut World,
+        image_id: ImageId,
+        image_paint: ImageArbPaint,
+        size: Size,
+    ) -> (Entity, Entity) {
+        let paint = world
+            .spawn((
+                ArbPaint {
+                    variant: ArbPaintVariant::Image,
+                },
+                image_paint,
+                ImageAssetMixin(Some(image_id)),
+            ))
+            .id();
+        let style = world.spawn_empty().id();
+        let bundle = ImageFillStyleSvgBundle::new(
+            style,
+            image_paint.scale_mode,
+            &mut world.resource_mut::<SvgContextRes>(),
+        );
+        world.entity_mut(style).insert((
+            ArbStyle {
+                variant: ArbStyleVariant::Fill,
+            },
+            PaintChildMixin(paint),
+            SizeMixin(size),
+            SvgBundleVariant::ImageFill(bundle),
+        ));
+        world
+            .entity_mut(paint)
+            .insert(PaintParentMixin(SmallVec::from_slice(&[style])));
+        return (paint, style);
+    }
+
+    #[test]
+    fn test_apply_image_paint_changes() {
+        let mut world = World::new();
+        world.insert_resource(SvgContextRes::default());
+        world.insert_resource(AssetsRes::default());
+        let image_id = insert_image(&mut world, 40, 30);
+        let (paint, style) = spawn_image_fill(
+            &mut world,
+            image_id,
+            ImageArbPaint {
+                scale_mode: ImageScaleMode::Fit,
+                filters: ImageFilters::default(),
+            },
+            Size::new(Abs::pt(200.0), Abs::pt(100.0)),
+        );
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_image_paint_changes);
+
+        // Fitted images span the node and are scaled by the 'preserveAspectRatio' attribute
+        schedule.run(&mut world);
+        let bundle = get_image_fill(&world, style);
+        assert_eq!(
+            get_size(&bundle.pattern),
+            (String::from("200"), String::from("100"))
+        );
+        assert_eq!(get_size(&bundle.image), get_size(&bundle.pattern));
+        assert!(bundle.image.get_attribute("transform").is_none());
+
+        // Unchanged styles aren't updated
+        match world.get_mut::<SvgBundleVariant>(style).unwrap().as_mut() {
+            SvgBundleVariant::ImageFill(bundle) => {
+                bundle.pattern.remove_attribute("width");
+            }
+            _ => {}
+        }
+        schedule.run(&mut world);
+        assert!(get_image_fill(&world, style)
+            .pattern
+            .get_attribute("width")
+            .is_none());
+
+        // Resized styles are updated
+        world.get_mut::<SizeMixin>(style).unwrap().0 = Size::new(Abs::pt(50.0), Abs::pt(60.0));
+        schedule.run(&mut world);
+        assert_eq!(
+            get_size(&get_image_fill(&world, style).pattern),
+            (String::from("50"), String::from("60"))
+        );
+
+        // Tiles are sized by the image and rotated by the pattern
+        world.get_mut::<ImageArbPaint>(paint).unwrap().scale_mode = ImageScaleMode::Tile {
+            rotation: 45.0,
+            scaling_factor: 0.5,
+        };
+        schedule.run(&mut world);
+        let bundle = get_image_fill(&world, style);
+        assert_eq!(
+            get_size(&bundle.pattern),
+            (String::from("20"), String::from("15"))
+        );
+        assert_eq!(get_size(&bundle.image), get_size(&bundle.pattern));
+        assert_eq!(
+            bundle
+                .pattern
+                .get_attribute("patternTransform")
+                .unwrap()
+                .to_svg_string(),
+            "rotate(45)"
+        );
+
+        // Changed image assets of the paint are updated
+        let image_id = insert_image(&mut world, 80, 10);
+        world.get_mut::<ImageAssetMixin>(paint).unwrap().0 = Some(image_id);
+        schedule.run(&mut world);
+        assert_eq!(
+            get_size(&get_image_fill(&world, style).pattern),
+            (String::from("40"), String::from("5"))
+        );
+    }
+
+    #[test]
+    fn test_apply_image_scale_mode_changes() {
+        let mut world = World::new();
+        world.insert_resource(SvgContextRes::default());
+        world.insert_resource(AssetsRes::default());
+        let image_id = insert_image(&mut world, 40, 30);
+        let (paint, style) = spawn_image_fill(
+            &mut world,
+            image_id,
+            ImageArbPaint {
+                scale_mode: ImageScaleMode::Fill,
+                filters: ImageFilters::default(),
+            },
+            Size::new(Abs::pt(200.0), Abs::pt(100.0)),
+        );
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_image_paint_changes);
+        let mut set_scale_mode = |world: &mut World, scale_mode: ImageScaleMode| {
+            world.get_mut::<ImageArbPaint>(paint).unwrap().scale_mode = scale_mode;
+            schedule.run(world);
+            return get_image_fill(world, style)
+                .image
+                .get_attribute("preserveAspectRatio")
+                .unwrap()
+                .to_svg_string();
+        };
+
+        // The scaling of the image follows the scale mode of the same bundle
+        assert_eq!(
+            set_scale_mode(&mut world, ImageScaleMode::Fill),
+            "xMidYMid slice"
+        );
+        assert_eq!(
+            set_scale_mode(&mut world, ImageScaleMode::Fit),
+            "xMidYMid meet"
+        );
+        assert_eq!(
+            set_scale_mode(
+                &mut world,
+                ImageScaleMode::Crop {
+                    transform: Mat3::IDENTITY
+                }
+            ),
+            "none"
+        );
+        assert!(matches!(
+            get_image_fill(&world, style).variant,
+            ImageFillStyleVariant::Crop
+        ));
+    }
+
+    #[test]
+    fn test_apply_image_filter_changes() {
+        let mut world = World::new();
+        world.insert_resource(SvgContextRes::default());
+        world.insert_resource(AssetsRes::default());
+        let image_id = insert_image(&mut world, 40, 30);
+        let (paint, style) = spawn_image_fill(
+            &mut world,
+            image_id,
+            ImageArbPaint {
+                scale_mode: ImageScaleMode::Fill,
+                filters: ImageFilters::default(),
+            },
+            Size::new(Abs::pt(40.0), Abs::pt(30.0)),
+        );
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_image_filter_changes);
+        let mut apply_filters = |world: &mut World, filters: ImageFilters| {
+            world.get_mut::<ImageArbPaint>(paint).unwrap().filters = filters;
+            schedule.run(world);
+            let bundle = get_image_fill(world, style);
+            let get = |element: &SvgElement, key| {
+                element
+                    .get_attribute(key)
+                    .map(|attribute| attribute.to_svg_string())
+                    .unwrap_or_default()
+            };
+            return (
+                get(&bundle.image, "filter"),
+                [&bundle.fe_func_r, &bundle.fe_func_g, &bundle.fe_func_b]
+                    .map(|fe_func| (get(fe_func, "slope"), get(fe_func, "intercept"))),
+                get(&bundle.fe_color_matrix, "values"),
+            );
+        };
+        let identity = ColorMatrix::from_saturation(1.0).to_string();
+        let unchanged = (String::from("1"), String::from("0"));
+
+        // Unfiltered images don't reference the filter
+        let (filter, ..) = apply_filters(&mut world, ImageFilters::default());
+        assert_eq!(filter, "none");
+
+        // Exposure doubles the channels per stop
+        let (filter, fe_funcs, values) = apply_filters(
+            &mut world,
+            ImageFilters {
+                exposure: 1.0,
+                ..Default::default()
+            },
+        );
+        assert!(filter.starts_with("url(#"));
+        assert_eq!(
+            fe_funcs,
+            [0, 1, 2].map(|_| (String::from("2"), String::from("0")))
+        );
+        assert_eq!(values, identity);
+
+        // Contrast scales the channels around the mid tone
+        let (_, fe_funcs, _) = apply_filters(
+            &mut world,
+            ImageFilters {
+                contrast: 0.5,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            fe_funcs,
+            [0, 1, 2].map(|_| (String::from("1.5"), String::from("-0.25")))
+        );
+
+        // Temperature warms the red and cools the blue channel,
+        // tint shifts the green channel
+        let (_, fe_funcs, _) = apply_filters(
+            &mut world,
+            ImageFilters {
+                temperature: 1.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            fe_funcs,
+            [
+                (String::from("1.2"), String::from("0")),
+                unchanged.clone(),
+                (String::from("0.8"), String::from("0")),
+            ]
+        );
+        let (_, fe_funcs, _) = apply_filters(
+            &mut world,
+            ImageFilters {
+                tint: -1.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            fe_funcs,
+            [
+                unchanged.clone(),
+                (String::from("1.2"), String::from("0")),
+                unchanged.clone(),
+            ]
+        );
+
+        // Saturation is applied by the color matrix
+        let (_, fe_funcs, values) = apply_filters(
+            &mut world,
+            ImageFilters {
+                saturation: -1.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(fe_funcs, [0, 1, 2].map(|_| unchanged.clone()));
+        assert_eq!(values, ColorMatrix::from_saturation(0.0).to_string());
+    }
+
+    /// Spawns a visible frame node of the given size and translation with the given styles.
+    fn spawn_frame(
+        world: &mut World,
+        size: Size,
+        translation: Vec2,
+        styles: Vec<(ArbStyleVariant, SvgBundleVariant)>,
+    ) -> Entity {
+        let node = world.spawn_empty().id();
+        let bundle = FrameNodeSvgBundle::new(node, &mut world.resource_mut::<SvgContextRes>());
+        world.entity_mut(node).insert((
+            ArbNode {
+                variant: ArbNodeVariant::Frame,
+            },
+            FrameArbNode::default(),
+            SizeMixin(size),
+            Transform::from_translation(translation.extend(0.0)),
+            VisibilityMixin(true),
+            SvgBundleVariant::FrameNode(bundle),
+        ));
+        let style_entities = styles
+            .into_iter()
+            .map(|(variant, bundle_variant)| {
+                world
+                    .spawn((
+                        ArbStyle { variant },
+                        StyleParentMixin(node),
+                        VisibilityMixin(true),
+                        bundle_variant,
+                    ))
+                    .id()
+            })
+            .collect();
+        world
+            .entity_mut(node)
+            .insert(StyleChildrenMixin(style_entities));
+        return node;
+    }
+
+    fn get_filter_region(element: &SvgElement) -> [String; 4] {
+        ["x", "y", "width", "height"].map(|key| {
+            element
+                .get_attribute(key)
+                .map(|attribute| attribute.to_svg_string())
+                .unwrap_or_default()
+        })
+    }
+
+    #[test]
+    fn test_apply_layer_blur_changes() {
+        let mut world = World::new();
+        world.insert_resource(SvgContextRes::default());
+        let layer_blur_bundle = LayerBlurEffectStyleSvgBundle::new(
+            Entity::from_raw(0),
+            &mut world.resource_mut::<SvgContextRes>(),
+        );
+        let node = spawn_frame(
+            &mut world,
+            Size::new(Abs::pt(100.0), Abs::pt(100.0)),
+            Vec2::ZERO,
+            vec![(
+                ArbStyleVariant::LayerBlur,
+                SvgBundleVariant::LayerBlurEffect(layer_blur_bundle),
+            )],
+        );
+        let layer_blur_style = world.get::<StyleChildrenMixin>(node).unwrap().0[0];
+        world
+            .entity_mut(layer_blur_style)
+            .insert(LayerBlurArbStyle {
+                blur: Abs::pt(10.0),
+            });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_layer_blur_changes);
+        let mut get_region = |world: &mut World| {
+            schedule.run(world);
+            match world.get::<SvgBundleVariant>(layer_blur_style).unwrap() {
+                SvgBundleVariant::LayerBlurEffect(bundle) => get_filter_region(&bundle.filter),
+                _ => panic!("Expected layer blur bundle"),
+            }
+        };
+
+        // The region extends the shape by the blur
+        assert_eq!(
+            get_region(&mut world),
+            ["-15", "-15", "130", "130"].map(String::from)
+        );
+
+        // Drop shadows and children overflowing the frame are blurred as well
+        let drop_shadow_style = world
+            .spawn((
+                ArbStyle {
+                    variant: ArbStyleVariant::DropShadow,
+                },
+                DropShadowArbStyle {
+                    position: Vec2::new(10.0, 10.0),
+                    blur: Abs::pt(4.0),
+                    ..Default::default()
+                },
+                StyleParentMixin(node),
+                VisibilityMixin(true),
+            ))
+            .id();
+        world
+            .get_mut::<StyleChildrenMixin>(node)
+            .unwrap()
+            .0
+            .push(drop_shadow_style);
+        let child = spawn_frame(
+            &mut world,
+            Size::new(Abs::pt(50.0), Abs::pt(50.0)),
+            Vec2::new(80.0, -20.0),
+            Vec::new(),
+        );
+        world.entity_mut(node).push_children(&[child]);
+        assert_eq!(
+            get_region(&mut world),
+            ["-15", "-35", "160", "166"].map(String::from)
+        );
+
+        // Clipped children don't extend the region
+        world.get_mut::<FrameArbNode>(node).unwrap().clip_content = true;
+        assert_eq!(
+            get_region(&mut world),
+            ["-15", "-15", "146", "146"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn test_apply_background_blur_backdrop_changes() {
+        let mut world = World::new();
+        world.insert_resource(SvgContextRes::default());
+        let size = Size::new(Abs::pt(100.0), Abs::pt(100.0));
+        let parent = spawn_frame(&mut world, size, Vec2::ZERO, Vec::new());
+        let background_blur_bundle = BackgroundBlurEffectStyleSvgBundle::new(
+            Entity::from_raw(0),
+            &mut world.resource_mut::<SvgContextRes>(),
+        );
+        let node = spawn_frame(
+            &mut world,
+            size,
+            Vec2::new(10.0, 20.0),
+            vec![(
+                ArbStyleVariant::BackgroundBlur,
+                SvgBundleVariant::BackgroundBlurEffect(background_blur_bundle),
+            )],
+        );
+        let background_blur_style = world.get::<StyleChildrenMixin>(node).unwrap().0[0];
+        world
+            .entity_mut(background_blur_style)
+            .insert(BackgroundBlurArbStyle {
+                blur: Abs::pt(10.0),
+            });
+        let sibling_above = spawn_frame(&mut world, size, Vec2::ZERO, Vec::new());
+        let sibling_behind = spawn_frame(&mut world, size, Vec2::ZERO, Vec::new());
+        world
+            .entity_mut(parent)
+            .push_children(&[sibling_above, node, sibling_behind]);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_background_blur_backdrop_changes);
+        let mut get_backdrop = |world: &mut World| {
+            schedule.run(world);
+            match world
+                .get::<SvgBundleVariant>(background_blur_style)
+                .unwrap()
+            {
+                SvgBundleVariant::BackgroundBlurEffect(bundle) => (
+                    bundle
+                        .backdrop_content_g
+                        .get_attribute("transform")
+                        .unwrap()
+                        .to_svg_string(),
+                    bundle
+                        .backdrop_uses
+                        .iter()
+                        .map(|element| element.get_attribute("href").unwrap().to_svg_string())
+                        .collect::<Vec<_>>(),
+                ),
+                _ => panic!("Expected background blur bundle"),
+            }
+        };
+        let get_id = |world: &World, entity: Entity| {
+            let bundle_variant = world.get::<SvgBundleVariant>(entity).unwrap();
+            if entity == parent {
+                return bundle_variant
+                    .get_styles_wrapper_element()
+                    .unwrap()
+                    .get_id();
+            }
+            return bundle_variant.get_root_element().get_id();
+        };
+
+        // The backdrop consists of the parent's styles and the siblings behind the node,
+        // moved into the space of the node
+        let (transform, hrefs) = get_backdrop(&mut world);
+        assert_eq!(transform, "matrix(1, 0, 0, 1, -10, -20)");
+        assert_eq!(
+            hrefs,
+            vec![
+                format!("#{}", get_id(&world, parent)),
+                format!("#{}", get_id(&world, sibling_behind)),
+            ]
+        );
+
+        // Reordered siblings update the backdrop
+        world
+            .entity_mut(parent)
+            .insert_children(0, &[sibling_behind]);
+        let (_, hrefs) = get_backdrop(&mut world);
+        assert_eq!(hrefs, vec![format!("#{}", get_id(&world, parent))]);
+    }
+}
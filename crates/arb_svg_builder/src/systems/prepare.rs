@@ -3,9 +3,11 @@ use crate::{
     svg::svg_bundle::{
         node::{frame::FrameNodeSvgBundle, shape::ShapeNodeSvgBundle},
         style::{
+            background_blur_effect::BackgroundBlurEffectStyleSvgBundle,
             drop_shadow_effect::DropShadowEffectStyleSvgBundle,
             gradient_fill::GradientFillStyleSvgBundle, image_fill::ImageFillStyleSvgBundle,
-            solid_fill::SolidFillStyleSvgBundle,
+            inner_shadow_effect::InnerShadowEffectStyleSvgBundle,
+            layer_blur_effect::LayerBlurEffectStyleSvgBundle, solid_fill::SolidFillStyleSvgBundle,
         },
         SvgBundleVariant,
     },
@@ -38,7 +40,9 @@ pub fn insert_node_svg_bundle(
                 | ArbNodeVariant::Polygon
                 | ArbNodeVariant::Star
                 | ArbNodeVariant::Text
-                | ArbNodeVariant::Vector => Some(SvgBundleVariant::ShapeNode(
+                | ArbNodeVariant::Vector
+                // Children of boolean operations are only operands and thus not rendered
+                | ArbNodeVariant::BooleanOperation => Some(SvgBundleVariant::ShapeNode(
                     ShapeNodeSvgBundle::new(entity, &mut svg_context_res),
                 )),
             };
@@ -121,6 +125,15 @@ pub fn insert_style_svg_bundle(
             ArbStyleVariant::DropShadow => Some(SvgBundleVariant::DropShadowEffect(
                 DropShadowEffectStyleSvgBundle::new(entity, &mut svg_context_res),
             )),
+            ArbStyleVariant::InnerShadow => Some(SvgBundleVariant::InnerShadowEffect(
+                InnerShadowEffectStyleSvgBundle::new(entity, &mut svg_context_res),
+            )),
+            ArbStyleVariant::LayerBlur => Some(SvgBundleVariant::LayerBlurEffect(
+                LayerBlurEffectStyleSvgBundle::new(entity, &mut svg_context_res),
+            )),
+            ArbStyleVariant::BackgroundBlur => Some(SvgBundleVariant::BackgroundBlurEffect(
+                BackgroundBlurEffectStyleSvgBundle::new(entity, &mut svg_context_res),
+            )),
         };
 
         // TODO: Very dirty (See README.md for future rewrite)
@@ -176,7 +189,11 @@ pub fn propagate_size_mixin_to_style(
     mut commands: Commands,
     node_query: Query<
         (Entity, &SizeMixin, &StyleChildrenMixin),
-        (With<ArbNode>, Without<ArbStyle>, Changed<SizeMixin>),
+        (
+            With<ArbNode>,
+            Without<ArbStyle>,
+            Or<(Changed<SizeMixin>, Changed<StyleChildrenMixin>)>,
+        ),
     >,
     mut style_with_size_query: Query<
         (Entity, &StyleParentMixin, &mut SizeMixin),
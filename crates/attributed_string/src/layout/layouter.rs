@@ -3,11 +3,17 @@ use super::{
     line_wrap::{no_wrap::NoLineWrap, word_wrap::WordWrap, LineWrapStrategy},
     HorizontalTextAlignment, LayoutSize, LineWrap, VerticalTextAlignment,
 };
-use crate::span::SpanIntervals;
+use crate::{
+    shape_tokens::{ShapeToken, ShapeTokenVariant},
+    span::SpanIntervals,
+    utils::is_range_within,
+};
 use dyn_utils::{
     properties::size::Size,
     units::{abs::Abs, auto_length::AutoLength},
 };
+use rust_lapper::Interval;
+use std::ops::Range;
 
 #[derive(Debug, Clone)]
 pub struct Layouter {
@@ -48,12 +54,8 @@ impl Layouter {
     }
 
     pub fn layout_lines(&mut self, spans: &mut SpanIntervals) {
-        self.lines = self.compute_lines(spans);
-
-        let text_size = self.compute_text_size(&spans);
-        self.text_size = Some(text_size);
-        let container_size = self.compute_container_size(&text_size);
-        self.container_size = Some(container_size);
+        let container_size = self.measure(spans);
+        let text_size = self.text_size.unwrap_or(container_size);
 
         let vertical_alignment_correction = match self.config.vertical_text_alignment {
             VerticalTextAlignment::Top => Abs::zero(),
@@ -84,9 +86,19 @@ impl Layouter {
                         container_size.width - line_width
                     }
                     (HorizontalTextAlignment::End, LineDirection::RightToLeft) => Abs::zero(),
+                    (HorizontalTextAlignment::Justified, LineDirection::LeftToRight) => Abs::zero(),
+                    (HorizontalTextAlignment::Justified, LineDirection::RightToLeft) => {
+                        container_size.width - line_width
+                    }
                 };
+            let mut maybe_justification =
+                self.compute_justification(index, container_size.width, spans);
 
-            curr_pos_x = horizontal_alignment_correction;
+            curr_pos_x = if maybe_justification.is_some() {
+                Abs::zero()
+            } else {
+                horizontal_alignment_correction
+            };
             curr_pos_y += if index == 0 {
                 line.get_max_ascent(&spans)
             } else {
@@ -100,10 +112,118 @@ impl Layouter {
                         curr_pos_y.to_pt(),
                     );
 
-                    curr_pos_x += glyph_token.layout.x_advance;
+                    match &mut maybe_justification {
+                        Some(justification) => {
+                            // Leading and trailing word separators don't advance,
+                            // so that the justified line is flush with both edges
+                            let glyph_range = glyph_token.get_range().clone();
+                            if !is_range_within(&glyph_range, &justification.content_range) {
+                                continue;
+                            }
+
+                            curr_pos_x += glyph_token.layout.x_advance;
+
+                            // Stretch each word separator once,
+                            // even if it's made up of multiple glyphs
+                            if let Some(separator_index) = justification
+                                .separator_ranges
+                                .iter()
+                                .position(|range| is_range_within(&glyph_range, range))
+                            {
+                                justification.separator_ranges.swap_remove(separator_index);
+                                curr_pos_x += justification.extra_space;
+                            }
+                        }
+                        None => curr_pos_x += glyph_token.layout.x_advance,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Breaks the spans into lines and computes the resulting container size
+    /// without positioning the glyphs (e.g. to measure the text during a layout pass).
+    pub fn measure(&mut self, spans: &SpanIntervals) -> Size {
+        self.lines = self.compute_lines(spans);
+
+        let text_size = self.compute_text_size(spans);
+        self.text_size = Some(text_size);
+        let container_size = self.compute_container_size(&text_size);
+        self.container_size = Some(container_size);
+
+        return container_size;
+    }
+
+    /// Computes how much the word separators of the line at the given index
+    /// have to be stretched to fill the container width.
+    ///
+    /// Returns `None` if the line shouldn't be justified,
+    /// e.g. because it's the last line of a paragraph or it has no word separators.
+    fn compute_justification(
+        &self,
+        index: usize,
+        container_width: Abs,
+        spans: &SpanIntervals,
+    ) -> Option<Justification> {
+        if !matches!(
+            self.config.horizontal_text_alignment,
+            HorizontalTextAlignment::Justified
+        ) || self.ends_paragraph(index, spans)
+        {
+            return None;
+        }
+
+        let content_range = self.lines.get(index)?.get_content_range(spans)?;
+        let mut content_width = Abs::zero();
+        let mut separator_ranges: Vec<Range<usize>> = Vec::new();
+        for Interval { val: span, .. } in spans.find(content_range.start, content_range.end) {
+            for token_variant in span.iter_tokens_in_range(&content_range) {
+                content_width += token_variant.get_shape_token().x_advance();
+                if let ShapeTokenVariant::WordSeparator(token) = token_variant {
+                    separator_ranges.push(token.get_range().clone());
                 }
             }
         }
+
+        if separator_ranges.is_empty() || content_width >= container_width {
+            return None;
+        }
+
+        return Some(Justification {
+            extra_space: (container_width - content_width) / separator_ranges.len() as f32,
+            content_range,
+            separator_ranges,
+        });
+    }
+
+    /// Whether the line at the given index is the last line of a paragraph,
+    /// i.e. it's the last line or a linebreak separates it from the next line.
+    fn ends_paragraph(&self, index: usize, spans: &SpanIntervals) -> bool {
+        let (line, next_line) = match (self.lines.get(index), self.lines.get(index + 1)) {
+            (Some(line), Some(next_line)) => (line, next_line),
+            _ => return true,
+        };
+
+        let between_range = Range {
+            start: line
+                .get_content_range(spans)
+                .map_or(line.get_range().start, |range| range.end),
+            end: next_line
+                .get_content_range(spans)
+                .map_or(next_line.get_range().end, |range| range.start),
+        };
+
+        return spans.find(between_range.start, between_range.end).any(
+            |Interval { val: span, .. }| {
+                span.get_tokens().iter().any(|token_variant| {
+                    matches!(token_variant, ShapeTokenVariant::Linebreak(_))
+                        && is_range_within(
+                            token_variant.get_shape_token().get_range(),
+                            &between_range,
+                        )
+                })
+            },
+        );
     }
 
     fn compute_lines(&self, spans: &SpanIntervals) -> Vec<Line> {
@@ -157,6 +277,16 @@ impl Layouter {
     }
 }
 
+/// Word separators that are stretched to justify a line.
+struct Justification {
+    /// Range of the line without leading and trailing word separators.
+    content_range: Range<usize>,
+    /// Ranges of the word separators that haven't been stretched yet.
+    separator_ranges: Vec<Range<usize>>,
+    /// Space added to each word separator.
+    extra_space: Abs,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct LayouterConfig {
     pub size: LayoutSize,
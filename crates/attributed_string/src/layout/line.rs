@@ -74,6 +74,30 @@ impl Line {
         return width;
     }
 
+    /// Returns the range from the first to the last visible (non-blank) token of the line,
+    /// thus excluding leading and trailing word separators or linebreaks.
+    pub fn get_content_range(&self, spans: &SpanIntervals) -> Option<Range<usize>> {
+        let mut content_range: Option<Range<usize>> = None;
+
+        for Interval { val: span, .. } in spans.find(self.range.start, self.range.end) {
+            for token in span.iter_tokens_in_range(&self.range) {
+                if token.is_blank() {
+                    continue;
+                }
+
+                let token_range = token.get_shape_token().get_range();
+                content_range = Some(match content_range {
+                    Some(range) => {
+                        range.start.min(token_range.start)..range.end.max(token_range.end)
+                    }
+                    None => token_range.clone(),
+                });
+            }
+        }
+
+        return content_range;
+    }
+
     pub fn get_max_height(&self, spans: &SpanIntervals) -> Abs {
         let mut current_height = Abs::zero();
 
@@ -37,7 +37,9 @@ pub enum HorizontalTextAlignment {
     Left,
     Right,
     Center,
-    // Justified, // TODO
+    /// Stretches the word separators so that each line fills the container width,
+    /// except for the last line of a paragraph, which is aligned to the start.
+    Justified,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
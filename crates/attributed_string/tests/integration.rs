@@ -417,6 +417,75 @@ fn test_horizontal_text_align_center() {
     );
 }
 
+#[test]
+fn test_horizontal_text_align_justified() {
+    let text = String::from("The quick brown fox jumps over the lazy dog.\nPack my box with five dozen liquor jugs, said the quick brown fox.");
+    let attrs_intervals = vec![
+        TextAttrsInterval {
+            start: 0,
+            stop: 19,
+            val: TextAttrs::new()
+                .font_family(FontFamily::Named(String::from("Fira Mono")))
+                .font_weight(FontWeight::MEDIUM)
+                .font_size(Abs::pt(24.0))
+                .letter_spacing(FontUnit::em(Em::new(0.2))),
+        },
+        TextAttrsInterval {
+            start: 19,
+            stop: text.len(),
+            val: TextAttrs::new()
+                .font_family(FontFamily::Named(String::from("Noto Sans")))
+                .font_weight(FontWeight::REGULAR)
+                .font_size(Abs::pt(24.0)),
+        },
+    ];
+
+    let mut attributed_string = AttributedString::new(text, attrs_intervals);
+
+    assert_attributed_string_rendered(
+        "test_horizontal_text_align_justified",
+        &mut attributed_string,
+        LayouterConfig {
+            line_wrap: LineWrap::Word,
+            horizontal_text_alignment: HorizontalTextAlignment::Justified,
+            size: LayoutSize::new(
+                AutoLength::abs(Abs::pt(300.0)),
+                AutoLength::abs(Abs::pt(240.0)),
+            ),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_horizontal_text_align_justified_rtl() {
+    let text = String::from("השועל החום המהיר קופץ מעל הכלב העצלן");
+    let attrs_intervals = vec![TextAttrsInterval {
+        start: 0,
+        stop: text.len(),
+        val: TextAttrs::new()
+            .font_family(FontFamily::Named(String::from("Noto Sans")))
+            .font_size(Abs::pt(36.0))
+            .line_height(FontUnit::abs(Abs::pt(40.0))),
+    }];
+
+    let mut attributed_string = AttributedString::new(text, attrs_intervals);
+
+    assert_attributed_string_rendered(
+        "test_horizontal_text_align_justified_rtl",
+        &mut attributed_string,
+        LayouterConfig {
+            line_wrap: LineWrap::Word,
+            horizontal_text_alignment: HorizontalTextAlignment::Justified,
+            size: LayoutSize::new(
+                AutoLength::abs(Abs::pt(400.0)),
+                AutoLength::abs(Abs::pt(110.0)),
+            ),
+            ..Default::default()
+        },
+    );
+}
+
 #[test]
 fn test_vertical_text_align_top() {
     let text = String::from("Hello, world!\nשלום עולם!\nThis is a mix of English and Hebrew.");
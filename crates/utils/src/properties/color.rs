@@ -1,15 +1,85 @@
+use super::opacity::Opacity;
+use std::{fmt::Display, str::FromStr};
+
+/// A RGBA color.
+///
+/// Serialized as `[red, green, blue]` or `[red, green, blue, alpha]`
+/// and additionally deserialized from CSS color strings
+/// like `#ff000080`, `rgb(255 0 0 / 50%)`, `hsl(0deg, 100%, 50%)` or `red`.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
-#[cfg_attr(
-    feature = "specta_support",
-    derive(serde::Serialize, serde::Deserialize, specta::Type)
-)]
-#[cfg_attr(feature = "utoipa_support", derive(utoipa::ToSchema))]
-pub struct Color(u8, u8, u8);
+pub struct Color {
+    red: u8,
+    green: u8,
+    blue: u8,
+    alpha: Opacity,
+}
 
 impl Color {
     /// Creates a new `Color` instance from RGB components.
     pub fn new_rgb(red: u8, green: u8, blue: u8) -> Self {
-        Self(red, green, blue)
+        Self::new_rgba(red, green, blue, 1.0)
+    }
+
+    /// Creates a new `Color` instance from RGB components
+    /// and an alpha value between 0.0 and 1.0.
+    pub fn new_rgba(red: u8, green: u8, blue: u8, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha: Opacity::new(alpha),
+        }
+    }
+
+    /// Creates a new `Color` instance from a hue in degrees
+    /// and a saturation and lightness between 0.0 and 1.0.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        Self::from_hsla(hue, saturation, lightness, 1.0)
+    }
+
+    /// Creates a new `Color` instance from a hue in degrees,
+    /// a saturation and lightness between 0.0 and 1.0 and an alpha value.
+    pub fn from_hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        // https://en.wikipedia.org/wiki/HSL_and_HSV#HSL_to_RGB_alternative
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let a = saturation * lightness.min(1.0 - lightness);
+        let f = |n: f32| {
+            let k = (n + hue.rem_euclid(360.0) / 30.0) % 12.0;
+            lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+
+        Self::new_rgba(
+            to_channel(f(0.0)),
+            to_channel(f(8.0)),
+            to_channel(f(4.0)),
+            alpha,
+        )
+    }
+
+    /// Creates a new `Color` instance from a hue in degrees
+    /// and a saturation and value between 0.0 and 1.0.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        Self::from_hsva(hue, saturation, value, 1.0)
+    }
+
+    /// Creates a new `Color` instance from a hue in degrees,
+    /// a saturation and value between 0.0 and 1.0 and an alpha value.
+    pub fn from_hsva(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        // https://en.wikipedia.org/wiki/HSL_and_HSV#HSV_to_RGB_alternative
+        let saturation = saturation.clamp(0.0, 1.0);
+        let value = value.clamp(0.0, 1.0);
+        let f = |n: f32| {
+            let k = (n + hue.rem_euclid(360.0) / 60.0) % 6.0;
+            value - value * saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+
+        Self::new_rgba(
+            to_channel(f(5.0)),
+            to_channel(f(3.0)),
+            to_channel(f(1.0)),
+            alpha,
+        )
     }
 
     /// Returns a color instance set to black.
@@ -22,16 +92,34 @@ impl Color {
         Color::new_rgb(255, 255, 255)
     }
 
+    /// Returns a fully transparent color instance.
+    pub fn transparent() -> Color {
+        Color::new_rgba(0, 0, 0, 0.0)
+    }
+
     pub fn get_red(&self) -> u8 {
-        self.0
+        self.red
     }
 
     pub fn get_green(&self) -> u8 {
-        self.1
+        self.green
     }
 
     pub fn get_blue(&self) -> u8 {
-        self.2
+        self.blue
+    }
+
+    pub fn get_alpha(&self) -> f32 {
+        self.alpha.get()
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.alpha == Opacity::one()
+    }
+
+    /// Returns a copy of the color with the given alpha value.
+    pub fn with_alpha(&self, alpha: f32) -> Self {
+        Self::new_rgba(self.red, self.green, self.blue, alpha)
     }
 }
 
@@ -40,3 +128,465 @@ impl Default for Color {
         Self::black()
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseColorError {
+    value: String,
+}
+
+impl ParseColorError {
+    fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+        }
+    }
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid CSS color '{}'", self.value)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+// https://developer.mozilla.org/en-US/docs/Web/CSS/color_value
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let css = value.trim().to_ascii_lowercase();
+        let err = || ParseColorError::new(value);
+
+        if let Some(hex) = css.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(err);
+        }
+
+        if let Some((name, args)) = css
+            .strip_suffix(')')
+            .and_then(|function| function.split_once('('))
+        {
+            // Legacy comma separated and modern space separated syntax
+            let args: Vec<&str> = args
+                .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+                .filter(|arg| !arg.is_empty())
+                .collect();
+            if args.len() != 3 && args.len() != 4 {
+                return Err(err());
+            }
+            let alpha = match args.get(3) {
+                Some(arg) => parse_number_or_percentage(arg, 1.0).ok_or_else(err)?,
+                None => 1.0,
+            };
+
+            return match name.trim() {
+                "rgb" | "rgba" => {
+                    let mut channels = [0; 3];
+                    for (channel, arg) in channels.iter_mut().zip(args.iter()) {
+                        *channel = to_channel(
+                            parse_number_or_percentage(arg, 255.0).ok_or_else(err)? / 255.0,
+                        );
+                    }
+                    Ok(Self::new_rgba(channels[0], channels[1], channels[2], alpha))
+                }
+                "hsl" | "hsla" => Ok(Self::from_hsla(
+                    parse_hue(args[0]).ok_or_else(err)?,
+                    parse_number_or_percentage(args[1], 100.0).ok_or_else(err)? / 100.0,
+                    parse_number_or_percentage(args[2], 100.0).ok_or_else(err)? / 100.0,
+                    alpha,
+                )),
+                _ => Err(err()),
+            };
+        }
+
+        if css == "transparent" {
+            return Ok(Self::transparent());
+        }
+
+        return NAMED_COLORS
+            .binary_search_by(|(name, _)| name.cmp(&css.as_str()))
+            .map(|index| {
+                let (red, green, blue) = NAMED_COLORS[index].1;
+                Self::new_rgb(red, green, blue)
+            })
+            .map_err(|_| err());
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.is_ascii() {
+        return None;
+    }
+
+    let digits: Vec<u8> = match hex.len() {
+        // Shorthand notation where each digit is doubled (e.g. #f80 = #ff8800)
+        3 | 4 => hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|digit| digit as u8 * 17))
+            .collect::<Option<Vec<u8>>>()?,
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?,
+        _ => return None,
+    };
+    let alpha = digits.get(3).map_or(1.0, |alpha| *alpha as f32 / 255.0);
+
+    return Some(Color::new_rgba(digits[0], digits[1], digits[2], alpha));
+}
+
+/// Parses a number or a percentage, where `100%` corresponds to `percentage_reference`.
+fn parse_number_or_percentage(value: &str, percentage_reference: f32) -> Option<f32> {
+    let number = match value.strip_suffix('%') {
+        Some(percentage) => percentage.parse::<f32>().ok()? / 100.0 * percentage_reference,
+        None => value.parse::<f32>().ok()?,
+    };
+    return number.is_finite().then_some(number);
+}
+
+/// Parses a hue into degrees.
+fn parse_hue(value: &str) -> Option<f32> {
+    let (number, factor) = if let Some(number) = value.strip_suffix("deg") {
+        (number, 1.0)
+    } else if let Some(number) = value.strip_suffix("grad") {
+        (number, 0.9)
+    } else if let Some(number) = value.strip_suffix("rad") {
+        (number, 180.0 / std::f32::consts::PI)
+    } else if let Some(number) = value.strip_suffix("turn") {
+        (number, 360.0)
+    } else {
+        (value, 1.0)
+    };
+    let hue = number.parse::<f32>().ok()? * factor;
+    return hue.is_finite().then_some(hue);
+}
+
+fn to_channel(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(feature = "specta_support")]
+const _: () = {
+    use serde::{
+        de::{self, SeqAccess, Visitor},
+        ser::SerializeTuple,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+    use std::fmt;
+
+    // Opaque colors are serialized without alpha,
+    // matching the format of colors before alpha was supported
+    impl Serialize for Color {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_tuple(if self.is_opaque() { 3 } else { 4 })?;
+            state.serialize_element(&self.red)?;
+            state.serialize_element(&self.green)?;
+            state.serialize_element(&self.blue)?;
+            if !self.is_opaque() {
+                state.serialize_element(&self.alpha)?;
+            }
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Color {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct ColorVisitor;
+
+            impl<'de> Visitor<'de> for ColorVisitor {
+                type Value = Color;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    formatter.write_str(
+                        "[red, green, blue], [red, green, blue, alpha] or a CSS color string",
+                    )
+                }
+
+                fn visit_seq<V>(self, mut seq: V) -> Result<Color, V::Error>
+                where
+                    V: SeqAccess<'de>,
+                {
+                    let red = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    let green = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    let blue = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                    let alpha: Option<f32> = seq.next_element()?;
+                    Ok(Color::new_rgba(red, green, blue, alpha.unwrap_or(1.0)))
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Color, E>
+                where
+                    E: de::Error,
+                {
+                    value.parse().map_err(de::Error::custom)
+                }
+            }
+
+            deserializer.deserialize_any(ColorVisitor)
+        }
+    }
+
+    #[derive(specta::Type)]
+    #[specta(rename = "Color", remote = Color, crate = specta, export = false)]
+    #[specta(untagged)]
+    #[allow(dead_code)]
+    enum ColorDef {
+        Rgb(u8, u8, u8),
+        Rgba(u8, u8, u8, f32),
+        Css(String),
+    }
+};
+
+#[cfg(feature = "utoipa_support")]
+impl<'s> utoipa::ToSchema<'s> for Color {
+    fn schema() -> (
+        &'s str,
+        utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+    ) {
+        use utoipa::openapi::{ArrayBuilder, ObjectBuilder, OneOfBuilder, SchemaType};
+
+        (
+            "Color",
+            OneOfBuilder::new()
+                .item(
+                    ArrayBuilder::new()
+                        .items(ObjectBuilder::new().schema_type(SchemaType::Number))
+                        .min_items(Some(3))
+                        .max_items(Some(4))
+                        .description(Some("[red, green, blue] or [red, green, blue, alpha]")),
+                )
+                .item(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                        .description(Some("CSS color (e.g. '#ff0000', 'rgb(255 0 0 / 50%)')")),
+                )
+                .into(),
+        )
+    }
+}
+
+/// CSS named colors sorted by name.
+///
+/// https://developer.mozilla.org/en-US/docs/Web/CSS/named-color
+const NAMED_COLORS: [(&str, (u8, u8, u8)); 148] = [
+    ("aliceblue", (240, 248, 255)),
+    ("antiquewhite", (250, 235, 215)),
+    ("aqua", (0, 255, 255)),
+    ("aquamarine", (127, 255, 212)),
+    ("azure", (240, 255, 255)),
+    ("beige", (245, 245, 220)),
+    ("bisque", (255, 228, 196)),
+    ("black", (0, 0, 0)),
+    ("blanchedalmond", (255, 235, 205)),
+    ("blue", (0, 0, 255)),
+    ("blueviolet", (138, 43, 226)),
+    ("brown", (165, 42, 42)),
+    ("burlywood", (222, 184, 135)),
+    ("cadetblue", (95, 158, 160)),
+    ("chartreuse", (127, 255, 0)),
+    ("chocolate", (210, 105, 30)),
+    ("coral", (255, 127, 80)),
+    ("cornflowerblue", (100, 149, 237)),
+    ("cornsilk", (255, 248, 220)),
+    ("crimson", (220, 20, 60)),
+    ("cyan", (0, 255, 255)),
+    ("darkblue", (0, 0, 139)),
+    ("darkcyan", (0, 139, 139)),
+    ("darkgoldenrod", (184, 134, 11)),
+    ("darkgray", (169, 169, 169)),
+    ("darkgreen", (0, 100, 0)),
+    ("darkgrey", (169, 169, 169)),
+    ("darkkhaki", (189, 183, 107)),
+    ("darkmagenta", (139, 0, 139)),
+    ("darkolivegreen", (85, 107, 47)),
+    ("darkorange", (255, 140, 0)),
+    ("darkorchid", (153, 50, 204)),
+    ("darkred", (139, 0, 0)),
+    ("darksalmon", (233, 150, 122)),
+    ("darkseagreen", (143, 188, 143)),
+    ("darkslateblue", (72, 61, 139)),
+    ("darkslategray", (47, 79, 79)),
+    ("darkslategrey", (47, 79, 79)),
+    ("darkturquoise", (0, 206, 209)),
+    ("darkviolet", (148, 0, 211)),
+    ("deeppink", (255, 20, 147)),
+    ("deepskyblue", (0, 191, 255)),
+    ("dimgray", (105, 105, 105)),
+    ("dimgrey", (105, 105, 105)),
+    ("dodgerblue", (30, 144, 255)),
+    ("firebrick", (178, 34, 34)),
+    ("floralwhite", (255, 250, 240)),
+    ("forestgreen", (34, 139, 34)),
+    ("fuchsia", (255, 0, 255)),
+    ("gainsboro", (220, 220, 220)),
+    ("ghostwhite", (248, 248, 255)),
+    ("gold", (255, 215, 0)),
+    ("goldenrod", (218, 165, 32)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("greenyellow", (173, 255, 47)),
+    ("grey", (128, 128, 128)),
+    ("honeydew", (240, 255, 240)),
+    ("hotpink", (255, 105, 180)),
+    ("indianred", (205, 92, 92)),
+    ("indigo", (75, 0, 130)),
+    ("ivory", (255, 255, 240)),
+    ("khaki", (240, 230, 140)),
+    ("lavender", (230, 230, 250)),
+    ("lavenderblush", (255, 240, 245)),
+    ("lawngreen", (124, 252, 0)),
+    ("lemonchiffon", (255, 250, 205)),
+    ("lightblue", (173, 216, 230)),
+    ("lightcoral", (240, 128, 128)),
+    ("lightcyan", (224, 255, 255)),
+    ("lightgoldenrodyellow", (250, 250, 210)),
+    ("lightgray", (211, 211, 211)),
+    ("lightgreen", (144, 238, 144)),
+    ("lightgrey", (211, 211, 211)),
+    ("lightpink", (255, 182, 193)),
+    ("lightsalmon", (255, 160, 122)),
+    ("lightseagreen", (32, 178, 170)),
+    ("lightskyblue", (135, 206, 250)),
+    ("lightslategray", (119, 136, 153)),
+    ("lightslategrey", (119, 136, 153)),
+    ("lightsteelblue", (176, 196, 222)),
+    ("lightyellow", (255, 255, 224)),
+    ("lime", (0, 255, 0)),
+    ("limegreen", (50, 205, 50)),
+    ("linen", (250, 240, 230)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("mediumaquamarine", (102, 205, 170)),
+    ("mediumblue", (0, 0, 205)),
+    ("mediumorchid", (186, 85, 211)),
+    ("mediumpurple", (147, 112, 219)),
+    ("mediumseagreen", (60, 179, 113)),
+    ("mediumslateblue", (123, 104, 238)),
+    ("mediumspringgreen", (0, 250, 154)),
+    ("mediumturquoise", (72, 209, 204)),
+    ("mediumvioletred", (199, 21, 133)),
+    ("midnightblue", (25, 25, 112)),
+    ("mintcream", (245, 255, 250)),
+    ("mistyrose", (255, 228, 225)),
+    ("moccasin", (255, 228, 181)),
+    ("navajowhite", (255, 222, 173)),
+    ("navy", (0, 0, 128)),
+    ("oldlace", (253, 245, 230)),
+    ("olive", (128, 128, 0)),
+    ("olivedrab", (107, 142, 35)),
+    ("orange", (255, 165, 0)),
+    ("orangered", (255, 69, 0)),
+    ("orchid", (218, 112, 214)),
+    ("palegoldenrod", (238, 232, 170)),
+    ("palegreen", (152, 251, 152)),
+    ("paleturquoise", (175, 238, 238)),
+    ("palevioletred", (219, 112, 147)),
+    ("papayawhip", (255, 239, 213)),
+    ("peachpuff", (255, 218, 185)),
+    ("peru", (205, 133, 63)),
+    ("pink", (255, 192, 203)),
+    ("plum", (221, 160, 221)),
+    ("powderblue", (176, 224, 230)),
+    ("purple", (128, 0, 128)),
+    ("rebeccapurple", (102, 51, 153)),
+    ("red", (255, 0, 0)),
+    ("rosybrown", (188, 143, 143)),
+    ("royalblue", (65, 105, 225)),
+    ("saddlebrown", (139, 69, 19)),
+    ("salmon", (250, 128, 114)),
+    ("sandybrown", (244, 164, 96)),
+    ("seagreen", (46, 139, 87)),
+    ("seashell", (255, 245, 238)),
+    ("sienna", (160, 82, 45)),
+    ("silver", (192, 192, 192)),
+    ("skyblue", (135, 206, 235)),
+    ("slateblue", (106, 90, 205)),
+    ("slategray", (112, 128, 144)),
+    ("slategrey", (112, 128, 144)),
+    ("snow", (255, 250, 250)),
+    ("springgreen", (0, 255, 127)),
+    ("steelblue", (70, 130, 180)),
+    ("tan", (210, 180, 140)),
+    ("teal", (0, 128, 128)),
+    ("thistle", (216, 191, 216)),
+    ("tomato", (255, 99, 71)),
+    ("turquoise", (64, 224, 208)),
+    ("violet", (238, 130, 238)),
+    ("wheat", (245, 222, 179)),
+    ("white", (255, 255, 255)),
+    ("whitesmoke", (245, 245, 245)),
+    ("yellow", (255, 255, 0)),
+    ("yellowgreen", (154, 205, 50)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_colors_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!("#f80".parse(), Ok(Color::new_rgb(255, 136, 0)));
+        assert_eq!("#FF8800".parse(), Ok(Color::new_rgb(255, 136, 0)));
+        assert_eq!(
+            "#ff880080".parse(),
+            Ok(Color::new_rgba(255, 136, 0, 128.0 / 255.0))
+        );
+        assert!("#ff88".parse::<Color>().unwrap().get_alpha() > 0.5);
+        assert!("#ff88001".parse::<Color>().is_err());
+        assert!("#gg8800".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!("rgb(255, 136, 0)".parse(), Ok(Color::new_rgb(255, 136, 0)));
+        assert_eq!(
+            "rgba(255, 136, 0, 0.5)".parse(),
+            Ok(Color::new_rgba(255, 136, 0, 0.5))
+        );
+        assert_eq!(
+            "rgb(100% 0% 0% / 25%)".parse(),
+            Ok(Color::new_rgba(255, 0, 0, 0.25))
+        );
+        assert_eq!(
+            "hsl(120deg, 100%, 25%)".parse(),
+            Ok(Color::new_rgb(0, 128, 0))
+        );
+        assert_eq!(
+            "hsl(0.5turn 100% 50%)".parse(),
+            Ok(Color::new_rgb(0, 255, 255))
+        );
+        assert!("rgb(255, 0)".parse::<Color>().is_err());
+        assert!("cmyk(0, 0, 0, 0)".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_named() {
+        assert_eq!(" Red ".parse(), Ok(Color::new_rgb(255, 0, 0)));
+        assert_eq!("rebeccapurple".parse(), Ok(Color::new_rgb(102, 51, 153)));
+        assert_eq!("transparent".parse(), Ok(Color::transparent()));
+        assert!("notacolor".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_hsv() {
+        assert_eq!(Color::from_hsv(0.0, 1.0, 1.0), Color::new_rgb(255, 0, 0));
+        assert_eq!(Color::from_hsv(240.0, 1.0, 0.5), Color::new_rgb(0, 0, 128));
+        assert_eq!(Color::from_hsv(0.0, 0.0, 1.0), Color::white());
+    }
+}
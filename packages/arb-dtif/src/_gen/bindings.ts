@@ -143,6 +143,12 @@ export type AngleUnit =
  */
 "Deg"
 
+/**
+ * Version of the artboard (DTIF) schema.
+ * 
+ * Variants must be declared in chronological order
+ * as the ordering is used to determine which migrations to apply.
+ */
 export type ArbVersion = "V000001"
 
 export type ArtboardChangeOutputEvent = { viewport: Viewport; size: Size }
@@ -178,19 +184,65 @@ x: T;
  */
 y: T }
 
+/**
+ * Blurs everything behind the node within its shape.
+ * 
+ * Only rendered by browsers, as SVG filters can't access what is behind an element.
+ * Renderers without HTML support (e.g. the PNG and PDF export) render the node without it.
+ */
+export type BackgroundBlurStyle = { id?: ReferenceId | null; blur: Abs; visible?: boolean }
+
+/**
+ * Binds a property of a node, style or paint to a variable
+ * or unbinds it if no variable id is specified.
+ */
+export type BindVariableInputEvent = { targetId: ReferenceIdOrEntity; property: VariableProperty; variableId?: ReferenceId | null }
+
 export type BlendMode = "Normal" | "Multiply" | "Screen" | "Overlay" | "Darken" | "Lighten" | "ColorDodge" | "ColorBurn" | "HardLight" | "SoftLight" | "Difference" | "Exclusion" | "Hue" | "Saturation" | "Color" | "Luminosity"
 
-export type Color = [number, number, number]
+export type BooleanOperation = 
+/**
+ * Area covered by any child.
+ */
+"Union" | 
+/**
+ * Area of the bottom-most child not covered by any other child.
+ */
+"Subtract" | 
+/**
+ * Area covered by all children.
+ */
+"Intersect" | 
+/**
+ * Area covered by an odd number of children.
+ */
+"Exclude"
+
+/**
+ * Combines the outlines of its children (shape nodes) into a single shape.
+ * 
+ * The children aren't rendered themselves, only the resulting shape is.
+ */
+export type BooleanOperationNode = { id?: ReferenceId | null; operation?: BooleanOperation; translation?: Vec2; rotationDeg?: Angle; size: Size; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity; layoutElement?: LayoutElement; styles?: Style[]; children?: ReferenceIdOrEntity[] }
+
+export type Color = [number, number, number] | [number, number, number, number] | string
 
 export type ColorMatrix = { x_axis: [number, number, number, number]; y_axis: [number, number, number, number]; z_axis: [number, number, number, number]; w_axis: [number, number, number, number]; v_axis: [number, number, number, number] }
 
 export type ComponentChange = { type: "Size"; size: Size } | { type: "Transform"; rotationDeg: number; translation: Vec2 } | { type: "GlobalTransform"; rotationDeg: number; translation: Vec2 }
 
+/**
+ * A reusable node tree that can be placed via instance nodes.
+ * 
+ * Components aren't rendered themselves, only their instances are.
+ */
+export type ComponentNode = { id?: ReferenceId | null; clipContent?: boolean; layoutParent?: StaticLayoutParent | null; cornerRadii?: CornerRadii; size: Size; styles?: Style[]; children?: ReferenceIdOrEntity[] }
+
 export type Constraint = "Start" | "Center" | "End" | "Stretch" | "Scale"
 
 export type Constraints = { horizontal: Constraint; vertical: Constraint }
 
-export type CoreInputEvent = ({ type: "UpdateArtboardSize" } & UpdateArtboardSizeInputEvent) | ({ type: "UpdateArtboardViewport" } & UpdateArtboardViewportInputEvent) | ({ type: "FocusRootNodes" }) | ({ type: "CreateNode" } & CreateNodeInputEvent) | ({ type: "UpdateFrameNode" } & UpdateFrameNodeInputEvent) | ({ type: "UpdateEllipseNode" } & UpdateEllipseNodeInputEvent) | ({ type: "UpdateStarNode" } & UpdateStarNodeInputEvent) | ({ type: "UpdatePolygonNode" } & UpdatePolygonNodeInputEvent) | ({ type: "UpdateTextNode" } & UpdateTextNodeInputEvent) | ({ type: "UpdateFillStyle" } & UpdateFillStyleInputEvent) | ({ type: "UpdateStrokeStyle" } & UpdateStorkeStyleInputEvent) | ({ type: "UpdateDropShadowStyle" } & UpdateDropShadowStyleInputEvent) | ({ type: "CreatePaint" } & CreatePaintInputEvent) | ({ type: "UpdateSolidPaint" } & UpdateSolidPaintInputEvent) | ({ type: "UpdateImagePaint" } & UpdateImagePaintInputEvent) | ({ type: "UpdateGradientPaint" } & UpdateGradientPaintInputEvent) | ({ type: "CreateAsset" } & CreateAssetInputEvent) | ({ type: "DeleteEntity" } & DeleteEntityInputEvent) | ({ type: "UpdateEntityTransform" } & UpdateEntityTransformInputEvent) | ({ type: "UpdateEntitySize" } & UpdateEntitySizeInputEvent) | ({ type: "MoveEntity" } & MoveEntityInputEvent) | ({ type: "UpdateEntityRotation" } & UpdateEntityRotationInputEvent) | ({ type: "UpdateEntityVisibility" } & UpdateEntityVisibilityInputEvent) | ({ type: "UpdateEntityCornerRadii" } & UpdateEntityCornerRadiiInputEvent) | ({ type: "UpdateEntityBlendMode" } & UpdateEntityBlendModeInputEvent) | ({ type: "UpdateEntityOpacity" } & UpdateEntityOpacityInputEvent) | ({ type: "UpdateEntityChildren" } & UpdateEntityChildrenInputEvent) | ({ type: "RegisterLuaScript" } & RegisterLuaScriptInputEvent) | ({ type: "ExecuteLuaScript" } & ExecuteLuaScriptInputEvent)
+export type CoreInputEvent = ({ type: "UpdateArtboardSize" } & UpdateArtboardSizeInputEvent) | ({ type: "UpdateArtboardViewport" } & UpdateArtboardViewportInputEvent) | ({ type: "FocusRootNodes" }) | ({ type: "CreateNode" } & CreateNodeInputEvent) | ({ type: "UpdateFrameNode" } & UpdateFrameNodeInputEvent) | ({ type: "UpdateEllipseNode" } & UpdateEllipseNodeInputEvent) | ({ type: "UpdateStarNode" } & UpdateStarNodeInputEvent) | ({ type: "UpdatePolygonNode" } & UpdatePolygonNodeInputEvent) | ({ type: "UpdateTextNode" } & UpdateTextNodeInputEvent) | ({ type: "UpdateInstanceNode" } & UpdateInstanceNodeInputEvent) | ({ type: "UpdateBooleanOperationNode" } & UpdateBooleanOperationNodeInputEvent) | ({ type: "CreateStyle" } & CreateStyleInputEvent) | ({ type: "DeleteStyle" } & DeleteStyleInputEvent) | ({ type: "ReorderStyles" } & ReorderStylesInputEvent) | ({ type: "UpdateFillStyle" } & UpdateFillStyleInputEvent) | ({ type: "UpdateStrokeStyle" } & UpdateStorkeStyleInputEvent) | ({ type: "UpdateDropShadowStyle" } & UpdateDropShadowStyleInputEvent) | ({ type: "UpdateInnerShadowStyle" } & UpdateInnerShadowStyleInputEvent) | ({ type: "UpdateLayerBlurStyle" } & UpdateLayerBlurStyleInputEvent) | ({ type: "UpdateBackgroundBlurStyle" } & UpdateBackgroundBlurStyleInputEvent) | ({ type: "CreatePaint" } & CreatePaintInputEvent) | ({ type: "UpdateSolidPaint" } & UpdateSolidPaintInputEvent) | ({ type: "UpdateImagePaint" } & UpdateImagePaintInputEvent) | ({ type: "UpdateGradientPaint" } & UpdateGradientPaintInputEvent) | ({ type: "CreateAsset" } & CreateAssetInputEvent) | ({ type: "CreateVariable" } & CreateVariableInputEvent) | ({ type: "UpdateVariable" } & UpdateVariableInputEvent) | ({ type: "UpdateVariableMode" } & UpdateVariableModeInputEvent) | ({ type: "BindVariable" } & BindVariableInputEvent) | ({ type: "DeleteEntity" } & DeleteEntityInputEvent) | ({ type: "UpdateEntityTransform" } & UpdateEntityTransformInputEvent) | ({ type: "UpdateEntitySize" } & UpdateEntitySizeInputEvent) | ({ type: "MoveEntity" } & MoveEntityInputEvent) | ({ type: "UpdateEntityRotation" } & UpdateEntityRotationInputEvent) | ({ type: "UpdateEntityVisibility" } & UpdateEntityVisibilityInputEvent) | ({ type: "UpdateEntityCornerRadii" } & UpdateEntityCornerRadiiInputEvent) | ({ type: "UpdateEntityBlendMode" } & UpdateEntityBlendModeInputEvent) | ({ type: "UpdateEntityOpacity" } & UpdateEntityOpacityInputEvent) | ({ type: "UpdateEntityChildren" } & UpdateEntityChildrenInputEvent) | ({ type: "RegisterLuaScript" } & RegisterLuaScriptInputEvent) | ({ type: "ExecuteLuaScript" } & ExecuteLuaScriptInputEvent)
 
 export type CornerRadii = [Angle, Angle, Angle, Angle]
 
@@ -200,6 +252,15 @@ export type CreateNodeInputEvent = { node: Node }
 
 export type CreatePaintInputEvent = { paint: Paint }
 
+export type CreateStyleInputEvent = { nodeId: ReferenceIdOrEntity; style: Style; 
+/**
+ * Position within the styles of the node where 0 is the top-most style.
+ * Defaults to the top.
+ */
+index?: number | null }
+
+export type CreateVariableInputEvent = { variable: Variable }
+
 export type Cursor = { type: "Default" } | { type: "Grabbing" } | { type: "Crosshair" } | { type: "Resize"; rotationDeg: number } | { type: "Rotate"; rotationDeg: number }
 
 export type CursorChangeOutputEvent = { cursor: Cursor }
@@ -222,6 +283,91 @@ export type CursorUpOnArbInputEvent = { position: Vec2; button: MouseButton }
 
 export type DeleteEntityInputEvent = { id: ReferenceIdOrEntity }
 
+export type DeleteStyleInputEvent = { id: ReferenceIdOrEntity }
+
+export type Diagnostic = { 
+/**
+ * JSON path to the offending value (e.g. `$.nodes[2].styles[0].paintId`).
+ */
+path: string; severity: DiagnosticSeverity; code: DiagnosticCode; 
+/**
+ * Human-readable description of the problem.
+ */
+message: string }
+
+export type DiagnosticCode = 
+/**
+ * The same reference id is used by more than one element.
+ */
+"DuplicateReferenceId" | 
+/**
+ * A reference id doesn't resolve to any element of the DTIF.
+ */
+"UnresolvedReference" | 
+/**
+ * A reference id resolves to an element of the wrong kind
+ * (e.g. a fill whose paint id points to a node).
+ */
+"ReferenceKindMismatch" | 
+/**
+ * A node references itself as child, directly or through its descendants.
+ */
+"SelfReference" | 
+/**
+ * A node is referenced as child by more than one frame.
+ */
+"MultipleParents" | 
+/**
+ * A text attribute interval has a start after its end.
+ */
+"InvalidTextAttributeInterval" | 
+/**
+ * A text attribute interval lies outside of the text.
+ */
+"TextAttributeIntervalOutOfBounds" | 
+/**
+ * A vector path couldn't be parsed.
+ */
+"InvalidVectorPath" | 
+/**
+ * A variable is bound to a property that doesn't accept values of its type.
+ */
+"VariableTypeMismatch" | 
+/**
+ * A stroke dash array can't be drawn as specified
+ * (e.g. it contains negative values and is drawn as solid stroke instead).
+ */
+"InvalidDashArray"
+
+export type DiagnosticSeverity = 
+/**
+ * The DTIF can't be loaded as intended.
+ */
+"Error" | 
+/**
+ * The DTIF can be loaded but likely doesn't do what was intended.
+ */
+"Warning"
+
+/**
+ * The layout algorithm used to position the children of a static layout parent.
+ * 
+ * The default behavior is [`Display::Flex`].
+ * 
+ * [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/display)
+ */
+export type Display = 
+/**
+ * Children are laid out one after another along the main axis
+ * defined by the [`FlexDirection`].
+ */
+"Flex" | 
+/**
+ * Children are placed into the cells of a grid
+ * defined by the template rows and columns.
+ */
+"Grid"
+
 export type DropShadowStyle = { id?: ReferenceId | null; color?: Color; position: Vec2; spread?: Abs; blur: Abs; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity }
 
 /**
@@ -238,6 +384,11 @@ size: Size;
  * The viewport of the artboard.
  */
 viewport?: Viewport | null; 
+/**
+ * Whether the layout keeps sub-pixel precision
+ * instead of rounding positions and sizes to whole pixels.
+ */
+subPixelLayout?: boolean; 
 /**
  * A list of nodes.
  */
@@ -250,6 +401,18 @@ paints?: Paint[];
  * A list of assets.
  */
 assets?: AssetWithId[]; 
+/**
+ * A list of variables (design tokens).
+ */
+variables?: Variable[]; 
+/**
+ * The active variable mode (e.g. "light" or "dark").
+ */
+variableMode?: string | null; 
+/**
+ * A list of properties bound to variables.
+ */
+variableBindings?: VariableBinding[]; 
 /**
  * A list of input events.
  */
@@ -259,6 +422,68 @@ events?: CoreInputEvent[];
  */
 scripts?: LuaScriptWithId[] }
 
+/**
+ * DTIF document holding multiple artboards (e.g. the same content in different formats)
+ * that share paints, assets, variables and scripts.
+ */
+export type DtifDocument = { version?: ArbVersion | null; 
+/**
+ * A list of artboards (pages).
+ */
+artboards: DtifDocumentArtboard[]; 
+/**
+ * A list of paints shared by all artboards.
+ */
+paints?: Paint[]; 
+/**
+ * A list of assets shared by all artboards.
+ */
+assets?: AssetWithId[]; 
+/**
+ * A list of variables (design tokens) shared by all artboards.
+ */
+variables?: Variable[]; 
+/**
+ * The active variable mode (e.g. "light" or "dark").
+ */
+variableMode?: string | null; 
+/**
+ * A list of properties bound to variables.
+ * Bindings only end up in the artboards containing their target.
+ */
+variableBindings?: VariableBinding[]; 
+/**
+ * A list of scripts shared by all artboards.
+ */
+scripts?: LuaScriptWithId[] }
+
+export type DtifDocumentArtboard = { 
+/**
+ * The name used to select the artboard (e.g. "instagram-story").
+ */
+name?: string | null; 
+/**
+ * The absolute size of the artboard.
+ */
+size: Size; 
+/**
+ * The viewport of the artboard.
+ */
+viewport?: Viewport | null; 
+/**
+ * Whether the layout keeps sub-pixel precision
+ * instead of rounding positions and sizes to whole pixels.
+ */
+subPixelLayout?: boolean; 
+/**
+ * A list of nodes.
+ */
+nodes: Node[]; 
+/**
+ * A list of input events.
+ */
+events?: CoreInputEvent[] }
+
 export type EllipseNode = { id?: ReferenceId | null; startingAngle?: number; endingAngle?: number; innerRadiusRatio?: number; translation?: Vec2; rotationDeg?: Angle; size: Size; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity; layoutElement?: LayoutElement; styles?: Style[] }
 
 /**
@@ -313,6 +538,27 @@ export type FlexDirection =
  */
 "ColumnReverse"
 
+/**
+ * Controls whether flex items are forced onto one line or can wrap onto multiple lines.
+ * 
+ * The default behavior is [`FlexWrap::NoWrap`].
+ * 
+ * [Specification](https://www.w3.org/TR/css-flexbox-1/#flex-wrap-property)
+ */
+export type FlexWrap = 
+/**
+ * Items will not wrap and stay on a single line
+ */
+"NoWrap" | 
+/**
+ * Items will wrap according to this item's [`FlexDirection`]
+ */
+"Wrap" | 
+/**
+ * Items will wrap in the opposite direction to this item's [`FlexDirection`]
+ */
+"WrapReverse"
+
 export type FocusRootNodesInputEvent = null
 
 /**
@@ -426,13 +672,107 @@ opacity?: Opacity }
 
 export type GradientPaint = { id?: ReferenceId | null; variant: GradientVariant; stops: GradientColorStop[] }
 
-export type GradientVariant = { type: "Linear"; transform?: Mat3 } | { type: "Radial"; transform?: Mat3 }
+export type GradientVariant = { type: "Linear"; transform?: Mat3 } | { type: "Radial"; transform?: Mat3 } | 
+/**
+ * Conic gradient sweeping clockwise around the center,
+ * starting at the positive x-axis of the gradient space.
+ */
+{ type: "Angular"; transform?: Mat3 } | 
+/**
+ * Gradient spreading from the center in the shape of a diamond.
+ */
+{ type: "Diamond"; transform?: Mat3 }
+
+/**
+ * A grid line an element edge is placed at.
+ * 
+ * [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-row-start)
+ */
+export type GridLine = 
+/**
+ * Placed by the auto-placement algorithm.
+ */
+{ type: "Auto" } | 
+/**
+ * Placed at the line with the given index.
+ * 
+ * Lines are 1-based, negative indices count backwards from the last line.
+ */
+{ type: "Line"; index: number } | 
+/**
+ * Placed the given number of tracks away from the opposite edge.
+ */
+{ type: "Span"; count: number }
+
+/**
+ * The placement of an element along one axis of a grid layout.
+ * 
+ * [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-row)
+ */
+export type GridPlacement = { start?: GridLine; end?: GridLine }
+
+/**
+ * The size of a single track (row or column) of a grid layout.
+ * 
+ * [MDN](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-columns)
+ */
+export type GridTrack = 
+/**
+ * A fixed size.
+ */
+{ type: "Abs"; value: Abs } | 
+/**
+ * A size relative to the size of the grid parent.
+ */
+{ type: "Ratio"; value: Ratio } | 
+/**
+ * A share of the free space left after all other tracks have been sized
+ * (similar to the `fr` unit in CSS).
+ */
+{ type: "Fraction"; value: number } | 
+/**
+ * Sized to fit the content of the track.
+ */
+{ type: "Auto" }
 
 export type HandleSide = "Top" | "Bottom" | "Left" | "Right"
 
-export type HorizontalTextAlignment = "Start" | "End" | "Left" | "Right" | "Center"
+export type HorizontalTextAlignment = "Start" | "End" | "Left" | "Right" | "Center" | 
+/**
+ * Stretches the word separators so that each line fills the container width,
+ * except for the last line of a paragraph, which is aligned to the start.
+ */
+"Justified"
+
+/**
+ * Color adjustments applied to an image.
+ * 
+ * Each adjustment ranges from -1.0 to 1.0, where 0.0 leaves the image unchanged.
+ */
+export type ImageFilters = { 
+/**
+ * Brightens (positive) or darkens (negative) the image.
+ */
+exposure?: number; 
+/**
+ * Increases (positive) or decreases (negative) the difference between light and dark tones.
+ */
+contrast?: number; 
+/**
+ * Intensifies (positive) or desaturates (negative) the colors,
+ * where -1.0 results in a grayscale image.
+ */
+saturation?: number; 
+/**
+ * Shifts the colors towards warm (positive) or cool (negative) tones.
+ */
+temperature?: number; 
+/**
+ * Shifts the colors towards magenta (positive) or green (negative).
+ */
+tint?: number }
 
-export type ImagePaint = { id?: ReferenceId | null; imageId: ReferenceIdOrImageId; scaleMode?: ImageScaleMode }
+export type ImagePaint = { id?: ReferenceId | null; imageId: ReferenceIdOrImageId; scaleMode?: ImageScaleMode; filters?: ImageFilters | null }
 
 export type ImageScaleMode = 
 /**
@@ -452,6 +792,33 @@ export type ImageScaleMode =
  */
 { type: "Tile"; rotation?: number; scalingFactor: number }
 
+export type InnerShadowStyle = { id?: ReferenceId | null; color?: Color; position: Vec2; spread?: Abs; blur: Abs; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity }
+
+/**
+ * Places the node tree of a component.
+ * 
+ * The content (children, styles, clipping, corner radii and layout)
+ * is taken from the component and kept in sync with it.
+ */
+export type InstanceNode = { id?: ReferenceId | null; componentId: ReferenceIdOrEntity; overrides?: InstanceOverride[]; translation?: Vec2; rotationDeg?: Angle; size: Size; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity; layoutElement?: LayoutElement }
+
+/**
+ * Overrides properties of a node or style within the component of an instance.
+ */
+export type InstanceOverride = { 
+/**
+ * The node or style within the component to override.
+ */
+targetId: ReferenceIdOrEntity; 
+/**
+ * Replaces the text of a text node.
+ */
+text?: string | null; 
+/**
+ * Replaces the paint of a fill or stroke style.
+ */
+paintId?: ReferenceIdOrEntity | null; visible?: boolean | null; size?: Size | null }
+
 export type InteractionInputEvent = ({ type: "KeyDownOnArtboard" } & KeyDownOnArbInputEvent) | ({ type: "KeyUpOnArtboard" } & KeyUpOnArbInputEvent) | ({ type: "CursorEnteredArtboard" }) | ({ type: "CursorExitedArtboard" }) | ({ type: "CursorMovedOnArtboard" } & CursorMovedOnArbInputEvent) | ({ type: "CursorDownOnArtboard" } & CursorDownOnArbInputEvent) | ({ type: "CursorUpOnArtboard" } & CursorUpOnArbInputEvent) | ({ type: "MouseWheeledOnArtboard" } & MouseWheeledOnArbInputEvent) | ({ type: "CursorDownOnEntity" } & CursorDownOnEntityInputEvent) | ({ type: "CursorDownOnResizeHandle" } & CursorDownOnResizeHandleInputEvent) | ({ type: "CursorDownOnRotateHandle" } & CursorDownOnRotateHandleInputEvent) | ({ type: "InteractionToolChanged" } & InteractionToolChangedInputEvent)
 
 export type InteractionMode = 
@@ -1395,7 +1762,18 @@ export type KeyUpOnArbInputEvent = {
  */
 keyCode: KeyCode }
 
-export type LayoutElement = ({ type: "Absolute" } & AbsoluteLayoutElement) | ({ type: "Static" } & StaticLayoutElement)
+export type LayerBlurStyle = { id?: ReferenceId | null; blur: Abs; visible?: boolean }
+
+export type LayoutElement = 
+/**
+ * Positioned by its constraints relative to the parent.
+ * Within a static layout parent the element is excluded from the flow of its siblings.
+ */
+({ type: "Absolute" } & AbsoluteLayoutElement) | 
+/**
+ * Positioned by the static layout (flex or grid) of the parent.
+ */
+({ type: "Static" } & StaticLayoutElement)
 
 export type LayoutElementSizingMode = "Fixed" | "Fill"
 
@@ -1528,7 +1906,7 @@ export type NativeKeyCode =
  */
 { xkb: number }
 
-export type Node = ({ type: "Frame" } & FrameNode) | ({ type: "Rectangle" } & RectangleNode) | ({ type: "Ellipse" } & EllipseNode) | ({ type: "Star" } & StarNode) | ({ type: "Polygon" } & PolygonNode) | ({ type: "Text" } & TextNode) | ({ type: "Vector" } & VectorNode)
+export type Node = ({ type: "Frame" } & FrameNode) | ({ type: "Rectangle" } & RectangleNode) | ({ type: "Ellipse" } & EllipseNode) | ({ type: "Star" } & StarNode) | ({ type: "Polygon" } & PolygonNode) | ({ type: "Text" } & TextNode) | ({ type: "Vector" } & VectorNode) | ({ type: "BooleanOperation" } & BooleanOperationNode) | ({ type: "Component" } & ComponentNode) | ({ type: "Instance" } & InstanceNode)
 
 /**
  * An opacity.
@@ -1558,6 +1936,12 @@ export type ReferenceIdOrImageId = { type: "ImageId"; imageId: { idx: number; ve
 
 export type RegisterLuaScriptInputEvent = { script: LuaScriptWithId }
 
+/**
+ * Reorders the styles of a node (index 0 is the top-most style).
+ * Styles of the node missing in the list keep their relative order below the listed ones.
+ */
+export type ReorderStylesInputEvent = { id: ReferenceIdOrEntity; styles: ReferenceIdOrEntity[] }
+
 /**
  * A 32-bit float that implements `Eq`, `Ord` and `Hash`.
  * 
@@ -1577,19 +1961,99 @@ export type SpectaExport = { arb_dtif: DtifArtboard; svg_arb_input_event: SvgArb
 
 export type StarNode = { id?: ReferenceId | null; innerRadiusRatio?: number; pointCount?: number; translation?: Vec2; rotationDeg?: Angle; size: Size; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity; layoutElement?: LayoutElement; styles?: Style[] }
 
-export type StaticLayoutElement = { alignSelf?: AlignItems | null; justifySelf?: AlignItems | null; margin?: Rect<AutoLength>; horizontalSizingMode?: LayoutElementSizingMode; verticalSizingMode?: LayoutElementSizingMode }
+export type StaticLayoutElement = { alignSelf?: AlignItems | null; justifySelf?: AlignItems | null; margin?: Rect<AutoLength>; 
+/**
+ * The placement within the rows of a grid parent.
+ */
+gridRow?: GridPlacement; 
+/**
+ * The placement within the columns of a grid parent.
+ */
+gridColumn?: GridPlacement; 
+/**
+ * The minimum size, an axis without value is unconstrained.
+ */
+minSize?: Axes<Length | null>; 
+/**
+ * The maximum size, an axis without value is unconstrained.
+ */
+maxSize?: Axes<Length | null>; 
+/**
+ * The preferred width to height ratio.
+ * If one axis fills the parent while the other is fixed,
+ * the fixed axis is derived from the ratio instead.
+ */
+aspectRatio?: number | null; 
+/**
+ * How much of the free space along the main axis of a flex parent
+ * the element takes relative to its siblings.
+ */
+flexGrow?: number; 
+/**
+ * How much the element shrinks relative to its siblings
+ * when its flex parent overflows along the main axis.
+ */
+flexShrink?: number; horizontalSizingMode?: LayoutElementSizingMode; verticalSizingMode?: LayoutElementSizingMode }
 
-export type StaticLayoutParent = { alignItems?: AlignItems | null; justifyContent?: AlignContent | null; gap?: Axes<Length>; padding?: Rect<Length>; flexDirection?: FlexDirection; horizontalSizingMode?: LayoutParentSizingMode; verticalSizingMode?: LayoutParentSizingMode }
+export type StaticLayoutParent = { display?: Display; alignItems?: AlignItems | null; justifyContent?: AlignContent | null; gap?: Axes<Length>; padding?: Rect<Length>; flexDirection?: FlexDirection; flexWrap?: FlexWrap; 
+/**
+ * The sizes of the grid rows, only used with [`Display::Grid`].
+ */
+gridTemplateRows?: GridTrack[]; 
+/**
+ * The sizes of the grid columns, only used with [`Display::Grid`].
+ */
+gridTemplateColumns?: GridTrack[]; horizontalSizingMode?: LayoutParentSizingMode; verticalSizingMode?: LayoutParentSizingMode }
+
+/**
+ * Position of the stroke relative to the outline of the shape.
+ */
+export type StrokeAlignment = 
+/**
+ * Centered on the outline.
+ */
+"Center" | 
+/**
+ * Within the filled area of the shape.
+ */
+"Inside" | 
+/**
+ * Outside the filled area of the shape.
+ */
+"Outside"
+
+/**
+ * Shape at the end of open stroked paths.
+ */
+export type StrokeCap = "Butt" | "Round" | "Square"
+
+/**
+ * Shape at the corners of stroked paths.
+ */
+export type StrokeJoin = 
+/**
+ * Sharp corner, beveled once the miter limit is exceeded.
+ */
+"Miter" | "Round" | "Bevel"
 
-export type StrokeStyle = { id?: ReferenceId | null; width: Abs; paintId: ReferenceIdOrEntity; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity }
+export type StrokeStyle = { id?: ReferenceId | null; width: Abs; paintId: ReferenceIdOrEntity; alignment?: StrokeAlignment; cap?: StrokeCap; join?: StrokeJoin; 
+/**
+ * Limit of the ratio of miter length to stroke width
+ * at which miter joins are beveled.
+ */
+miterLimit?: number; 
+/**
+ * Alternating lengths of dashes and gaps. An empty array draws a solid stroke.
+ */
+dashArray?: Abs[]; dashOffset?: Abs; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity }
 
-export type Style = ({ type: "Fill" } & FillStyle) | ({ type: "Stroke" } & StrokeStyle) | ({ type: "DropShadow" } & DropShadowStyle)
+export type Style = ({ type: "Fill" } & FillStyle) | ({ type: "Stroke" } & StrokeStyle) | ({ type: "DropShadow" } & DropShadowStyle) | ({ type: "InnerShadow" } & InnerShadowStyle) | ({ type: "LayerBlur" } & LayerBlurStyle) | ({ type: "BackgroundBlur" } & BackgroundBlurStyle)
 
 export type SvgArbInputEvent = { type: "Core"; event: CoreInputEvent } | { type: "Interaction"; event: InteractionInputEvent }
 
 export type SvgArbOutputEvent = ({ type: "SvgElementChange" } & SvgElementChangesOutputEvent) | ({ type: "ArtboardChange" } & ArtboardChangeOutputEvent) | ({ type: "WatchedEntityChange" } & WatchedEntityChangesOutputEvent) | ({ type: "SelectionChange" } & SelectionChangeOutputEvent) | ({ type: "InteractionModeChange" } & InteractionModeChangeOutputEvent) | ({ type: "InteractionToolChange" } & InteractionToolChangeOutputEvent) | ({ type: "CursorChange" } & CursorChangeOutputEvent)
 
-export type SvgAttribute = { type: "Id"; id: SvgElementId } | { type: "Class"; class: string } | { type: "Href"; href: SvgHrefAttribute } | { type: "Width"; width: number; unit: SvgMeasurementUnit } | { type: "Height"; height: number; unit: SvgMeasurementUnit } | { type: "X"; x: number; unit: SvgMeasurementUnit } | { type: "Y"; y: number; unit: SvgMeasurementUnit } | { type: "DX"; dx: number } | { type: "DY"; dy: number } | { type: "X1"; x1: number } | { type: "Y1"; y1: number } | { type: "X2"; x2: number } | { type: "Y2"; y2: number } | { type: "Radius"; radius: number } | { type: "Transform"; transform: SvgTransformAttribute } | { type: "PatternTransform"; patternTransform: SvgTransformAttribute } | { type: "Fill"; fill: SvgAttributeColor } | { type: "FillRule"; fillRule: WindingRule } | { type: "Filter"; filter: SvgAttributeFilter } | { type: "D"; d: SvgPathAttribute } | { type: "ClipPath"; clipPath: SvgElementId } | { type: "PatternUnits"; patternUnits: SvgUnits } | { type: "GradientUnits"; gradientUnits: SvgUnits } | { type: "FilterUnits"; filterUnits: SvgUnits } | { type: "PreserveAspectRatio"; preserveAspectRatio: string } | { type: "StopColor"; stopColor: SvgAttributeColor } | { type: "StopOpacity"; stopOpacity: number } | { type: "FloodOpacity"; floodOpacity: number } | { type: "ColorInterpolationFilters"; colorInterpolationFilters: string } | { type: "NumOctaves"; numOctaves: number } | { type: "BaseFrequency"; baseFrequency: number } | { type: "Mode"; mode: SvgAttributeMode } | { type: "K1"; k1: number } | { type: "K2"; k2: number } | { type: "Offset"; offset: number } | { type: "Slope"; slope: number } | { type: "StdDeviation"; stdDeviation: number } | { type: "TableValues"; tableValues: number[] } | { type: "In"; value: SvgAttributeIn } | { type: "In2"; value: SvgAttributeIn } | { type: "Type"; value: SvgAttributeType } | { type: "Result"; result: string } | { type: "Values"; values: SvgAttributeValues } | { type: "Operator"; operator: SvgAttributeOperator }
+export type SvgAttribute = { type: "Id"; id: SvgElementId } | { type: "Class"; class: string } | { type: "Href"; href: SvgHrefAttribute } | { type: "Width"; width: number; unit: SvgMeasurementUnit } | { type: "Height"; height: number; unit: SvgMeasurementUnit } | { type: "X"; x: number; unit: SvgMeasurementUnit } | { type: "Y"; y: number; unit: SvgMeasurementUnit } | { type: "DX"; dx: number } | { type: "DY"; dy: number } | { type: "X1"; x1: number } | { type: "Y1"; y1: number } | { type: "X2"; x2: number } | { type: "Y2"; y2: number } | { type: "Radius"; radius: number } | { type: "Transform"; transform: SvgTransformAttribute } | { type: "PatternTransform"; patternTransform: SvgTransformAttribute } | { type: "Fill"; fill: SvgAttributeColor } | { type: "FillRule"; fillRule: WindingRule } | { type: "Filter"; filter: SvgAttributeFilter } | { type: "D"; d: SvgPathAttribute } | { type: "ClipPath"; clipPath: SvgElementId } | { type: "ClipRule"; clipRule: WindingRule } | { type: "PatternUnits"; patternUnits: SvgUnits } | { type: "GradientUnits"; gradientUnits: SvgUnits } | { type: "FilterUnits"; filterUnits: SvgUnits } | { type: "PreserveAspectRatio"; preserveAspectRatio: string } | { type: "StopColor"; stopColor: SvgAttributeColor } | { type: "StopOpacity"; stopOpacity: number } | { type: "FloodOpacity"; floodOpacity: number } | { type: "ColorInterpolationFilters"; colorInterpolationFilters: string } | { type: "NumOctaves"; numOctaves: number } | { type: "BaseFrequency"; baseFrequency: number } | { type: "Mode"; mode: SvgAttributeMode } | { type: "K1"; k1: number } | { type: "K2"; k2: number } | { type: "K3"; k3: number } | { type: "Offset"; offset: number } | { type: "Slope"; slope: number } | { type: "Intercept"; intercept: number } | { type: "StdDeviation"; stdDeviation: number } | { type: "TableValues"; tableValues: number[] } | { type: "In"; value: SvgAttributeIn } | { type: "In2"; value: SvgAttributeIn } | { type: "Type"; value: SvgAttributeType } | { type: "Result"; result: string } | { type: "Values"; values: SvgAttributeValues } | { type: "Operator"; operator: SvgAttributeOperator }
 
 export type SvgAttributeColor = { RGB: { red: number; green: number; blue: number } } | { RGBA: { red: number; green: number; blue: number; alpha: number } } | { Reference: { id: SvgElementId } } | "None"
 
@@ -1599,14 +2063,14 @@ export type SvgAttributeIn = "SourceAlpha" | "SourceGraphic" | { Other: string }
 
 export type SvgAttributeMode = "Normal" | { Other: string }
 
-export type SvgAttributeOperator = "Dilate" | { Other: string }
+export type SvgAttributeOperator = "Dilate" | "Erode" | "Arithmetic" | { Other: string }
 
 /**
  * Emitted when an attribute of a SvgElement is removed.
  */
 export type SvgAttributeRemovedChange = { key: string }
 
-export type SvgAttributeType = "Matrix" | { Other: string }
+export type SvgAttributeType = "Matrix" | "Linear" | { Other: string }
 
 /**
  * Emitted when an attribute of an SvgElement is updated.
@@ -1657,7 +2121,11 @@ export type SvgElementDeletedChange = Record<string, never>
 
 export type SvgElementId = number
 
-export type SvgHrefAttribute = { Base64: { content: string; contentType: SvgHrefContentType } } | { Url: { url: string } }
+export type SvgHrefAttribute = { Base64: { content: string; contentType: SvgHrefContentType } } | { Url: { url: string } } | 
+/**
+ * Reference to another element of the SVG (e.g. for `use` elements).
+ */
+{ Reference: { id: SvgElementId } }
 
 export type SvgHrefContentType = "Jpeg" | "Png" | "Svg"
 
@@ -1699,6 +2167,10 @@ export type UpdateArtboardSizeInputEvent = { size: Size }
 
 export type UpdateArtboardViewportInputEvent = { viewport: Viewport }
 
+export type UpdateBackgroundBlurStyleInputEvent = { id: ReferenceIdOrEntity; blur?: Abs | null }
+
+export type UpdateBooleanOperationNodeInputEvent = { id: ReferenceIdOrEntity; operation?: BooleanOperation | null }
+
 export type UpdateDropShadowStyleInputEvent = { id: ReferenceIdOrEntity; color?: Color | null; position?: Vec2 | null; spread?: Abs | null; blur?: Abs | null }
 
 export type UpdateEllipseNodeInputEvent = { id: ReferenceIdOrEntity; startingAngle?: number | null; endingAngle?: number | null; innerRadiusRatio?: number | null }
@@ -1725,7 +2197,13 @@ export type UpdateFrameNodeInputEvent = { id: ReferenceIdOrEntity; clipContent?:
 
 export type UpdateGradientPaintInputEvent = { id: ReferenceIdOrEntity; variant?: GradientVariant | null; stops?: GradientColorStop[] | null }
 
-export type UpdateImagePaintInputEvent = { id: ReferenceIdOrEntity; scaleMode?: ImageScaleMode | null; imageId?: { idx: number; version: number } | null }
+export type UpdateImagePaintInputEvent = { id: ReferenceIdOrEntity; scaleMode?: ImageScaleMode | null; imageId?: { idx: number; version: number } | null; filters?: ImageFilters | null }
+
+export type UpdateInnerShadowStyleInputEvent = { id: ReferenceIdOrEntity; color?: Color | null; position?: Vec2 | null; spread?: Abs | null; blur?: Abs | null }
+
+export type UpdateInstanceNodeInputEvent = { id: ReferenceIdOrEntity; componentId?: ReferenceIdOrEntity | null; overrides?: InstanceOverride[] | null }
+
+export type UpdateLayerBlurStyleInputEvent = { id: ReferenceIdOrEntity; blur?: Abs | null }
 
 export type UpdatePolygonNodeInputEvent = { id: ReferenceIdOrEntity; pointCount?: number | null }
 
@@ -1733,10 +2211,84 @@ export type UpdateSolidPaintInputEvent = { id: ReferenceIdOrEntity; color?: Colo
 
 export type UpdateStarNodeInputEvent = { id: ReferenceIdOrEntity; pointCount?: number | null; innerRadiusRatio?: number | null }
 
-export type UpdateStorkeStyleInputEvent = { id: ReferenceIdOrEntity; paintId?: ReferenceIdOrEntity | null; width?: Abs | null }
+export type UpdateStorkeStyleInputEvent = { id: ReferenceIdOrEntity; paintId?: ReferenceIdOrEntity | null; width?: Abs | null; alignment?: StrokeAlignment | null; cap?: StrokeCap | null; join?: StrokeJoin | null; miterLimit?: number | null; dashArray?: Abs[] | null; dashOffset?: Abs | null }
 
 export type UpdateTextNodeInputEvent = { id: ReferenceIdOrEntity; text?: string | null; attributes?: TextAttributeInterval[] | null; lineWrap?: LineWrap | null; horizontalTextAlignment?: HorizontalTextAlignment | null; verticalTextAlignment?: VerticalTextAlignment | null; sizingMode?: TextSizingMode | null }
 
+/**
+ * Updates the value of a variable and re-resolves all properties bound to it.
+ */
+export type UpdateVariableInputEvent = { id: ReferenceId; value?: VariableValue | null; modes?: { [key in string]: VariableValue } | null }
+
+/**
+ * Switches the active variable mode (e.g. from "light" to "dark").
+ */
+export type UpdateVariableModeInputEvent = { mode?: string | null }
+
+/**
+ * A named value (design token) that properties can be bound to.
+ */
+export type Variable = { id: ReferenceId; name?: string | null; 
+/**
+ * The value used if no mode is active or the active mode doesn't define a value.
+ */
+value: VariableValue; 
+/**
+ * Values per mode (e.g. "light", "dark").
+ */
+modes?: { [key in string]: VariableValue } }
+
+/**
+ * Binds a property of a node, style or paint to a variable.
+ */
+export type VariableBinding = { targetId: ReferenceIdOrEntity; property: VariableProperty; variableId: ReferenceId }
+
+/**
+ * Property of a node, style or paint whose value can be bound to a variable.
+ * 
+ * Only the targets listed per property are supported,
+ * bindings to any other target (e.g. the color of a gradient stop or inner shadow)
+ * are ignored with a warning.
+ */
+export type VariableProperty = 
+/**
+ * Color of a solid paint or drop shadow style.
+ */
+"Color" | 
+/**
+ * Opacity of a node or style (between 0.0 and 1.0).
+ */
+"Opacity" | 
+/**
+ * Width of a node.
+ */
+"Width" | 
+/**
+ * Height of a node.
+ */
+"Height" | 
+/**
+ * Width of a stroke style.
+ */
+"StrokeWidth" | 
+/**
+ * Text of a text node.
+ */
+"Text" | 
+/**
+ * Font size of all attribute intervals of a text node.
+ */
+"FontSize" | 
+/**
+ * Font family of all attribute intervals of a text node.
+ */
+"FontFamily"
+
+/**
+ * Typed value of a variable (design token).
+ */
+export type VariableValue = { type: "Color"; value: Color } | { type: "Number"; value: number } | { type: "String"; value: string } | { type: "FontFamily"; value: FontFamily }
+
 export type Vec2 = [number, number]
 
 export type VectorNode = { id?: ReferenceId | null; path: string; windingRule: WindingRule; translation?: Vec2; rotationDeg?: Angle; size: Size; visible?: boolean; blendMode?: BlendMode; opacity?: Opacity; layoutElement?: LayoutElement; styles?: Style[] }
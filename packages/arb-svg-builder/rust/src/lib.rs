@@ -25,7 +25,7 @@ use dyn_arb_core::{
     },
     ArbCorePlugin,
 };
-use dyn_arb_dtif::DtifArtboard;
+use dyn_arb_dtif::{document::DtifDocument, migration::migrate, DtifArtboard};
 use dyn_arb_interaction::ArbInteractionPlugin;
 use dyn_arb_lua::{freeze::Frozen, script::LuaScriptError};
 use dyn_arb_svg_builder::{
@@ -33,6 +33,7 @@ use dyn_arb_svg_builder::{
 };
 use events::{SvgArbInputEvent, SvgArbOutputEvent};
 use modules::watch::{resources::watched_entities::WatchedEntitiesRes, ArbWatchPlugin};
+use serde::de::DeserializeOwned;
 use std::sync::mpsc::{channel, Receiver};
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
@@ -46,39 +47,24 @@ pub struct SvgArbHandle {
 #[wasm_bindgen]
 impl SvgArbHandle {
     pub fn create(js_dtif: JsValue, interactive: bool) -> Result<SvgArbHandle, JsValue> {
-        let mut dtif: DtifArtboard = serde_wasm_bindgen::from_value(js_dtif)?;
-        let mut app = App::new();
-
-        let (svg_builder_output_event_sender, svg_builder_output_event_receiver) =
-            channel::<SvgBuilderOutputEvent>();
-        let (output_event_sender, output_event_receiver) = channel::<SvgArbOutputEvent>();
-
-        // Register plugins
-        app.add_plugins((
-            ArbCorePlugin {
-                version: dtif.version,
-                size: dtif.size,
-                viewport: dtif.viewport,
-            },
-            ArbWatchPlugin {
-                output_event_sender,
-                interactive,
-            },
-            ArbSvgBuilderPlugin {
-                output_event_sender: svg_builder_output_event_sender,
-            },
-        ));
-        if interactive {
-            app.add_plugins(ArbInteractionPlugin);
-        }
-
-        dtif.send_into_world(&mut app.world);
+        let dtif: DtifArtboard = Self::migrate_dtif(js_dtif)?;
+        return Ok(Self::create_from_dtif(dtif, interactive));
+    }
 
-        return Ok(Self {
-            app,
-            svg_builder_output_event_receiver,
-            output_event_receiver,
-        });
+    #[wasm_bindgen(js_name = createFromDocument)]
+    pub fn create_from_document(
+        js_document: JsValue,
+        artboard: String,
+        interactive: bool,
+    ) -> Result<SvgArbHandle, JsValue> {
+        let document: DtifDocument = Self::migrate_dtif(js_document)?;
+        let dtif = document
+            .find_artboard_index(&artboard)
+            .and_then(|index| document.to_artboard(index))
+            .ok_or_else(|| {
+                JsValue::from_str(&format!("Document contains no artboard '{}'!", artboard))
+            })?;
+        return Ok(Self::create_from_dtif(dtif, interactive));
     }
 
     pub fn update(&mut self, js_input_events: JsValue) -> Result<JsValue, JsValue> {
@@ -224,6 +210,12 @@ impl SvgArbHandle {
         };
     }
 
+    #[wasm_bindgen(js_name = toDtif)]
+    pub fn to_dtif(&self) -> Result<JsValue, JsValue> {
+        let dtif = DtifArtboard::from_world(&self.app.world);
+        return Ok(serde_wasm_bindgen::to_value(&dtif)?);
+    }
+
     #[wasm_bindgen(js_name = toString)]
     pub fn to_string(&mut self) -> Option<String> {
         let mut result = String::new();
@@ -337,3 +329,82 @@ impl SvgArbHandle {
         log::warn!("[temp_dev_log] Not supported in this build! Build with feature 'tracing'.");
     }
 }
+
+impl SvgArbHandle {
+    fn migrate_dtif<T: DeserializeOwned>(js_dtif: JsValue) -> Result<T, JsValue> {
+        let raw_dtif: serde_json::Value = serde_wasm_bindgen::from_value(js_dtif)?;
+        return migrate_raw_dtif(raw_dtif).map_err(|err| JsValue::from_str(&err));
+    }
+
+    fn create_from_dtif(mut dtif: DtifArtboard, interactive: bool) -> SvgArbHandle {
+        let mut app = App::new();
+
+        let (svg_builder_output_event_sender, svg_builder_output_event_receiver) =
+            channel::<SvgBuilderOutputEvent>();
+        let (output_event_sender, output_event_receiver) = channel::<SvgArbOutputEvent>();
+
+        // Register plugins
+        app.add_plugins((
+            ArbCorePlugin {
+                version: dtif.version,
+                size: dtif.size,
+                viewport: dtif.viewport,
+                sub_pixel_layout: dtif.sub_pixel_layout,
+            },
+            ArbWatchPlugin {
+                output_event_sender,
+                interactive,
+            },
+            ArbSvgBuilderPlugin {
+                output_event_sender: svg_builder_output_event_sender,
+            },
+        ));
+        if interactive {
+            app.add_plugins(ArbInteractionPlugin);
+        }
+
+        dtif.send_into_world(&mut app.world);
+
+        return Self {
+            app,
+            svg_builder_output_event_receiver,
+            output_event_receiver,
+        };
+    }
+}
+
+/// Upgrades the raw DTIF artboard or document to the latest schema version
+/// before deserializing it.
+fn migrate_raw_dtif<T: DeserializeOwned>(mut raw_dtif: serde_json::Value) -> Result<T, String> {
+    migrate(&mut raw_dtif).map_err(|err| err.to_string())?;
+    return serde_json::from_value(raw_dtif).map_err(|err| err.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dyn_arb_bundles::properties::ArbVersion;
+
+    #[test]
+    fn test_migrate_raw_dtif() {
+        let dtif: DtifArtboard =
+            migrate_raw_dtif(serde_json::json!({ "size": [100, 100], "nodes": [] })).unwrap();
+        assert_eq!(dtif.version, Some(ArbVersion::LATEST));
+
+        let document: DtifDocument = migrate_raw_dtif(serde_json::json!({
+            "artboards": [{ "size": [100, 100], "nodes": [] }]
+        }))
+        .unwrap();
+        assert_eq!(document.version, Some(ArbVersion::LATEST));
+
+        let result = migrate_raw_dtif::<DtifArtboard>(serde_json::json!({
+            "version": "V999999",
+            "size": [100, 100],
+            "nodes": []
+        }));
+        assert_eq!(
+            result.unwrap_err(),
+            "DTIF version \"V999999\" is not supported!"
+        );
+    }
+}
@@ -32,6 +32,16 @@ function mapFigmaGradientTypeToDTIF(
 				type: 'Radial',
 				transform: mapFigmaTransformToMat3(paint.gradientTransform)
 			};
+		case 'GRADIENT_ANGULAR':
+			return {
+				type: 'Angular',
+				transform: mapFigmaTransformToMat3(paint.gradientTransform)
+			};
+		case 'GRADIENT_DIAMOND':
+			return {
+				type: 'Diamond',
+				transform: mapFigmaTransformToMat3(paint.gradientTransform)
+			};
 		default:
 			throw new UnsupportedFigmaPaintException(paint, nodeIds[0] as any);
 	}
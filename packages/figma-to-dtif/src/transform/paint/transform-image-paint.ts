@@ -10,10 +10,19 @@ export function transformImagePaint(
 	return {
 		type: 'Image',
 		imageId: { type: 'ReferenceId', referenceId: `a${imageId}` },
-		scaleMode: resolveScaleMode(paint)
+		scaleMode: resolveScaleMode(paint),
+		filters: resolveFilters(paint)
 	};
 }
 
+function resolveFilters(paint: ImagePaint): ARB.ImagePaint['filters'] {
+	if (paint.filters == null) {
+		return null;
+	}
+	const { exposure, contrast, saturation, temperature, tint } = paint.filters;
+	return { exposure, contrast, saturation, temperature, tint };
+}
+
 function resolveScaleMode(paint: ImagePaint): ARB.ImagePaint['scaleMode'] {
 	switch (paint.scaleMode) {
 		case 'CROP':
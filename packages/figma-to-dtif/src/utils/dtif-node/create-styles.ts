@@ -47,6 +47,8 @@ export function createDtifStyles(
 		}
 	}
 
+	// Inner shadows are drawn on top of the fills while the other effects are drawn behind them
+	const innerShadowStyles: ARB.Style[] = [];
 	const effectStyles: ARB.Style[] = [];
 	for (const effect of effects) {
 		switch (effect.variant.type) {
@@ -64,10 +66,48 @@ export function createDtifStyles(
 				});
 				break;
 			}
+			case 'INNER_SHADOW': {
+				const innerShadow = effect.variant;
+				innerShadowStyles.push({
+					type: 'InnerShadow',
+					color: mapFigmaRGBToDtif(innerShadow.color),
+					position: [innerShadow.offset.x, innerShadow.offset.y],
+					blur: innerShadow.radius,
+					spread: innerShadow.spread,
+					visible: innerShadow.visible,
+					blendMode: mapFigmaBlendModeToDtif(innerShadow.blendMode),
+					opacity: innerShadow.color.a
+				});
+				break;
+			}
+			case 'LAYER_BLUR': {
+				const layerBlur = effect.variant;
+				effectStyles.push({
+					type: 'LayerBlur',
+					blur: layerBlur.radius,
+					visible: layerBlur.visible
+				});
+				break;
+			}
+			case 'BACKGROUND_BLUR': {
+				const backgroundBlur = effect.variant;
+				effectStyles.push({
+					type: 'BackgroundBlur',
+					blur: backgroundBlur.radius,
+					visible: backgroundBlur.visible
+				});
+				break;
+			}
 			default:
-				console.warn(`Unsuported stroke align: ${effect.variant.type}`);
+				console.warn(`Unsuported effect type: ${(effect.variant as Effect).type}`);
 		}
 	}
 
-	return [...centerStrokeStyles, ...fillStyles, ...outsideStrokeStyles, ...effectStyles];
+	return [
+		...centerStrokeStyles,
+		...innerShadowStyles,
+		...fillStyles,
+		...outsideStrokeStyles,
+		...effectStyles
+	];
 }
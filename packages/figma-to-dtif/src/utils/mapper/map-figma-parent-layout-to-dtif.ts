@@ -12,6 +12,7 @@ export function mapFigmaParentLayoutToDtif(
 
 	return {
 		flexDirection,
+		flexWrap: node.layoutWrap === 'WRAP' ? 'Wrap' : 'NoWrap',
 		alignItems: mapCounterAxisAlignItemsToDtif(node.counterAxisAlignItems),
 		justifyContent: mapPrimaryAxisAlignItemsToDtif(node.primaryAxisAlignItems),
 		padding: {
@@ -11,6 +11,6 @@ export function mapFigmaTextAlignHorizontalToDtif(
 		case 'CENTER':
 			return 'Center';
 		case 'JUSTIFIED':
-			return 'Left'; // TODO
+			return 'Justified';
 	}
 }